@@ -0,0 +1,203 @@
+// Automatic backups taken before content files are overwritten
+// Backups live in .hugo-bros/backups/<original-relative-path>.<timestamp>.bak
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const BACKUP_EXTENSION: &str = "bak";
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupEntry {
+    pub id: String,
+    pub file_id: String,
+    /// Milliseconds since the epoch
+    pub created_at: i64,
+    pub size: u64,
+}
+
+fn backups_dir(project_path: &Path) -> PathBuf {
+    project_path.join(".hugo-bros").join("backups")
+}
+
+/// Parse `<file-name>.<millis>.bak` into the original file name and timestamp
+fn parse_backup_name(name: &str) -> Option<(&str, i64)> {
+    let stem = name.strip_suffix(BACKUP_EXTENSION)?.strip_suffix('.')?;
+    let (file_name, millis) = stem.rsplit_once('.')?;
+    Some((file_name, millis.parse().ok()?))
+}
+
+/// Backups of one file, newest first, as (path, timestamp in millis)
+fn backups_for(project_path: &Path, relative: &Path) -> Vec<(PathBuf, i64)> {
+    let Some(file_name) = relative.file_name().and_then(|n| n.to_str()) else {
+        return Vec::new();
+    };
+    let dir = match relative.parent() {
+        Some(parent) => backups_dir(project_path).join(parent),
+        None => backups_dir(project_path),
+    };
+
+    let Ok(entries) = fs::read_dir(&dir) else {
+        return Vec::new();
+    };
+
+    let mut backups: Vec<(PathBuf, i64)> = entries
+        .filter_map(|e| e.ok())
+        .filter_map(|entry| {
+            let name = entry.file_name().to_str()?.to_string();
+            let (original, millis) = parse_backup_name(&name)?;
+            (original == file_name).then(|| (entry.path(), millis))
+        })
+        .collect();
+
+    backups.sort_by_key(|(_, millis)| std::cmp::Reverse(*millis));
+    backups
+}
+
+/// Copy an existing file into the backups folder, keeping at most
+/// `max_backups` copies of it. Missing files (new documents) are skipped.
+pub fn backup_file(project_path: &Path, file_path: &Path, max_backups: usize) -> Result<(), String> {
+    if max_backups == 0 || !file_path.is_file() {
+        return Ok(());
+    }
+    let Ok(relative) = file_path.strip_prefix(project_path) else {
+        return Ok(());
+    };
+    let file_name = relative
+        .file_name()
+        .and_then(|n| n.to_str())
+        .ok_or("Invalid file name")?;
+
+    let timestamp = chrono::Utc::now().timestamp_millis();
+    let dest = backups_dir(project_path).join(relative).with_file_name(format!(
+        "{}.{}.{}",
+        file_name, timestamp, BACKUP_EXTENSION
+    ));
+
+    if let Some(parent) = dest.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create backup directory: {}", e))?;
+    }
+
+    fs::copy(file_path, &dest)
+        .map_err(|e| format!("Failed to back up file: {}", e))?;
+
+    for (old, _) in backups_for(project_path, relative).into_iter().skip(max_backups) {
+        let _ = fs::remove_file(old);
+    }
+
+    Ok(())
+}
+
+/// List backups of a file (given relative to the project), newest first
+pub fn list_backups(project_path: &Path, file_id: &Path) -> Vec<BackupEntry> {
+    let file_id_str = file_id.to_string_lossy().replace('\\', "/");
+    let root = backups_dir(project_path);
+
+    backups_for(project_path, file_id)
+        .into_iter()
+        .map(|(path, millis)| BackupEntry {
+            id: path
+                .strip_prefix(&root)
+                .map(|p| p.to_string_lossy().replace('\\', "/"))
+                .unwrap_or_default(),
+            file_id: file_id_str.clone(),
+            created_at: millis,
+            size: fs::metadata(&path).map(|m| m.len()).unwrap_or(0),
+        })
+        .collect()
+}
+
+/// Copy a backup over its original file. The current contents are backed up
+/// first so a restore can itself be undone.
+/// `backup_id` must already be validated as a safe relative path.
+pub fn restore_backup(project_path: &Path, backup_id: &Path, max_backups: usize) -> Result<String, String> {
+    let source = backups_dir(project_path).join(backup_id);
+    if !source.is_file() {
+        return Err("Backup not found".to_string());
+    }
+
+    let (file_name, _) = backup_id
+        .file_name()
+        .and_then(|n| n.to_str())
+        .and_then(parse_backup_name)
+        .ok_or("Invalid backup id")?;
+    let original_relative = backup_id.with_file_name(file_name);
+    let original = project_path.join(&original_relative);
+
+    // Read before backing up, since that may prune the backup being restored
+    let contents = fs::read(&source)
+        .map_err(|e| format!("Failed to read backup: {}", e))?;
+
+    backup_file(project_path, &original, max_backups)?;
+
+    if let Some(parent) = original.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create directory: {}", e))?;
+    }
+
+    crate::files::write_file_atomic(&original, &contents)
+        .map_err(|e| format!("Failed to restore backup: {}", e))?;
+
+    Ok(original_relative.to_string_lossy().replace('\\', "/"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Backups are named by millisecond, so keep them from sharing a name
+    fn backup_later(project_path: &Path, file_path: &Path, max_backups: usize) {
+        std::thread::sleep(std::time::Duration::from_millis(5));
+        backup_file(project_path, file_path, max_backups).unwrap();
+    }
+
+    #[test]
+    fn backup_skips_new_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("content/new.md");
+
+        backup_file(dir.path(), &file, 3).unwrap();
+        assert!(!backups_dir(dir.path()).exists());
+    }
+
+    #[test]
+    fn backup_prunes_to_max_backups() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("content/posts/hello.md");
+        fs::create_dir_all(file.parent().unwrap()).unwrap();
+
+        for version in ["one", "two", "three"] {
+            fs::write(&file, version).unwrap();
+            backup_later(dir.path(), &file, 2);
+        }
+
+        let backups = list_backups(dir.path(), Path::new("content/posts/hello.md"));
+        assert_eq!(backups.len(), 2);
+        assert_eq!(backups[0].file_id, "content/posts/hello.md");
+        let newest = backups_dir(dir.path()).join(&backups[0].id);
+        assert_eq!(fs::read_to_string(newest).unwrap(), "three");
+    }
+
+    #[test]
+    fn restore_backs_up_the_current_version_first() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("content/about.md");
+        fs::create_dir_all(file.parent().unwrap()).unwrap();
+        fs::write(&file, "old").unwrap();
+        backup_later(dir.path(), &file, 5);
+        fs::write(&file, "current").unwrap();
+
+        let backup_id = list_backups(dir.path(), Path::new("content/about.md"))[0].id.clone();
+        std::thread::sleep(std::time::Duration::from_millis(5));
+        let restored = restore_backup(dir.path(), Path::new(&backup_id), 5).unwrap();
+
+        assert_eq!(restored, "content/about.md");
+        assert_eq!(fs::read_to_string(&file).unwrap(), "old");
+        let backups = list_backups(dir.path(), Path::new("content/about.md"));
+        assert_eq!(backups.len(), 2);
+        let newest = backups_dir(dir.path()).join(&backups[0].id);
+        assert_eq!(fs::read_to_string(newest).unwrap(), "current");
+    }
+}
@@ -1,14 +1,21 @@
 // Tauri commands for frontend-backend communication
 
-use crate::hugo::HugoProject;
-use crate::markdown::{Draft, ImageInfo, Page, Post};
+use crate::config::SlugMode;
+use crate::errors::{localized, ErrorCode};
+use crate::hugo::{ContentState, HugoProject};
+use crate::markdown::{BundleResource, Draft, ImageInfo, Page, Post};
 use crate::frontmatter_config::{
-    generate_frontmatter_config, load_frontmatter_config, FrontmatterConfig,
+    frontmatter_config_path, generate_frontmatter_config, load_frontmatter_config,
+    validate_config as validate_frontmatter_config, validate_frontmatter as check_frontmatter,
+    merge_frontmatter_config, write_frontmatter_config, FrontmatterConfig, FrontmatterIssue,
+    IssueSeverity,
 };
+use rayon::prelude::*;
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::{Component, Path, PathBuf};
 use tauri::command;
-use tauri::AppHandle;
+use tauri::{AppHandle, Emitter};
 
 // ====================
 // Project Commands
@@ -56,18 +63,185 @@ pub async fn select_project_folder(app: AppHandle) -> Result<String, String> {
     }
 }
 
+/// Show a project file or folder in the OS file manager, selected in its
+/// parent folder where the platform supports it. `path` is relative to the
+/// project or absolute; it must be inside the project.
 #[command]
-pub fn get_project_config(project_path: String) -> Result<HugoConfig, String> {
+pub fn reveal_in_file_manager(project_path: String, path: String) -> Result<(), String> {
+    let target = resolve_project_path(Path::new(&project_path), &path)?;
+    if !target.exists() {
+        return Err("Path not found".to_string());
+    }
+
+    tauri_plugin_opener::reveal_item_in_dir(&target)
+        .map_err(|e| format!("Failed to open file manager: {}", e))
+}
+
+/// Effective site config. `environment` overlays `config/<environment>/`
+/// on top of `config/_default/`, like `hugo --environment`.
+#[command]
+pub fn get_project_config(
+    project_path: String,
+    environment: Option<String>,
+) -> Result<HugoConfig, String> {
     let project = HugoProject::new(PathBuf::from(&project_path));
+    let config_value = load_hugo_config(&project, environment.as_deref())?;
+    Ok(HugoConfig::from_value(config_value))
+}
+
+/// Write site settings back to the main config file, keeping its format.
+///
+/// The typed fields (title, baseURL, ...) take precedence over `raw`; keys the
+/// app doesn't model are carried over from `raw`. `config` should come from
+/// `get_project_config` without an environment, since keys that live in other
+/// `config/_default/` files are left out. The file is re-serialized, so
+/// comments and formatting in it are not preserved.
+#[command]
+pub fn save_project_config(project_path: String, config: HugoConfig) -> Result<(), String> {
+    let project_root = PathBuf::from(&project_path);
+    let project = HugoProject::new(project_root.clone());
     let config_path = project
         .find_config_path()
-        .ok_or("Hugo config not found (config.* or hugo.*)".to_string())?;
+        .ok_or_else(|| localized(ErrorCode::HugoConfigNotFound))?;
 
     let content = fs::read_to_string(&config_path)
         .map_err(|e| format!("Failed to read config: {}", e))?;
+    let original = parse_hugo_config(&config_path, &content)?;
 
-    let config_value = parse_hugo_config(&config_path, &content)?;
-    Ok(HugoConfig::from_value(config_value))
+    let serde_json::Value::Object(mut table) = config.raw.clone() else {
+        return Err("Config must be an object".to_string());
+    };
+
+    // Keys owned by other split config files stay where they are
+    for key in split_config_keys(&project, &config_path)? {
+        if original.get(&key).is_none() {
+            table.shift_remove(&key);
+        }
+    }
+
+    set_config_string(&mut table, &["title"], config.title);
+    set_config_string(&mut table, &["baseURL", "baseUrl", "base_url"], config.base_url);
+    set_config_string(&mut table, &["languageCode", "language_code"], config.language_code);
+    set_config_string(
+        &mut table,
+        &["defaultContentLanguage", "default_content_language"],
+        config.default_content_language,
+    );
+    // `theme` may be a list of themes, which the typed field can't express
+    if config.theme.is_some() || table.get("theme").is_some_and(|v| v.is_string()) {
+        set_config_string(&mut table, &["theme"], config.theme);
+    }
+
+    let contents = serialize_hugo_config(&config_path, serde_json::Value::Object(table))?;
+    write_content_file(&project_root, &config_path, &contents)
+}
+
+/// Settings from `.hugo-bros/settings.json`; defaults when the file is absent
+#[command]
+pub fn get_project_settings(project_path: String) -> Result<crate::project_settings::ProjectSettings, String> {
+    crate::project_settings::ProjectSettings::load(Path::new(&project_path))
+}
+
+#[command]
+pub fn save_project_settings(
+    project_path: String,
+    mut settings: crate::project_settings::ProjectSettings,
+) -> Result<(), String> {
+    settings.posts_section = normalize_posts_section(settings.posts_section)?;
+    settings.save(Path::new(&project_path))
+}
+
+/// Set the folder under content/ that holds posts, e.g. `blog`. `None`
+/// goes back to detecting `posts` or `post`.
+#[command]
+pub fn set_posts_section(project_path: String, section: Option<String>) -> Result<(), String> {
+    let section = normalize_posts_section(section)?;
+
+    let project_root = Path::new(&project_path);
+    let mut settings = crate::project_settings::ProjectSettings::load(project_root)?;
+    settings.posts_section = section;
+    settings.save(project_root)
+}
+
+fn normalize_posts_section(section: Option<String>) -> Result<Option<String>, String> {
+    let section = section
+        .map(|section| section.trim().trim_matches('/').to_string())
+        .filter(|section| !section.is_empty());
+    if let Some(section) = &section {
+        validate_relative_path(section)?;
+    }
+    Ok(section)
+}
+
+#[command]
+pub fn get_params(project_path: String) -> Result<serde_json::Value, String> {
+    Ok(get_project_config(project_path, None)?.params())
+}
+
+#[command]
+pub fn get_menus(project_path: String) -> Result<Vec<MenuEntry>, String> {
+    Ok(get_project_config(project_path, None)?.menus())
+}
+
+/// Pre-flight check of the site config for problems Hugo would only report
+/// at build time. An empty list means nothing was found.
+#[command]
+pub fn validate_project_config(project_path: String) -> Result<Vec<ConfigIssue>, String> {
+    let project = HugoProject::new(PathBuf::from(&project_path));
+    let mut issues = Vec::new();
+
+    let raw = match load_hugo_config(&project, None) {
+        Ok(raw) => raw,
+        Err(e) => {
+            issues.push(ConfigIssue::error(None, &e));
+            return Ok(issues);
+        }
+    };
+    let config = HugoConfig::from_value(raw);
+
+    match config.base_url.as_deref().map(str::trim) {
+        None | Some("") => issues.push(ConfigIssue::warning(
+            Some("baseURL"),
+            "baseURL is not set; absolute links and feeds will be wrong",
+        )),
+        Some(url) if !url.starts_with("http://") && !url.starts_with("https://") && url != "/" => {
+            issues.push(ConfigIssue::warning(
+                Some("baseURL"),
+                "baseURL should be an absolute URL such as https://example.com/",
+            ))
+        }
+        _ => {}
+    }
+
+    // Themes can be a single name or a list; module paths (with a slash)
+    // are fetched by Hugo Modules and not expected under themes/
+    let themes: Vec<String> = match config.raw.get("theme") {
+        Some(serde_json::Value::String(theme)) => vec![theme.clone()],
+        Some(serde_json::Value::Array(items)) => items
+            .iter()
+            .filter_map(|v| v.as_str().map(str::to_string))
+            .collect(),
+        _ => Vec::new(),
+    };
+    for theme in themes.iter().filter(|t| !t.contains('/')) {
+        if !project.path.join("themes").join(theme).is_dir() {
+            issues.push(ConfigIssue::error(
+                Some("theme"),
+                &format!("Theme '{}' not found in themes/", theme),
+            ));
+        }
+    }
+
+    if let Some(default_language) = &config.default_content_language {
+        if !config.languages.is_empty() && !config.languages.contains(&default_language.to_lowercase()) {
+            issues.push(ConfigIssue::warning(
+                Some("defaultContentLanguage"),
+                &format!("defaultContentLanguage '{}' is not listed in languages", default_language),
+            ));
+        }
+    }
+
+    Ok(issues)
 }
 
 #[command]
@@ -76,121 +250,448 @@ pub fn get_frontmatter_config(project_path: String) -> Result<FrontmatterConfig,
 }
 
 #[command]
-pub fn generate_frontmatter_config_command(project_path: String) -> Result<FrontmatterConfig, String> {
-    let config_path = Path::new(&project_path)
-        .join(".hugo-bros")
-        .join("frontmatter-config.json");
-
-    if config_path.exists() {
+pub fn generate_frontmatter_config_command(
+    project_path: String,
+    include_drafts: Option<bool>,
+) -> Result<FrontmatterConfig, String> {
+    if frontmatter_config_path(Path::new(&project_path)).exists() {
         return Err("frontmatter-config.json already exists".to_string());
     }
 
-    let config = generate_frontmatter_config(Path::new(&project_path))?;
+    let config = generate_frontmatter_config(Path::new(&project_path), include_drafts.unwrap_or(true))?;
+    write_frontmatter_config(Path::new(&project_path), &config)?;
 
-    if let Some(parent) = config_path.parent() {
-        fs::create_dir_all(parent)
-            .map_err(|e| format!("Failed to create .hugo-bros directory: {}", e))?;
+    Ok(config)
+}
+
+/// Regenerate the config from the current content, e.g. after new custom
+/// fields were added to posts. With `merge`, customizations of fields that
+/// are still in use are kept; otherwise the config is replaced. The previous
+/// config is backed up to `frontmatter-config.json.bak`.
+#[command]
+pub fn regenerate_frontmatter_config(
+    project_path: String,
+    merge: bool,
+    include_drafts: Option<bool>,
+) -> Result<FrontmatterConfig, String> {
+    let project_root = Path::new(&project_path);
+    let config_path = frontmatter_config_path(project_root);
+
+    let config = regenerated_frontmatter_config(project_root, merge, include_drafts.unwrap_or(true))?;
+    if config_path.exists() {
+        fs::copy(&config_path, config_path.with_extension("json.bak"))
+            .map_err(|e| format!("Failed to back up frontmatter config: {}", e))?;
     }
 
-    let content = serde_json::to_string_pretty(&config)
-        .map_err(|e| format!("Failed to serialize frontmatter config: {}", e))?;
-    fs::write(&config_path, content)
-        .map_err(|e| format!("Failed to write frontmatter config: {}", e))?;
+    write_frontmatter_config(project_root, &config)?;
+
+    Ok(config)
+}
+
+/// The config `regenerate_frontmatter_config` would write, without writing
+/// it, so it can be reviewed first. Works whether or not a config exists.
+#[command]
+pub fn preview_frontmatter_config(
+    project_path: String,
+    merge: bool,
+    include_drafts: Option<bool>,
+) -> Result<FrontmatterConfig, String> {
+    regenerated_frontmatter_config(Path::new(&project_path), merge, include_drafts.unwrap_or(true))
+}
 
+fn regenerated_frontmatter_config(
+    project_root: &Path,
+    merge: bool,
+    include_drafts: bool,
+) -> Result<FrontmatterConfig, String> {
+    let config = generate_frontmatter_config(project_root, include_drafts)?;
+    if merge && frontmatter_config_path(project_root).exists() {
+        return Ok(merge_frontmatter_config(&load_frontmatter_config(project_root)?, config));
+    }
     Ok(config)
 }
 
+/// Validate and save an edited config. Returns the problems found; the file
+/// is only written when none of them are errors.
+#[command]
+pub fn save_frontmatter_config(
+    project_path: String,
+    mut config: FrontmatterConfig,
+) -> Result<Vec<FrontmatterIssue>, String> {
+    let issues = validate_frontmatter_config(&config);
+    if issues.iter().any(|issue| issue.severity == IssueSeverity::Error) {
+        return Ok(issues);
+    }
+
+    config.is_default = false;
+    write_frontmatter_config(Path::new(&project_path), &config)?;
+
+    Ok(issues)
+}
+
+#[command]
+pub fn validate_frontmatter(
+    project_path: String,
+    frontmatter: crate::markdown::Frontmatter,
+) -> Result<Vec<FrontmatterIssue>, String> {
+    let config = load_frontmatter_config(Path::new(&project_path))?;
+    Ok(check_frontmatter(Path::new(&project_path), &config, &frontmatter))
+}
+
+/// Reject frontmatter with validation errors when the project opts into strict mode
+fn ensure_valid_frontmatter(
+    project_path: &Path,
+    frontmatter: &crate::markdown::Frontmatter,
+) -> Result<(), String> {
+    let config = load_frontmatter_config(project_path)?;
+    if !config.strict_validation {
+        return Ok(());
+    }
+
+    let errors: Vec<String> = check_frontmatter(project_path, &config, frontmatter)
+        .into_iter()
+        .filter(|issue| issue.severity == IssueSeverity::Error)
+        .map(|issue| format!("{}: {}", issue.field, issue.message))
+        .collect();
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(format!("Frontmatter validation failed: {}", errors.join("; ")))
+    }
+}
+
 // ====================
 // Posts Commands
 // ====================
 
 #[command]
-pub fn list_posts(project_path: String) -> Result<Vec<Post>, String> {
+pub fn list_posts(
+    project_path: String,
+    offset: Option<usize>,
+    limit: Option<usize>,
+    metadata_only: Option<bool>,
+    sort: Option<SortSpec>,
+    filter: Option<PostFilter>,
+) -> Result<PostList, String> {
+    let filter = filter.unwrap_or_default();
+    let mut posts = collect_posts(&project_path, metadata_only.unwrap_or(false), &filter)?;
+    if let Some(sort) = sort {
+        posts = sort_content(posts, sort);
+    }
+    let total = posts.len();
+    let posts = posts
+        .into_iter()
+        .skip(offset.unwrap_or(0))
+        .take(limit.unwrap_or(usize::MAX))
+        .collect();
+
+    Ok(PostList { posts, total })
+}
+
+/// All published posts without their bodies, newest first. Only the
+/// frontmatter block of each file is read, which keeps list views cheap on
+/// sites with long posts; use `list_posts` when the full objects are needed.
+#[command]
+pub fn list_posts_metadata(project_path: String) -> Result<Vec<crate::markdown::PostMeta>, String> {
+    let posts = collect_posts(&project_path, true, &PostFilter::default())?;
+    Ok(posts.into_iter().map(Into::into).collect())
+}
+
+/// Published posts sharing the most tags and categories with `post_id`,
+/// best match first (newer posts win ties). Posts sharing nothing are left
+/// out. `limit` defaults to 5.
+#[command]
+pub fn get_related_posts(
+    project_path: String,
+    post_id: String,
+    limit: Option<usize>,
+) -> Result<Vec<RelatedPost>, String> {
+    let source_path = resolve_project_path(Path::new(&project_path), &post_id)
+        .map_err(|_| localized(ErrorCode::PostNotFound))?;
+    if !source_path.is_file() {
+        return Err(localized(ErrorCode::PostNotFound));
+    }
+    let source = crate::content_cache::load_post(&source_path, Path::new(&project_path), true)?;
+
+    // Terms compare case-insensitively, as Hugo lowercases them
+    let terms = |frontmatter: &crate::markdown::Frontmatter| -> HashSet<String> {
+        frontmatter
+            .tags
+            .iter()
+            .map(|tag| format!("tag:{}", tag.to_lowercase()))
+            .chain(
+                frontmatter
+                    .categories
+                    .iter()
+                    .map(|category| format!("category:{}", category.to_lowercase())),
+            )
+            .collect()
+    };
+    let source_terms = terms(&source.frontmatter);
+    if source_terms.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut related: Vec<RelatedPost> = collect_posts(&project_path, true, &PostFilter::default())?
+        .into_iter()
+        .filter(|post| post.id != source.id)
+        .filter_map(|post| {
+            let score = terms(&post.frontmatter).intersection(&source_terms).count();
+            (score > 0).then_some(RelatedPost {
+                id: post.id,
+                title: post.title,
+                score,
+            })
+        })
+        .collect();
+
+    // Stable, so equal scores keep collect_posts' newest-first order
+    related.sort_by_key(|post| std::cmp::Reverse(post.score));
+    related.truncate(limit.unwrap_or(5));
+    Ok(related)
+}
+
+/// Fields shared by posts and pages that listings can sort on
+trait SortableContent {
+    fn id(&self) -> &str;
+    fn title(&self) -> &str;
+    fn frontmatter(&self) -> &crate::markdown::Frontmatter;
+    fn modified_at(&self) -> i64;
+}
+
+impl SortableContent for Post {
+    fn id(&self) -> &str {
+        &self.id
+    }
+    fn title(&self) -> &str {
+        &self.title
+    }
+    fn frontmatter(&self) -> &crate::markdown::Frontmatter {
+        &self.frontmatter
+    }
+    fn modified_at(&self) -> i64 {
+        self.modified_at
+    }
+}
+
+impl SortableContent for Page {
+    fn id(&self) -> &str {
+        &self.id
+    }
+    fn title(&self) -> &str {
+        &self.title
+    }
+    fn frontmatter(&self) -> &crate::markdown::Frontmatter {
+        &self.frontmatter
+    }
+    fn modified_at(&self) -> i64 {
+        self.modified_at
+    }
+}
+
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+enum SortValue {
+    Text(String),
+    Time(i64),
+    Number(i64),
+}
+
+/// Sort by the requested key; ties are broken by id, always ascending
+fn sort_content<T: SortableContent>(items: Vec<T>, sort: SortSpec) -> Vec<T> {
+    let mut keyed: Vec<(SortValue, T)> = items
+        .into_iter()
+        .map(|item| {
+            let value = match sort.key {
+                SortKey::Title => SortValue::Text(item.title().to_lowercase()),
+                SortKey::Modified => SortValue::Time(item.modified_at()),
+                // Hugo treats a weight of 0 as unset
+                SortKey::Weight => SortValue::Number(
+                    item.frontmatter().weight.filter(|w| *w != 0).unwrap_or(i64::MAX),
+                ),
+                // Unparseable dates fall back to mtime, as in the default order
+                SortKey::Date => SortValue::Time(
+                    crate::markdown::parse_frontmatter_date(&item.frontmatter().date)
+                        .map(|date| date.timestamp_millis())
+                        .unwrap_or(item.modified_at()),
+                ),
+            };
+            (value, item)
+        })
+        .collect();
+
+    keyed.sort_by(|(a, a_item), (b, b_item)| {
+        let order = if sort.ascending { a.cmp(b) } else { b.cmp(a) };
+        order.then_with(|| a_item.id().cmp(b_item.id()))
+    });
+
+    keyed.into_iter().map(|(_, item)| item).collect()
+}
+
+/// Posts matching `filter` (published only by default), newest first. With
+/// `metadata_only`, bodies are not read (see `Post::from_file_metadata`).
+fn collect_posts(
+    project_path: &str,
+    metadata_only: bool,
+    filter: &PostFilter,
+) -> Result<Vec<Post>, String> {
     let project = HugoProject::new(PathBuf::from(&project_path));
     let posts_dir = project.get_posts_dir();
-    let drafts_dir = project.get_content_dir().join("drafts");
 
     if !posts_dir.exists() {
         return Ok(Vec::new());
     }
 
-    let mut posts = Vec::new();
+    let languages = ContentLanguages::load(project_path);
 
-    for entry in walkdir::WalkDir::new(&posts_dir)
+    // Walk first, then read and parse the files in parallel
+    let paths: Vec<PathBuf> = walkdir::WalkDir::new(&posts_dir)
         .max_depth(4)
         .into_iter()
         .filter_map(|e| e.ok())
-    {
-        let path = entry.path();
-        if path.is_file() && path.extension().and_then(|s| s.to_str()) == Some("md") {
-            if path.file_name().and_then(|s| s.to_str()) == Some("_index.md") {
-                continue;
-            }
-            if drafts_dir.exists() && path.starts_with(&drafts_dir) {
-                continue;
-            }
-            match Post::from_file(path, Path::new(&project_path)) {
-                Ok(post) => {
-                    if post.frontmatter.draft.unwrap_or(false) {
-                        continue;
+        .map(|entry| entry.into_path())
+        .filter(|path| {
+            path.is_file()
+                && path.extension().and_then(|s| s.to_str()) == Some("md")
+                && path.file_name().and_then(|s| s.to_str()) != Some("_index.md")
+        })
+        .collect();
+
+    let mut posts: Vec<Post> = paths
+        .par_iter()
+        .filter_map(|path| {
+            match crate::content_cache::load_post(path, Path::new(project_path), metadata_only) {
+                Ok(mut post) => {
+                    let state = project.classify_content(path, &post.frontmatter);
+                    if !filter.matches(state, &post.frontmatter) {
+                        return None;
                     }
-                    posts.push(post);
+                    post.language = Some(languages.language_of(path));
+                    Some(post)
                 },
-                Err(e) => eprintln!("Failed to parse post {:?}: {}", path, e),
+                Err(e) => {
+                    eprintln!("Failed to parse post {:?}: {}", path, e);
+                    None
+                }
             }
-        }
-    }
+        })
+        .collect();
 
-    // Sort by date (newest first)
-    posts.sort_by(|a, b| b.modified_at.cmp(&a.modified_at));
+    // Sort by post date (newest first), falling back to mtime for unparseable
+    // dates; the id breaks ties so the order doesn't depend on the walk
+    posts.sort_by_cached_key(|post| {
+        let timestamp = crate::markdown::parse_frontmatter_date(&post.date)
+            .map(|date| date.timestamp_millis())
+            .unwrap_or(post.modified_at);
+        (std::cmp::Reverse(timestamp), post.id.clone())
+    });
 
     Ok(posts)
 }
 
 #[command]
 pub fn get_post(project_path: String, post_id: String) -> Result<Post, String> {
-    let file_path = Path::new(&project_path).join(&post_id);
+    let file_path = resolve_project_path(Path::new(&project_path), &post_id)
+        .map_err(|_| localized(ErrorCode::PostNotFound))?;
 
-    if !file_path.exists() {
-        return Err("Post not found".to_string());
+    if !file_path.is_file() {
+        return Err(localized(ErrorCode::PostNotFound));
     }
 
-    Post::from_file(&file_path, Path::new(&project_path))
+    let mut post = Post::from_file(&file_path, Path::new(&project_path))?;
+    post.language = Some(ContentLanguages::load(&project_path).language_of(&file_path));
+    Ok(post)
 }
 
 #[command]
-pub fn save_post(_project_path: String, post: Post) -> Result<(), String> {
-    let file_path = Path::new(&post.file_path);
+pub fn save_post(
+    project_path: String,
+    post: Post,
+    expected_modified_at: Option<i64>,
+) -> Result<i64, String> {
+    let file_path = resolve_project_path(Path::new(&project_path), &post.file_path)?;
 
-    let markdown = post.to_markdown()?;
+    ensure_valid_frontmatter(Path::new(&project_path), &post.frontmatter)?;
+    ensure_unchanged_on_disk(&file_path, expected_modified_at)?;
 
-    fs::write(file_path, markdown)
+    let markdown = post.to_markdown(final_newline())?;
+
+    if post.encoding.is_some() {
+        ensure_utf8_on_disk(&file_path)?;
+    }
+    replace_content_file(Path::new(&project_path), &file_path, &markdown)
         .map_err(|e| format!("Failed to save post: {}", e))?;
 
-    Ok(())
+    Ok(file_modified_at(&file_path))
+}
+
+/// Line diff between a post's body on disk and the editor's copy, so the UI
+/// can show what an external change would overwrite. An empty `changes`
+/// means they match.
+#[command]
+pub fn diff_post(project_path: String, post_id: String, editor_content: String) -> Result<PostDiff, String> {
+    let file_path = resolve_project_path(Path::new(&project_path), &post_id)
+        .map_err(|_| localized(ErrorCode::PostNotFound))?;
+
+    if !file_path.is_file() {
+        return Err(localized(ErrorCode::PostNotFound));
+    }
+
+    let post = Post::from_file(&file_path, Path::new(&project_path))?;
+
+    Ok(PostDiff {
+        modified_at: post.modified_at,
+        changes: diff_lines(&post.content, &editor_content),
+    })
+}
+
+/// Hash of a project file's bytes (posts, pages, drafts or static files).
+/// Comparing it with the hash taken at load time shows whether the file
+/// really changed, which mtimes can't tell reliably.
+#[command]
+pub fn get_content_hash(project_path: String, file_id: String) -> Result<String, String> {
+    let file_path = resolve_project_path(Path::new(&project_path), &file_id)?;
+    if !file_path.is_file() {
+        return Err("File not found".to_string());
+    }
+
+    crate::files::content_hash(&file_path)
 }
 
 #[command]
 pub fn get_page(project_path: String, page_id: String) -> Result<Page, String> {
-    let file_path = Path::new(&project_path).join(&page_id);
+    let file_path = resolve_project_path(Path::new(&project_path), &page_id)
+        .map_err(|_| localized(ErrorCode::PageNotFound))?;
 
-    if !file_path.exists() {
-        return Err("Page not found".to_string());
+    if !file_path.is_file() {
+        return Err(localized(ErrorCode::PageNotFound));
     }
 
-    Page::from_file(&file_path, Path::new(&project_path))
+    let mut page = Page::from_file(&file_path, Path::new(&project_path))?;
+    page.language = Some(ContentLanguages::load(&project_path).language_of(&file_path));
+    Ok(page)
 }
 
 #[command]
-pub fn save_page(_project_path: String, page: Page) -> Result<(), String> {
-    let file_path = Path::new(&page.file_path);
+pub fn save_page(
+    project_path: String,
+    page: Page,
+    expected_modified_at: Option<i64>,
+) -> Result<i64, String> {
+    let file_path = resolve_project_path(Path::new(&project_path), &page.file_path)?;
+
+    ensure_valid_frontmatter(Path::new(&project_path), &page.frontmatter)?;
+    ensure_unchanged_on_disk(&file_path, expected_modified_at)?;
 
-    let markdown = page.to_markdown()?;
+    let markdown = page.to_markdown(final_newline())?;
 
-    fs::write(file_path, markdown)
+    if page.encoding.is_some() {
+        ensure_utf8_on_disk(&file_path)?;
+    }
+    replace_content_file(Path::new(&project_path), &file_path, &markdown)
         .map_err(|e| format!("Failed to save page: {}", e))?;
 
-    Ok(())
+    Ok(file_modified_at(&file_path))
 }
 
 #[command]
@@ -203,7 +704,7 @@ pub fn create_post(project_path: String, title: String) -> Result<Post, String>
         .map_err(|e| format!("Failed to create posts directory: {}", e))?;
 
     // Generate filename from title (transliterate to ASCII)
-    let filename = sanitize_filename(&title);
+    let filename = sanitize_filename(Path::new(&project_path), &title);
     let file_path = posts_dir.join(format!("{}.md", filename));
 
     // Get current time in ISO 8601 format
@@ -221,12 +722,17 @@ pub fn create_post(project_path: String, title: String) -> Result<Post, String>
         layout: None,
         description: None,
         permalink: None,
+        slug: None,
+        aliases: Vec::new(),
         draft: None,
+        weight: None,
+        publish_date: None,
+        expiry_date: None,
         custom_fields: Default::default(),
     };
 
     // Create markdown content
-    let frontmatter_yaml = crate::markdown::frontmatter_to_yaml(&frontmatter)?;
+    let frontmatter_yaml = crate::markdown::frontmatter_to_yaml(&frontmatter, &[])?;
 
     let content = format!("---\n{}---\n\n", frontmatter_yaml);
 
@@ -238,123 +744,453 @@ pub fn create_post(project_path: String, title: String) -> Result<Post, String>
     Post::from_file(&file_path, Path::new(&project_path))
 }
 
+/// Create a post from a fully specified frontmatter and body in one step.
+/// The filename is generated from the title; an existing file is never
+/// overwritten. An empty date is filled with the current time.
 #[command]
-pub fn get_draft(project_path: String, draft_id: String) -> Result<Draft, String> {
-    let file_path = Path::new(&project_path).join(&draft_id);
-
-    if !file_path.exists() {
-        return Err("Draft not found".to_string());
+pub fn create_post_with_frontmatter(
+    project_path: String,
+    frontmatter: crate::markdown::Frontmatter,
+    content: String,
+) -> Result<Post, String> {
+    let mut frontmatter = frontmatter;
+    frontmatter.title = frontmatter.title.trim().to_string();
+    if frontmatter.title.is_empty() {
+        return Err(localized(ErrorCode::TitleRequired));
+    }
+    if frontmatter.date.trim().is_empty() {
+        frontmatter.date = chrono::Local::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
     }
 
-    Draft::from_file(&file_path, Path::new(&project_path))
-}
+    ensure_valid_frontmatter(Path::new(&project_path), &frontmatter)?;
 
-#[command]
-pub fn save_draft(_project_path: String, draft: Draft) -> Result<(), String> {
-    let file_path = Path::new(&draft.file_path);
+    let project = HugoProject::new(PathBuf::from(&project_path));
+    let posts_dir = project.get_posts_dir();
 
-    let markdown = draft.to_markdown()?;
+    fs::create_dir_all(&posts_dir)
+        .map_err(|e| format!("Failed to create posts directory: {}", e))?;
 
-    fs::write(file_path, markdown)
-        .map_err(|e| format!("Failed to save draft: {}", e))?;
+    let file_path = unique_path(&posts_dir, &content_file_stem(Path::new(&project_path), &frontmatter.title, "post"), "md");
 
-    Ok(())
+    let markdown = crate::markdown::render_markdown(
+        &frontmatter,
+        &content,
+        crate::markdown::FrontmatterFormat::Yaml,
+        &Default::default(),
+        final_newline(),
+    )?;
+
+    fs::write(&file_path, markdown)
+        .map_err(|e| format!("Failed to create post: {}", e))?;
+
+    Post::from_file(&file_path, Path::new(&project_path))
 }
 
 #[command]
-pub fn delete_post(project_path: String, post_id: String) -> Result<(), String> {
-    let file_path = Path::new(&project_path).join(&post_id);
+pub fn rename_post(project_path: String, post_id: String, new_title: String) -> Result<Post, String> {
+    let file_path = resolve_project_path(Path::new(&project_path), &post_id)
+        .map_err(|_| localized(ErrorCode::PostNotFound))?;
 
     if !file_path.exists() {
-        return Err("Post not found".to_string());
+        return Err(localized(ErrorCode::PostNotFound));
     }
 
-    fs::remove_file(&file_path)
-        .map_err(|e| format!("Failed to delete post: {}", e))?;
-
-    Ok(())
-}
+    let file_name = file_path.file_name().and_then(|s| s.to_str()).unwrap_or("");
+    if file_name == "_index.md" {
+        return Err("Section index files cannot be renamed".to_string());
+    }
 
-#[command]
-pub fn delete_page(project_path: String, page_id: String) -> Result<(), String> {
-    let file_path = Path::new(&project_path).join(&page_id);
+    let title = new_title.trim();
+    if title.is_empty() {
+        return Err(localized(ErrorCode::TitleRequired));
+    }
 
-    if !file_path.exists() {
-        return Err("Page not found".to_string());
+    let mut slug = sanitize_filename(Path::new(&project_path), title);
+    if slug.is_empty() {
+        slug = "post".to_string();
     }
 
-    if let Some(parent) = file_path.parent() {
-        if parent.file_name().and_then(|s| s.to_str()) == Some("content") {
-            return Err("Refusing to delete content root".to_string());
-        }
-        if parent.ends_with("posts") || parent.ends_with("drafts") {
-            return Err("Invalid page path".to_string());
-        }
-        if matches!(
-            file_path.file_name().and_then(|s| s.to_str()),
-            Some("index.md") | Some("_index.md")
-        ) {
-            fs::remove_file(&file_path)
-                .map_err(|e| format!("Failed to delete page: {}", e))?;
-            if fs::read_dir(parent).map(|mut i| i.next().is_none()).unwrap_or(false) {
-                let _ = fs::remove_dir(parent);
-            }
-            return Ok(());
-        }
+    let mut post = Post::from_file(&file_path, Path::new(&project_path))?;
+    post.title = title.to_string();
+    post.frontmatter.title = title.to_string();
+
+    // Leaf bundles are named by their folder, so rename the folder instead
+    let is_bundle = crate::markdown::is_bundle_index(&file_path);
+    let source = if is_bundle {
+        file_path.parent().ok_or("Invalid post path")?.to_path_buf()
+    } else {
+        file_path.clone()
+    };
+    let parent_dir = source.parent().ok_or("Invalid post path")?;
+    let current_stem = source.file_stem().and_then(|s| s.to_str()).unwrap_or("");
+
+    let dest = if current_stem == slug {
+        source.clone()
+    } else if is_bundle {
+        unique_path(parent_dir, &slug, "")
+    } else {
+        unique_path(parent_dir, &slug, "md")
+    };
+
+    // Save the new title first, so a failed write leaves the name alone
+    write_content_file(Path::new(&project_path), &file_path, &post.to_markdown(final_newline())?)
+        .map_err(|e| format!("Failed to save post: {}", e))?;
+
+    if dest != source {
+        fs::rename(&source, &dest)
+            .map_err(|e| format!("Failed to rename post: {}", e))?;
     }
 
-    fs::remove_file(&file_path)
-        .map_err(|e| format!("Failed to delete page: {}", e))?;
+    let new_file_path = if is_bundle { dest.join("index.md") } else { dest };
 
-    Ok(())
+    Post::from_file(&new_file_path, Path::new(&project_path))
 }
 
-// ====================
-// Pages Commands
-// ====================
-
 #[command]
-pub fn create_page(project_path: String, title: String) -> Result<Page, String> {
+pub fn move_post(
+    project_path: String,
+    post_id: String,
+    target_relative_dir: String,
+) -> Result<Post, String> {
     let project = HugoProject::new(PathBuf::from(&project_path));
-    let pages_dir = project.get_pages_dir();
-
-    fs::create_dir_all(&pages_dir)
-        .map_err(|e| format!("Failed to create pages directory: {}", e))?;
+    let content_dir = project.get_content_dir();
+    let file_path = resolve_project_path(Path::new(&project_path), &post_id)
+        .map_err(|_| localized(ErrorCode::PostNotFound))?;
 
-    let mut folder_name = sanitize_filename(&title);
-    if folder_name.is_empty() {
-        folder_name = "page".to_string();
+    if !file_path.exists() {
+        return Err(localized(ErrorCode::PostNotFound));
     }
 
-    let mut page_dir = pages_dir.join(&folder_name);
-    if page_dir.exists() {
-        let timestamp = chrono::Utc::now().timestamp();
-        page_dir = pages_dir.join(format!("{}_{}", folder_name, timestamp));
+    let relative_target = validate_relative_path(&target_relative_dir)?;
+    let target_dir = content_dir.join(&relative_target);
+
+    // Leaf bundles move together with their resources
+    let is_bundle = crate::markdown::is_bundle_index(&file_path);
+    let source = if is_bundle {
+        file_path.parent().ok_or("Invalid post path")?.to_path_buf()
+    } else {
+        file_path.clone()
+    };
+
+    if source.parent() == Some(target_dir.as_path()) {
+        return Err("Post is already in the target directory".to_string());
+    }
+    if is_bundle && target_dir.starts_with(&source) {
+        return Err("Cannot move a bundle into itself".to_string());
     }
 
-    fs::create_dir_all(&page_dir)
-        .map_err(|e| format!("Failed to create page directory: {}", e))?;
+    let stem = source.file_stem().and_then(|s| s.to_str()).unwrap_or("post");
+    let new_file_path = relocate_content(&file_path, &target_dir, stem)
+        .map_err(|e| format!("Failed to move post: {}", e))?;
 
-    let file_path = page_dir.join("index.md");
+    Post::from_file(&new_file_path, Path::new(&project_path))
+}
 
-    let now = chrono::Local::now();
-    let date_str = now.to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
+/// Move a content file into `target_dir` under a free name based on `stem`.
+/// Leaf bundles move together with their resources. Returns the new path of
+/// the markdown file.
+fn relocate_content(file_path: &Path, target_dir: &Path, stem: &str) -> Result<PathBuf, String> {
+    let is_bundle = crate::markdown::is_bundle_index(file_path);
+    let source = if is_bundle {
+        file_path.parent().ok_or("Invalid file path")?
+    } else {
+        file_path
+    };
 
-    let frontmatter = crate::markdown::Frontmatter {
-        title: title.clone(),
-        date: date_str,
-        tags: Vec::new(),
-        categories: Vec::new(),
+    fs::create_dir_all(target_dir)
+        .map_err(|e| format!("Failed to create target directory: {}", e))?;
+
+    let dest = if is_bundle {
+        unique_path(target_dir, stem, "")
+    } else {
+        unique_path(target_dir, stem, "md")
+    };
+    fs::rename(source, &dest).map_err(|e| e.to_string())?;
+
+    Ok(if is_bundle { dest.join("index.md") } else { dest })
+}
+
+/// Move a file `relocate_content` moved back to where it was, e.g. after a
+/// later step failed. Best effort: the original error is what gets reported.
+fn undo_relocation(moved: &Path, original: &Path) {
+    if moved == original {
+        return;
+    }
+    let (from, to) = if crate::markdown::is_bundle_index(original) {
+        (moved.parent(), original.parent())
+    } else {
+        (Some(moved), Some(original))
+    };
+    if let (Some(from), Some(to)) = (from, to) {
+        let _ = fs::rename(from, to);
+    }
+}
+
+/// Flip a post's `draft` flag in place.
+///
+/// The file is not moved: `list_posts` hides anything flagged as a draft and
+/// `list_drafts` picks it up, so the flag alone decides where it is listed.
+/// Files under `content/drafts` are always treated as drafts by path, so they
+/// are rejected here instead of being silently left unpublished.
+#[command]
+pub fn toggle_draft(project_path: String, post_id: String) -> Result<Post, String> {
+    let project = HugoProject::new(PathBuf::from(&project_path));
+    let drafts_dir = project.get_drafts_dir();
+    let file_path = resolve_project_path(Path::new(&project_path), &post_id)
+        .map_err(|_| localized(ErrorCode::PostNotFound))?;
+
+    if !file_path.is_file() {
+        return Err(localized(ErrorCode::PostNotFound));
+    }
+    if file_path.starts_with(&drafts_dir) {
+        return Err("Files in the drafts directory must be moved to be published".to_string());
+    }
+
+    let mut post = Post::from_file(&file_path, Path::new(&project_path))?;
+    if post.frontmatter.draft.unwrap_or(false) {
+        post.frontmatter.mark_published(&post.layout);
+    } else {
+        post.frontmatter.draft = Some(true);
+    }
+
+    write_content_file(Path::new(&project_path), &file_path, &post.to_markdown(final_newline())?)
+        .map_err(|e| format!("Failed to save post: {}", e))?;
+
+    Post::from_file(&file_path, Path::new(&project_path))
+}
+
+#[command]
+pub fn copy_resource_to_bundle(
+    project_path: String,
+    post_id: String,
+    source_path: String,
+) -> Result<BundleResource, String> {
+    let file_path = resolve_project_path(Path::new(&project_path), &post_id)
+        .map_err(|_| localized(ErrorCode::PostNotFound))?;
+
+    if !file_path.exists() {
+        return Err(localized(ErrorCode::PostNotFound));
+    }
+    if !crate::markdown::is_bundle_index(&file_path) {
+        return Err("Post is not a page bundle".to_string());
+    }
+    let bundle_dir = file_path.parent().ok_or("Invalid post path")?;
+
+    let source = Path::new(&source_path);
+    if !source.is_file() {
+        return Err("Source file not found".to_string());
+    }
+    let filename = source
+        .file_name()
+        .and_then(|s| s.to_str())
+        .ok_or("Invalid source filename")?;
+    let sanitized_filename = sanitize_image_filename(filename);
+    let sanitized = Path::new(&sanitized_filename);
+    let stem = sanitized.file_stem().and_then(|s| s.to_str()).unwrap_or("file");
+    let ext = sanitized.extension().and_then(|s| s.to_str()).unwrap_or("");
+    if ext.eq_ignore_ascii_case("md") {
+        return Err("Markdown files cannot be added as bundle resources".to_string());
+    }
+
+    let dest = unique_path(bundle_dir, stem, ext);
+    fs::copy(source, &dest)
+        .map_err(|e| format!("Failed to copy resource: {}", e))?;
+
+    let size = fs::metadata(&dest).map(|m| m.len()).unwrap_or(0);
+    Ok(BundleResource {
+        name: dest
+            .file_name()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_default(),
+        path: dest
+            .strip_prefix(bundle_dir)
+            .map(|p| p.to_string_lossy().replace('\\', "/"))
+            .unwrap_or_default(),
+        size,
+    })
+}
+
+#[command]
+pub fn get_draft(project_path: String, draft_id: String) -> Result<Draft, String> {
+    let file_path = resolve_project_path(Path::new(&project_path), &draft_id)
+        .map_err(|_| localized(ErrorCode::DraftNotFound))?;
+
+    if !file_path.is_file() {
+        return Err(localized(ErrorCode::DraftNotFound));
+    }
+
+    Draft::from_file(&file_path, Path::new(&project_path))
+}
+
+#[command]
+pub fn save_draft(
+    project_path: String,
+    draft: Draft,
+    expected_modified_at: Option<i64>,
+) -> Result<i64, String> {
+    let file_path = resolve_project_path(Path::new(&project_path), &draft.file_path)?;
+
+    ensure_unchanged_on_disk(&file_path, expected_modified_at)?;
+
+    let markdown = draft.to_markdown(final_newline())?;
+
+    if draft.encoding.is_some() {
+        ensure_utf8_on_disk(&file_path)?;
+    }
+    replace_content_file(Path::new(&project_path), &file_path, &markdown)
+        .map_err(|e| format!("Failed to save draft: {}", e))?;
+
+    Ok(file_modified_at(&file_path))
+}
+
+#[command]
+pub fn delete_post(project_path: String, post_id: String) -> Result<(), String> {
+    let file_path = resolve_project_path(Path::new(&project_path), &post_id)
+        .map_err(|_| localized(ErrorCode::PostNotFound))?;
+
+    if !file_path.exists() {
+        return Err(localized(ErrorCode::PostNotFound));
+    }
+
+    delete_content_file(Path::new(&project_path), &file_path)
+        .map_err(|e| format!("Failed to delete post: {}", e))?;
+
+    Ok(())
+}
+
+#[command]
+pub fn delete_page(project_path: String, page_id: String) -> Result<(), String> {
+    let file_path = resolve_project_path(Path::new(&project_path), &page_id)
+        .map_err(|_| localized(ErrorCode::PageNotFound))?;
+
+    if !file_path.exists() {
+        return Err(localized(ErrorCode::PageNotFound));
+    }
+
+    if let Some(parent) = file_path.parent() {
+        if parent.ends_with("posts") || parent.ends_with("drafts") {
+            return Err("Invalid page path".to_string());
+        }
+        // Bundle pages take their folder with them once it's empty.
+        // Single-file pages (`content/about.md`) are deleted like any file.
+        if matches!(
+            file_path.file_name().and_then(|s| s.to_str()),
+            Some("index.md") | Some("_index.md")
+        ) {
+            if parent.file_name().and_then(|s| s.to_str()) == Some("content") {
+                return Err("Refusing to delete content root".to_string());
+            }
+            delete_content_file(Path::new(&project_path), &file_path)
+                .map_err(|e| format!("Failed to delete page: {}", e))?;
+            if fs::read_dir(parent).map(|mut i| i.next().is_none()).unwrap_or(false) {
+                let _ = fs::remove_dir(parent);
+            }
+            return Ok(());
+        }
+    }
+
+    delete_content_file(Path::new(&project_path), &file_path)
+        .map_err(|e| format!("Failed to delete page: {}", e))?;
+
+    Ok(())
+}
+
+// ====================
+// Template Commands
+// ====================
+
+#[command]
+pub fn list_templates(project_path: String) -> Result<Vec<crate::templates::PostTemplate>, String> {
+    crate::templates::load_templates(Path::new(&project_path))
+}
+
+/// Add a template to `.hugo-bros/templates.json`, replacing one with the same name
+#[command]
+pub fn save_template(project_path: String, template: crate::templates::PostTemplate) -> Result<(), String> {
+    if template.name.trim().is_empty() {
+        return Err("Template name is required".to_string());
+    }
+    crate::templates::save_template(Path::new(&project_path), template)
+}
+
+/// Create a post from a template, filling in `{title}` and `{date}`
+#[command]
+pub fn create_post_from_template(
+    project_path: String,
+    template_name: String,
+    title: String,
+) -> Result<Post, String> {
+    let templates = crate::templates::load_templates(Path::new(&project_path))?;
+    let template = templates
+        .iter()
+        .find(|t| t.name == template_name)
+        .ok_or_else(|| format!("Template not found: {}", template_name))?;
+
+    let date = chrono::Local::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
+    let (frontmatter, content) = crate::templates::apply_template(template, title.trim(), &date)?;
+
+    create_post_with_frontmatter(project_path, frontmatter, content)
+}
+
+// ====================
+// Pages Commands
+// ====================
+
+/// Create a page as a leaf bundle (`content/<slug>/index.md`, the default)
+/// or, with `bundle: false`, as a single file (`content/<slug>.md`)
+#[command]
+pub fn create_page(project_path: String, title: String, bundle: Option<bool>) -> Result<Page, String> {
+    let project = HugoProject::new(PathBuf::from(&project_path));
+    let pages_dir = project.get_pages_dir();
+
+    fs::create_dir_all(&pages_dir)
+        .map_err(|e| format!("Failed to create pages directory: {}", e))?;
+
+    let mut name = sanitize_filename(Path::new(&project_path), &title);
+    if name.is_empty() {
+        name = "page".to_string();
+    }
+
+    // `about/` and `about.md` would both be the /about/ page
+    let taken = |name: &str| {
+        pages_dir.join(name).exists() || pages_dir.join(format!("{}.md", name)).exists()
+    };
+    if taken(&name) {
+        name = format!("{}_{}", name, chrono::Utc::now().timestamp());
+    }
+
+    let file_path = if bundle.unwrap_or(true) {
+        let page_dir = pages_dir.join(&name);
+        fs::create_dir_all(&page_dir)
+            .map_err(|e| format!("Failed to create page directory: {}", e))?;
+        page_dir.join("index.md")
+    } else {
+        pages_dir.join(format!("{}.md", name))
+    };
+
+    let now = chrono::Local::now();
+    let date_str = now.to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
+
+    let frontmatter = crate::markdown::Frontmatter {
+        title: title.clone(),
+        date: date_str,
+        tags: Vec::new(),
+        categories: Vec::new(),
         updated: None,
         comments: None,
         layout: None,
         description: None,
         permalink: None,
+        slug: None,
+        aliases: Vec::new(),
         draft: None,
+        weight: None,
+        publish_date: None,
+        expiry_date: None,
         custom_fields: Default::default(),
     };
 
-    let frontmatter_yaml = crate::markdown::frontmatter_to_yaml(&frontmatter)?;
+    let frontmatter_yaml = crate::markdown::frontmatter_to_yaml(&frontmatter, &[])?;
 
     let content = format!("---\n{}---\n\n", frontmatter_yaml);
 
@@ -365,53 +1201,217 @@ pub fn create_page(project_path: String, title: String) -> Result<Page, String>
 }
 
 #[command]
-pub fn list_pages(project_path: String) -> Result<Vec<Page>, String> {
+pub fn list_pages(project_path: String, sort: Option<SortSpec>) -> Result<Vec<Page>, String> {
     let project = HugoProject::new(PathBuf::from(&project_path));
     let pages_dir = project.get_pages_dir();
     let posts_dir = project.get_posts_dir();
-    let drafts_dir = project.get_content_dir().join("drafts");
     let should_skip_posts = posts_dir != pages_dir;
 
     if !pages_dir.exists() {
         return Ok(Vec::new());
     }
 
-    let mut pages = Vec::new();
+    let languages = ContentLanguages::load(&project_path);
 
     // Look for index.md/_index.md files and standalone pages in content/
-    for entry in walkdir::WalkDir::new(&pages_dir)
+    let paths: Vec<PathBuf> = walkdir::WalkDir::new(&pages_dir)
         .max_depth(4)
         .into_iter()
         .filter_map(|e| e.ok())
-    {
-        let path = entry.path();
-        if !path.is_file() || path.extension().and_then(|s| s.to_str()) != Some("md") {
-            continue;
-        }
-        if (should_skip_posts && path.starts_with(&posts_dir)) || path.starts_with(&drafts_dir) {
-            continue;
-        }
-        let filename = path.file_name().and_then(|s| s.to_str());
-        let is_index = matches!(filename, Some("index.md") | Some("_index.md"));
-        let is_root_page = path.parent() == Some(pages_dir.as_path());
-        if !is_index && !is_root_page {
-            continue;
-        }
-
-        match Page::from_file(path, Path::new(&project_path)) {
-            Ok(page) => {
-                if page.frontmatter.draft.unwrap_or(false) {
-                    continue;
+        .map(|entry| entry.into_path())
+        .filter(|path| {
+            if !path.is_file() || path.extension().and_then(|s| s.to_str()) != Some("md") {
+                return false;
+            }
+            if should_skip_posts && path.starts_with(&posts_dir) {
+                return false;
+            }
+            let filename = path.file_name().and_then(|s| s.to_str());
+            let is_index = matches!(filename, Some("index.md") | Some("_index.md"));
+            let is_root_page = path.parent() == Some(pages_dir.as_path());
+            is_index || is_root_page
+        })
+        .collect();
+
+    let pages: Vec<Page> = paths
+        .par_iter()
+        .filter_map(|path| match Page::from_file(path, Path::new(&project_path)) {
+            Ok(mut page) => {
+                if project.classify_content(path, &page.frontmatter) == ContentState::Draft {
+                    return None;
                 }
-                pages.push(page);
+                page.language = Some(languages.language_of(path));
+                Some(page)
             },
-            Err(e) => eprintln!("Failed to parse page: {}", e),
-        }
+            Err(e) => {
+                eprintln!("Failed to parse page: {}", e);
+                None
+            }
+        })
+        .collect();
+
+    let sort = sort.unwrap_or(SortSpec { key: SortKey::Modified, ascending: false });
+
+    Ok(sort_content(pages, sort))
+}
+
+// ====================
+// Section Commands
+// ====================
+
+/// Folders that are or can become sections: the content root (the home
+/// page) and every folder below it that isn't a leaf bundle
+#[command]
+pub fn list_sections(project_path: String) -> Result<Vec<SectionInfo>, String> {
+    let project = HugoProject::new(PathBuf::from(&project_path));
+    let content_dir = project.get_content_dir();
+    let drafts_dir = project.get_drafts_dir();
+
+    if !content_dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut sections = Vec::new();
+    let walker = walkdir::WalkDir::new(&content_dir)
+        .max_depth(4)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|entry| {
+            let hidden = entry.depth() > 0 && entry.file_name().to_string_lossy().starts_with('.');
+            // A leaf bundle's folders are its resources, not sections
+            entry.file_type().is_dir()
+                && !hidden
+                && entry.path() != drafts_dir
+                && !entry.path().join("index.md").exists()
+        });
+
+    for entry in walker.filter_map(|e| e.ok()) {
+        let Ok(relative) = entry.path().strip_prefix(&content_dir) else {
+            continue;
+        };
+        let index_path = entry.path().join("_index.md");
+        let title = crate::files::read_frontmatter_block(&index_path)
+            .ok()
+            .and_then(|block| crate::markdown::MarkdownDocument::parse(&block).ok())
+            .filter(|(_, had_no_frontmatter)| !had_no_frontmatter)
+            .map(|(doc, _)| doc.frontmatter.title);
+
+        sections.push(SectionInfo {
+            path: relative.to_string_lossy().replace('\\', "/"),
+            index_id: section_index_id(relative),
+            has_index: index_path.is_file(),
+            title,
+        });
+    }
+
+    Ok(sections)
+}
+
+/// The section's `_index.md` as a page. A section without one gets an
+/// unsaved page that `save_section` creates.
+#[command]
+pub fn get_section(project_path: String, section_path: String) -> Result<Page, String> {
+    let relative = validate_relative_path(section_path.trim_matches('/'))?;
+    let content_dir = HugoProject::new(PathBuf::from(&project_path)).get_content_dir();
+    let section_dir = content_dir.join(&relative);
+
+    if !section_dir.is_dir() {
+        return Err("Section not found".to_string());
+    }
+
+    let index_path = section_dir.join("_index.md");
+    if index_path.is_file() {
+        return Page::from_file(&index_path, Path::new(&project_path));
     }
 
-    pages.sort_by(|a, b| b.modified_at.cmp(&a.modified_at));
+    let title = relative
+        .file_name()
+        .and_then(|s| s.to_str())
+        .map(format_section_title)
+        .unwrap_or_else(|| "Home".to_string());
+    let now = chrono::Local::now();
+
+    Ok(Page {
+        id: section_index_id(&relative),
+        title: title.clone(),
+        content: String::new(),
+        frontmatter: crate::markdown::Frontmatter {
+            title,
+            date: now.to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+            tags: Vec::new(),
+            categories: Vec::new(),
+            updated: None,
+            comments: None,
+            layout: None,
+            description: None,
+            permalink: None,
+            slug: None,
+            aliases: Vec::new(),
+            draft: None,
+            weight: None,
+            publish_date: None,
+            expiry_date: None,
+            custom_fields: Default::default(),
+        },
+        file_path: index_path.to_string_lossy().to_string(),
+        created_at: now.timestamp_millis(),
+        modified_at: now.timestamp_millis(),
+        frontmatter_format: Default::default(),
+        layout: Default::default(),
+        word_count: 0,
+        reading_time_minutes: 0,
+        language: None,
+        encoding: None,
+    })
+}
+
+/// Save a section's `_index.md`. Pass no `expected_modified_at` for a
+/// section that has no index yet.
+#[command]
+pub fn save_section(
+    project_path: String,
+    section: Page,
+    expected_modified_at: Option<i64>,
+) -> Result<i64, String> {
+    let file_path = resolve_project_path(Path::new(&project_path), &section.id)?;
+    let content_dir = HugoProject::new(PathBuf::from(&project_path)).get_content_dir();
+
+    let in_content = file_path.parent().is_some_and(|dir| dir.starts_with(&content_dir));
+    if !in_content || file_path.file_name().and_then(|s| s.to_str()) != Some("_index.md") {
+        return Err("Not a section index".to_string());
+    }
+
+    ensure_valid_frontmatter(Path::new(&project_path), &section.frontmatter)?;
+    ensure_unchanged_on_disk(&file_path, expected_modified_at)?;
+
+    let markdown = section.to_markdown(final_newline())?;
+
+    if section.encoding.is_some() {
+        ensure_utf8_on_disk(&file_path)?;
+    }
+    replace_content_file(Path::new(&project_path), &file_path, &markdown)
+        .map_err(|e| format!("Failed to save section: {}", e))?;
 
-    Ok(pages)
+    Ok(file_modified_at(&file_path))
+}
+
+/// Id of a section's `_index.md`, relative to the project like other page ids
+fn section_index_id(relative: &Path) -> String {
+    Path::new("content")
+        .join(relative)
+        .join("_index.md")
+        .to_string_lossy()
+        .replace('\\', "/")
+}
+
+/// `web-dev` -> `Web dev`
+fn format_section_title(name: &str) -> String {
+    let words = name.replace(['-', '_'], " ");
+    let mut chars = words.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
 }
 
 // ====================
@@ -421,12 +1421,12 @@ pub fn list_pages(project_path: String) -> Result<Vec<Page>, String> {
 #[command]
 pub fn create_draft(project_path: String, title: String) -> Result<Draft, String> {
     let project = HugoProject::new(PathBuf::from(&project_path));
-    let drafts_dir = project.get_content_dir().join("drafts");
+    let drafts_dir = project.get_drafts_dir();
 
     fs::create_dir_all(&drafts_dir)
         .map_err(|e| format!("Failed to create drafts directory: {}", e))?;
 
-    let mut filename = sanitize_filename(&title);
+    let mut filename = sanitize_filename(Path::new(&project_path), &title);
     if filename.is_empty() {
         filename = "draft".to_string();
     }
@@ -452,11 +1452,16 @@ pub fn create_draft(project_path: String, title: String) -> Result<Draft, String
         layout: None,
         description: None,
         permalink: None,
+        slug: None,
+        aliases: Vec::new(),
         draft: Some(true),
+        weight: None,
+        publish_date: None,
+        expiry_date: None,
         custom_fields: Default::default(),
     };
 
-    let frontmatter_yaml = crate::markdown::frontmatter_to_yaml(&frontmatter)?;
+    let frontmatter_yaml = crate::markdown::frontmatter_to_yaml(&frontmatter, &[])?;
 
     let content = format!("---\n{}---\n\n", frontmatter_yaml);
 
@@ -466,62 +1471,272 @@ pub fn create_draft(project_path: String, title: String) -> Result<Draft, String
     Draft::from_file(&final_path, Path::new(&project_path))
 }
 
+/// Publish a draft: clear its draft flag and move it into the posts section
+/// under a file name generated from its title. With `refresh_date` the date
+/// is set to now, so it appears as a new post.
 #[command]
-pub fn delete_draft(project_path: String, draft_id: String) -> Result<(), String> {
-    let file_path = Path::new(&project_path).join(&draft_id);
+pub fn publish_draft(
+    project_path: String,
+    draft_id: String,
+    refresh_date: Option<bool>,
+) -> Result<Post, String> {
+    let project_root = Path::new(&project_path);
+    let project = HugoProject::new(PathBuf::from(&project_path));
+    let file_path = resolve_project_path(project_root, &draft_id)
+        .map_err(|_| localized(ErrorCode::DraftNotFound))?;
 
-    if !file_path.exists() {
-        return Err("Draft not found".to_string());
+    if !file_path.is_file() {
+        return Err(localized(ErrorCode::DraftNotFound));
     }
 
-    fs::remove_file(&file_path)
-        .map_err(|e| format!("Failed to delete draft: {}", e))?;
+    let mut post = Post::from_file(&file_path, project_root)?;
+    post.frontmatter.mark_published(&post.layout);
+    if refresh_date.unwrap_or(false) {
+        post.frontmatter.date = chrono::Local::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
+    }
+    let markdown = post.to_markdown(final_newline())?;
 
-    Ok(())
+    // Move first: if saving the flag then fails, the move is undone and the
+    // draft is left exactly as it was
+    let posts_dir = project.get_posts_dir();
+    let new_file_path = if file_path.starts_with(&posts_dir) {
+        file_path.clone()
+    } else {
+        relocate_content(&file_path, &posts_dir, &content_file_stem(project_root, &post.title, "post"))
+            .map_err(|e| format!("Failed to move draft: {}", e))?
+    };
+    if let Err(e) = write_content_file(project_root, &new_file_path, &markdown) {
+        undo_relocation(&new_file_path, &file_path);
+        return Err(format!("Failed to save post: {}", e));
+    }
+
+    Post::from_file(&new_file_path, project_root)
 }
 
+/// Turn a post back into a draft: set its draft flag and move it into
+/// `content/drafts`
 #[command]
-pub fn list_drafts(project_path: String) -> Result<Vec<Draft>, String> {
+pub fn unpublish_post(project_path: String, post_id: String) -> Result<Draft, String> {
+    let project_root = Path::new(&project_path);
     let project = HugoProject::new(PathBuf::from(&project_path));
-    let content_dir = project.get_content_dir();
-    let drafts_dir = content_dir.join("drafts");
+    let file_path = resolve_project_path(project_root, &post_id)
+        .map_err(|_| localized(ErrorCode::PostNotFound))?;
 
-    if !content_dir.exists() {
-        return Ok(Vec::new());
+    if !file_path.is_file() {
+        return Err(localized(ErrorCode::PostNotFound));
     }
 
-    let mut drafts = Vec::new();
+    let mut post = Post::from_file(&file_path, project_root)?;
+    post.frontmatter.draft = Some(true);
+    let markdown = post.to_markdown(final_newline())?;
 
-    for entry in walkdir::WalkDir::new(&content_dir)
-        .max_depth(4)
-        .into_iter()
-        .filter_map(|e| e.ok())
-    {
-        let path = entry.path();
-        if path.is_file() && path.extension().and_then(|s| s.to_str()) == Some("md") {
-            let is_draft_path = drafts_dir.exists() && path.starts_with(&drafts_dir);
-            match Draft::from_file(path, Path::new(&project_path)) {
-                Ok(draft) => {
-                    if draft.frontmatter.draft.unwrap_or(false) || is_draft_path {
-                        drafts.push(draft);
-                    }
-                },
-                Err(e) => eprintln!("Failed to parse draft: {}", e),
-            }
-        }
+    // Move first, as in `publish_draft`
+    let drafts_dir = project.get_drafts_dir();
+    let new_file_path = if file_path.starts_with(&drafts_dir) {
+        file_path.clone()
+    } else {
+        relocate_content(&file_path, &drafts_dir, &content_file_stem(project_root, &post.title, "draft"))
+            .map_err(|e| format!("Failed to move post: {}", e))?
+    };
+    if let Err(e) = write_content_file(project_root, &new_file_path, &markdown) {
+        undo_relocation(&new_file_path, &file_path);
+        return Err(format!("Failed to save post: {}", e));
     }
 
-    drafts.sort_by(|a, b| b.modified_at.cmp(&a.modified_at));
-
-    Ok(drafts)
+    Draft::from_file(&new_file_path, project_root)
 }
 
-// ====================
-// Images Commands
-// ====================
-
+/// Apply the same frontmatter changes to several files. Every file must
+/// exist before anything is written; after that, each file succeeds or fails
+/// on its own and the results say which.
 #[command]
-pub fn list_images(project_path: String) -> Result<Vec<ImageInfo>, String> {
+pub fn batch_update_frontmatter(
+    project_path: String,
+    post_ids: Vec<String>,
+    changes: Vec<FrontmatterChange>,
+) -> Result<Vec<BatchUpdateResult>, String> {
+    let project_root = Path::new(&project_path);
+
+    for change in &changes {
+        change.validate()?;
+    }
+
+    let mut targets = Vec::with_capacity(post_ids.len());
+    for post_id in &post_ids {
+        let file_path = resolve_project_path(project_root, post_id)
+            .ok()
+            .filter(|path| path.is_file())
+            .ok_or_else(|| format!("Post not found: {}", post_id))?;
+        targets.push((post_id.clone(), file_path));
+    }
+
+    let results = targets
+        .into_iter()
+        .map(|(id, file_path)| {
+            let result = update_post_frontmatter(project_root, &file_path, &changes);
+            BatchUpdateResult {
+                id,
+                success: result.is_ok(),
+                error: result.err(),
+            }
+        })
+        .collect();
+
+    Ok(results)
+}
+
+fn update_post_frontmatter(
+    project_root: &Path,
+    file_path: &Path,
+    changes: &[FrontmatterChange],
+) -> Result<(), String> {
+    let mut post = Post::from_file(file_path, project_root)?;
+    post.frontmatter = crate::markdown::edit_frontmatter(&post.frontmatter, &mut post.layout, |mapping| {
+        changes.iter().try_for_each(|change| change.apply(mapping))
+    })?;
+
+    write_content_file(project_root, file_path, &post.to_markdown(final_newline())?)
+        .map_err(|e| format!("Failed to save post: {}", e))
+}
+
+/// Copy a post into `content/drafts` as a draft, leaving the published post
+/// as it is, e.g. to work on a major revision. Leaf bundles are copied with
+/// their resources. Name clashes get a timestamp suffix, as in `create_draft`.
+#[command]
+pub fn create_draft_from_post(project_path: String, post_id: String) -> Result<Draft, String> {
+    let project_root = Path::new(&project_path);
+    let project = HugoProject::new(PathBuf::from(&project_path));
+    let file_path = resolve_project_path(project_root, &post_id)
+        .map_err(|_| localized(ErrorCode::PostNotFound))?;
+
+    if !file_path.is_file() {
+        return Err(localized(ErrorCode::PostNotFound));
+    }
+
+    let mut post = Post::from_file(&file_path, project_root)?;
+    post.frontmatter.draft = Some(true);
+
+    let drafts_dir = project.get_drafts_dir();
+    fs::create_dir_all(&drafts_dir)
+        .map_err(|e| format!("Failed to create drafts directory: {}", e))?;
+
+    // A copied bundle is removed again if the draft can't be written,
+    // so a failure doesn't leave a half-made draft behind
+    let mut copied_bundle = None;
+    let draft_path = if crate::markdown::is_bundle_index(&file_path) {
+        let bundle_dir = file_path.parent().ok_or("Invalid post path")?;
+        let stem = bundle_dir.file_name().and_then(|s| s.to_str()).unwrap_or("draft");
+        let dest = unique_path(&drafts_dir, stem, "");
+        if let Err(e) = copy_dir_all(bundle_dir, &dest) {
+            let _ = fs::remove_dir_all(&dest);
+            return Err(e);
+        }
+        copied_bundle = Some(dest.clone());
+        dest.join("index.md")
+    } else {
+        let stem = file_path.file_stem().and_then(|s| s.to_str()).unwrap_or("draft");
+        unique_path(&drafts_dir, stem, "md")
+    };
+
+    let written = post.to_markdown(final_newline()).and_then(|markdown| {
+        crate::files::write_file_atomic(&draft_path, markdown.as_bytes())
+            .map_err(|e| format!("Failed to create draft: {}", e))
+    });
+    if let Err(e) = written {
+        if let Some(bundle) = copied_bundle {
+            let _ = fs::remove_dir_all(bundle);
+        }
+        return Err(e);
+    }
+
+    Draft::from_file(&draft_path, project_root)
+}
+
+fn copy_dir_all(source: &Path, dest: &Path) -> Result<(), String> {
+    for entry in walkdir::WalkDir::new(source).into_iter().filter_map(|e| e.ok()) {
+        let Ok(relative) = entry.path().strip_prefix(source) else {
+            continue;
+        };
+        let target = dest.join(relative);
+        if entry.file_type().is_dir() {
+            fs::create_dir_all(&target)
+                .map_err(|e| format!("Failed to create directory: {}", e))?;
+        } else {
+            fs::copy(entry.path(), &target)
+                .map_err(|e| format!("Failed to copy file: {}", e))?;
+        }
+    }
+    Ok(())
+}
+
+/// File name for content moved between sections, generated from its title
+fn content_file_stem(project_path: &Path, title: &str, fallback: &str) -> String {
+    let stem = sanitize_filename(project_path, title);
+    if stem.is_empty() {
+        fallback.to_string()
+    } else {
+        stem
+    }
+}
+
+#[command]
+pub fn delete_draft(project_path: String, draft_id: String) -> Result<(), String> {
+    let file_path = resolve_project_path(Path::new(&project_path), &draft_id)
+        .map_err(|_| localized(ErrorCode::DraftNotFound))?;
+
+    if !file_path.exists() {
+        return Err(localized(ErrorCode::DraftNotFound));
+    }
+
+    delete_content_file(Path::new(&project_path), &file_path)
+        .map_err(|e| format!("Failed to delete draft: {}", e))?;
+
+    Ok(())
+}
+
+#[command]
+pub fn list_drafts(project_path: String) -> Result<Vec<Draft>, String> {
+    let project = HugoProject::new(PathBuf::from(&project_path));
+    let content_dir = project.get_content_dir();
+
+    if !content_dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut drafts = Vec::new();
+
+    for entry in walkdir::WalkDir::new(&content_dir)
+        .max_depth(4)
+        .into_iter()
+        .filter_map(|e| e.ok())
+    {
+        let path = entry.path();
+        if path.is_file() && path.extension().and_then(|s| s.to_str()) == Some("md") {
+            match Draft::from_file(path, Path::new(&project_path)) {
+                Ok(draft) => {
+                    if project.classify_content(path, &draft.frontmatter) == ContentState::Draft {
+                        drafts.push(draft);
+                    }
+                },
+                Err(e) => eprintln!("Failed to parse draft: {}", e),
+            }
+        }
+    }
+
+    drafts.sort_by(|a, b| b.modified_at.cmp(&a.modified_at));
+
+    Ok(drafts)
+}
+
+// ====================
+// Images Commands
+// ====================
+
+/// Deepest level `list_static_tree` descends to
+const MAX_STATIC_TREE_DEPTH: usize = 8;
+
+#[command]
+pub fn list_images(project_path: String) -> Result<Vec<ImageInfo>, String> {
     let project = HugoProject::new(PathBuf::from(&project_path));
     let static_dir = project.get_static_dir();
 
@@ -540,7 +1755,7 @@ pub fn list_images(project_path: String) -> Result<Vec<ImageInfo>, String> {
 
         if path.is_file() {
             if let Some(ext) = path.extension().and_then(|s| s.to_str()) {
-                if matches!(ext.to_lowercase().as_str(), "png" | "jpg" | "jpeg" | "gif" | "webp" | "svg" | "ico") {
+                if is_static_image_extension(&ext.to_lowercase()) {
                     match create_image_info(path, &static_dir, Path::new(&project_path)) {
                         Ok(img) => images.push(img),
                         Err(e) => eprintln!("Failed to read image {:?}: {}", path, e),
@@ -555,10 +1770,31 @@ pub fn list_images(project_path: String) -> Result<Vec<ImageInfo>, String> {
     Ok(images)
 }
 
+/// Absolute path of a cached JPEG thumbnail for the media browser, fitting
+/// in `max_edge` pixels. `None` means the original can be shown as is (SVGs
+/// and images that are already small enough).
+#[command]
+pub fn get_image_thumbnail(
+    project_path: String,
+    image_path: String,
+    max_edge: u32,
+) -> Result<Option<String>, String> {
+    let project_root = Path::new(&project_path);
+    let file_path = resolve_project_path(project_root, &image_path)?;
+    if !file_path.is_file() {
+        return Err("Image not found".to_string());
+    }
+
+    let thumbnail = crate::images::thumbnail(&crate::images::thumbnails_dir(), &file_path, max_edge)?;
+    Ok(thumbnail.map(|path| path.to_string_lossy().to_string()))
+}
+
 #[command]
 pub fn list_static_entries(
     project_path: String,
     dir: Option<String>,
+    filter: Option<StaticFilter>,
+    include_dir_sizes: Option<bool>,
 ) -> Result<Vec<StaticEntry>, String> {
     let project = HugoProject::new(PathBuf::from(&project_path));
     let static_dir = project.get_static_dir();
@@ -578,8 +1814,78 @@ pub fn list_static_entries(
         return Err("Not a directory".to_string());
     }
 
+    let mut entries = read_static_dir(&static_dir, &target_dir, &filter.unwrap_or_default())?;
+
+    // Walks every folder recursively, so only on request
+    if include_dir_sizes.unwrap_or(false) {
+        for entry in entries.iter_mut().filter(|e| e.kind == "dir") {
+            let (size, file_count) = dir_size(Path::new(&entry.full_path));
+            entry.size = size;
+            entry.file_count = Some(file_count);
+        }
+    }
+
+    Ok(entries)
+}
+
+/// Total bytes and number of files under a directory
+fn dir_size(dir: &Path) -> (u64, u64) {
+    walkdir::WalkDir::new(dir)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .fold((0, 0), |(size, count), entry| {
+            let len = entry.metadata().map(|m| m.len()).unwrap_or(0);
+            (size + len, count + 1)
+        })
+}
+
+/// Whole `static/` tree in one call: folders carry their `children`, down to
+/// `max_depth` levels (capped at `MAX_STATIC_TREE_DEPTH`). Folders below the
+/// cap have no children and can be loaded with `list_static_entries`.
+#[command]
+pub fn list_static_tree(
+    project_path: String,
+    max_depth: Option<usize>,
+    filter: Option<StaticFilter>,
+) -> Result<Vec<StaticEntry>, String> {
+    let project = HugoProject::new(PathBuf::from(&project_path));
+    let static_dir = project.get_static_dir();
+
+    if !static_dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let max_depth = max_depth
+        .unwrap_or(MAX_STATIC_TREE_DEPTH)
+        .clamp(1, MAX_STATIC_TREE_DEPTH);
+    static_tree(&static_dir, &static_dir, max_depth, &filter.unwrap_or_default())
+}
+
+fn static_tree(
+    static_dir: &Path,
+    dir: &Path,
+    depth: usize,
+    filter: &StaticFilter,
+) -> Result<Vec<StaticEntry>, String> {
+    let mut entries = read_static_dir(static_dir, dir, filter)?;
+    if depth > 1 {
+        for entry in entries.iter_mut().filter(|e| e.kind == "dir") {
+            entry.children = Some(static_tree(static_dir, Path::new(&entry.full_path), depth - 1, filter)?);
+        }
+    }
+    Ok(entries)
+}
+
+/// Folders and files matching `filter` directly inside `target_dir`, sorted
+/// by name. Only images get a `url`.
+fn read_static_dir(
+    static_dir: &Path,
+    target_dir: &Path,
+    filter: &StaticFilter,
+) -> Result<Vec<StaticEntry>, String> {
     let mut entries = Vec::new();
-    for entry in fs::read_dir(&target_dir).map_err(|e| format!("Failed to read directory: {}", e))? {
+    for entry in fs::read_dir(target_dir).map_err(|e| format!("Failed to read directory: {}", e))? {
         let entry = entry.map_err(|e| format!("Failed to read directory entry: {}", e))?;
         let path = entry.path();
         let name = entry
@@ -590,7 +1896,7 @@ pub fn list_static_entries(
         if path.is_dir() {
             let (created_at, modified_at) = file_times(&path)?;
             let relative_path = path
-                .strip_prefix(&static_dir)
+                .strip_prefix(static_dir)
                 .ok()
                 .and_then(|p| p.to_str())
                 .unwrap_or("")
@@ -604,16 +1910,20 @@ pub fn list_static_entries(
                 modified_at,
                 url: None,
                 full_path: path.to_string_lossy().to_string(),
+                children: None,
+                file_count: None,
             });
             continue;
         }
 
         if path.is_file() {
-            if let Some(ext) = path.extension().and_then(|s| s.to_str()) {
-                if !matches!(ext.to_lowercase().as_str(), "png" | "jpg" | "jpeg" | "gif" | "webp" | "svg" | "ico") {
-                    continue;
-                }
-            } else {
+            let extension = path
+                .extension()
+                .and_then(|s| s.to_str())
+                .map(|ext| ext.to_lowercase())
+                .unwrap_or_default();
+            let is_image = is_static_image_extension(&extension);
+            if !filter.matches(&extension, is_image) {
                 continue;
             }
 
@@ -622,12 +1932,12 @@ pub fn list_static_entries(
                 .map(|m| m.len())
                 .unwrap_or(0);
             let relative_path = path
-                .strip_prefix(&static_dir)
+                .strip_prefix(static_dir)
                 .ok()
                 .and_then(|p| p.to_str())
                 .unwrap_or("")
                 .to_string();
-            let url = format!("/{}", relative_path.replace('\\', "/"));
+            let url = is_image.then(|| format!("/{}", relative_path.replace('\\', "/")));
             entries.push(StaticEntry {
                 name,
                 path: relative_path.replace('\\', "/"),
@@ -635,8 +1945,10 @@ pub fn list_static_entries(
                 size,
                 created_at,
                 modified_at,
-                url: Some(url),
+                url,
                 full_path: path.to_string_lossy().to_string(),
+                children: None,
+                file_count: None,
             });
         }
     }
@@ -706,18 +2018,171 @@ pub fn delete_static_entry(project_path: String, relative_path: String) -> Resul
     Ok(())
 }
 
+/// Rename or move a file or folder within static/. Returns the new path.
+#[command]
+pub fn move_static_entry(
+    project_path: String,
+    from_relative: String,
+    to_relative: String,
+) -> Result<String, String> {
+    let project = HugoProject::new(PathBuf::from(&project_path));
+    let static_dir = project.get_static_dir();
+
+    let from = validate_relative_path(from_relative.trim())?;
+    let to = validate_relative_path(to_relative.trim())?;
+    if from.as_os_str().is_empty() || to.as_os_str().is_empty() {
+        return Err("Refusing to move static root".to_string());
+    }
+
+    let source = static_dir.join(&from);
+    let destination = static_dir.join(&to);
+
+    if !source.exists() {
+        return Err("Entry not found".to_string());
+    }
+    if destination.exists() {
+        return Err("An entry already exists at the destination".to_string());
+    }
+    if source.is_dir() && destination.starts_with(&source) {
+        return Err("Cannot move a folder into itself".to_string());
+    }
+
+    if let Some(parent) = destination.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create folder: {}", e))?;
+    }
+
+    fs::rename(&source, &destination)
+        .map_err(|e| format!("Failed to move entry: {}", e))?;
+
+    Ok(to.to_string_lossy().replace('\\', "/"))
+}
+
 #[command]
 pub fn copy_image_to_project(
     project_path: String,
     source_path: String,
     target_dir: Option<String>,
-) -> Result<String, String> {
+    max_width: Option<u32>,
+    max_height: Option<u32>,
+    quality: Option<u8>,
+) -> Result<CopiedImage, String> {
+    let project = HugoProject::new(PathBuf::from(&project_path));
+    let static_dir = project.get_static_dir();
+    let dest_dir = image_target_dir(&static_dir, target_dir)?;
+
+    let options = crate::images::ResizeOptions {
+        max_width,
+        max_height,
+        quality,
+    };
+    copy_image(Path::new(&source_path), &dest_dir, &static_dir, &options)
+}
+
+/// Check that an image's content matches its extension, e.g. a JPEG saved as
+/// `.png`, which some Hugo image pipelines reject
+#[command]
+pub fn verify_image(project_path: String, image_path: String) -> Result<ImageVerification, String> {
+    let file_path = resolve_project_path(Path::new(&project_path), &image_path)?;
+    if !file_path.is_file() {
+        return Err("Image not found".to_string());
+    }
+
+    let actual = crate::images::sniff_format(&file_path)?;
+    let expected = crate::images::extension_format(&file_path);
+    let format_name = |format: image::ImageFormat| format!("{:?}", format).to_lowercase();
+
+    // Unrecognized content (SVG, text) only matches a non-raster extension
+    let matches = actual == expected || (actual.is_none() && expected.is_none());
+    let suggested_extension = actual
+        .filter(|_| !matches)
+        .and_then(|format| format.extensions_str().first())
+        .map(|ext| ext.to_string());
+
+    Ok(ImageVerification {
+        path: image_path,
+        extension_format: expected.map(format_name),
+        actual_format: actual.map(format_name),
+        matches,
+        suggested_extension,
+    })
+}
+
+/// Rename an image to the extension matching its content. Returns the new
+/// path relative to the project. References in content are not updated.
+#[command]
+pub fn fix_image_extension(project_path: String, image_path: String) -> Result<String, String> {
+    let verification = verify_image(project_path.clone(), image_path.clone())?;
+    let Some(extension) = verification.suggested_extension else {
+        return Err("Image extension already matches its format".to_string());
+    };
+
+    let file_path = resolve_project_path(Path::new(&project_path), &image_path)?;
+    let dir = file_path.parent().ok_or("Invalid image path")?;
+    let stem = file_path.file_stem().and_then(|s| s.to_str()).unwrap_or("image");
+    let new_path = unique_path(dir, stem, &extension);
+
+    fs::rename(&file_path, &new_path)
+        .map_err(|e| format!("Failed to rename image: {}", e))?;
+
+    Ok(new_path
+        .strip_prefix(&project_path)
+        .unwrap_or(&new_path)
+        .to_string_lossy()
+        .replace('\\', "/"))
+}
+
+/// Copy several images in one go. Files with the same name get distinct
+/// destinations, and one failed file doesn't stop the rest.
+#[command]
+pub fn copy_images_to_project(
+    project_path: String,
+    source_paths: Vec<String>,
+    target_dir: Option<String>,
+    max_width: Option<u32>,
+    max_height: Option<u32>,
+    quality: Option<u8>,
+) -> Result<Vec<ImageCopyResult>, String> {
     let project = HugoProject::new(PathBuf::from(&project_path));
     let static_dir = project.get_static_dir();
+    let dest_dir = image_target_dir(&static_dir, target_dir)?;
+
+    let options = crate::images::ResizeOptions {
+        max_width,
+        max_height,
+        quality,
+    };
+
+    // One at a time, so each file sees the names taken by the ones before it
+    let results = source_paths
+        .into_iter()
+        .map(|source_path| {
+            match copy_image(Path::new(&source_path), &dest_dir, &static_dir, &options) {
+                Ok(image) => ImageCopyResult {
+                    source: source_path,
+                    success: true,
+                    image: Some(image),
+                    error: None,
+                },
+                Err(e) => ImageCopyResult {
+                    source: source_path,
+                    success: false,
+                    image: None,
+                    error: Some(e),
+                },
+            }
+        })
+        .collect();
+
+    Ok(results)
+}
+
+/// Folder under static/ that copied images go to, created if missing
+fn image_target_dir(static_dir: &Path, target_dir: Option<String>) -> Result<PathBuf, String> {
     let target_dir = target_dir.unwrap_or_default();
     let relative_target = validate_relative_path(&target_dir)?;
     let dest_dir = if target_dir.is_empty() {
-        static_dir.clone()
+        static_dir.to_path_buf()
     } else {
         static_dir.join(relative_target)
     };
@@ -726,7 +2191,15 @@ pub fn copy_image_to_project(
     fs::create_dir_all(&dest_dir)
         .map_err(|e| format!("Failed to create target directory: {}", e))?;
 
-    let source = Path::new(&source_path);
+    Ok(dest_dir)
+}
+
+fn copy_image(
+    source: &Path,
+    dest_dir: &Path,
+    static_dir: &Path,
+    options: &crate::images::ResizeOptions,
+) -> Result<CopiedImage, String> {
     let filename = source
         .file_name()
         .and_then(|s| s.to_str())
@@ -735,30 +2208,47 @@ pub fn copy_image_to_project(
 
     let dest_path = dest_dir.join(&sanitized_filename);
 
-    // Handle duplicate filenames
-    let final_dest = if dest_path.exists() {
+    // Handle duplicate filenames: a timestamp suffix, then a counter if
+    // another file got the same timestamp
+    let final_dest = if !dest_path.exists() {
+        dest_path
+    } else {
         let timestamp = chrono::Utc::now().timestamp();
         let stem = Path::new(&sanitized_filename)
             .file_stem()
             .and_then(|s| s.to_str())
             .unwrap_or("file");
         let ext = source.extension().and_then(|s| s.to_str()).unwrap_or("");
-        dest_dir.join(format!("{}_{}.{}", stem, timestamp, ext))
-    } else {
-        dest_path
+        (1..)
+            .map(|n| {
+                let suffix = if n == 1 { timestamp.to_string() } else { format!("{}_{}", timestamp, n) };
+                dest_dir.join(format!("{}_{}.{}", stem, suffix, ext))
+            })
+            .find(|candidate| !candidate.exists())
+            .ok_or("Failed to pick a file name")?
     };
 
-    fs::copy(source, &final_dest)
-        .map_err(|e| format!("Failed to copy image: {}", e))?;
+    if !crate::images::optimize_image(source, &final_dest, options)? {
+        fs::copy(source, &final_dest)
+            .map_err(|e| format!("Failed to copy image: {}", e))?;
+    }
 
     // Return URL path for markdown
     let relative_path = final_dest
-        .strip_prefix(&static_dir)
+        .strip_prefix(static_dir)
         .ok()
         .and_then(|p| p.to_str())
         .ok_or("Failed to get relative path")?;
 
-    Ok(format!("/{}", relative_path.replace('\\', "/")))
+    let (width, height) = get_image_dimensions(&final_dest);
+    let size = fs::metadata(&final_dest).map(|m| m.len()).unwrap_or(0);
+
+    Ok(CopiedImage {
+        url: format!("/{}", relative_path.replace('\\', "/")),
+        width,
+        height,
+        size,
+    })
 }
 
 fn sanitize_image_filename(filename: &str) -> String {
@@ -786,6 +2276,75 @@ fn sanitize_image_filename(filename: &str) -> String {
     }
 }
 
+/// All markdown files under the content directory, at the depth the list commands walk
+fn content_markdown_files(content_dir: &Path) -> Vec<PathBuf> {
+    if !content_dir.exists() {
+        return Vec::new();
+    }
+
+    walkdir::WalkDir::new(content_dir)
+        .max_depth(4)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .map(|e| e.into_path())
+        .filter(|path| path.is_file() && path.extension().and_then(|s| s.to_str()) == Some("md"))
+        .collect()
+}
+
+/// Build `dir/stem.extension`, appending a timestamp to the stem when that
+/// path is already taken. An empty extension yields a directory-style name.
+fn unique_path(dir: &Path, stem: &str, extension: &str) -> PathBuf {
+    let file_name = |stem: &str| {
+        if extension.is_empty() {
+            stem.to_string()
+        } else {
+            format!("{}.{}", stem, extension)
+        }
+    };
+
+    let candidate = dir.join(file_name(stem));
+    if !candidate.exists() {
+        return candidate;
+    }
+
+    let timestamp = chrono::Utc::now().timestamp();
+    dir.join(file_name(&format!("{}_{}", stem, timestamp)))
+}
+
+/// Resolve a content id to a path inside the project. Ids are normally
+/// relative to the project, but absolute paths inside it (as the watcher and
+/// external opens produce) are accepted too. Symlinks and `..` are resolved
+/// before the check, and the result is always rooted at `project_path`.
+fn resolve_project_path(project_path: &Path, id: &str) -> Result<PathBuf, String> {
+    let root = project_path
+        .canonicalize()
+        .map_err(|e| format!("Failed to resolve project path: {}", e))?;
+
+    let candidate = if Path::new(id).is_absolute() {
+        PathBuf::from(id)
+    } else {
+        project_path.join(id)
+    };
+    let resolved = match candidate.canonicalize() {
+        Ok(path) => path,
+        // Files that don't exist yet are checked through their folder
+        Err(_) => {
+            let name = candidate.file_name().ok_or("Invalid path")?;
+            candidate
+                .parent()
+                .ok_or("Invalid path")?
+                .canonicalize()
+                .map_err(|_| "Path not found".to_string())?
+                .join(name)
+        }
+    };
+
+    let relative = resolved
+        .strip_prefix(&root)
+        .map_err(|_| "Path is outside the project".to_string())?;
+    Ok(project_path.join(relative))
+}
+
 fn validate_relative_path(relative: &str) -> Result<PathBuf, String> {
     if relative.is_empty() {
         return Ok(PathBuf::new());
@@ -830,94 +2389,1070 @@ fn file_times(path: &Path) -> Result<(i64, i64), String> {
         .ok()
         .or(metadata.modified().ok())
         .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
-        .map(|d: std::time::Duration| d.as_secs() as i64)
+        .map(|d: std::time::Duration| d.as_millis() as i64)
         .unwrap_or(0);
 
     let modified_at = metadata
         .modified()
         .ok()
         .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
-        .map(|d: std::time::Duration| d.as_secs() as i64)
+        .map(|d: std::time::Duration| d.as_millis() as i64)
         .unwrap_or(0);
 
     Ok((created_at, modified_at))
 }
 
 #[command]
-pub fn delete_image(project_path: String, image_path: String) -> Result<(), String> {
-    let file_path = Path::new(&project_path).join(&image_path);
+pub fn check_preview_images(project_path: String) -> Result<Vec<PreviewImageReport>, String> {
+    let config = load_frontmatter_config(Path::new(&project_path))?;
+    let Some(field) = config.preview_image_field else {
+        return Ok(Vec::new());
+    };
 
-    if !file_path.exists() {
-        return Err("Image not found".to_string());
+    let static_dir = HugoProject::new(PathBuf::from(&project_path)).get_static_dir();
+    let mut reports = Vec::new();
+
+    for post in collect_posts(&project_path, true, &PostFilter::default())? {
+        let Some(value) = post
+            .frontmatter
+            .custom_fields
+            .get(&field)
+            .and_then(|v| v.as_str())
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty())
+        else {
+            continue;
+        };
+
+        // Relative references in a leaf bundle point at the bundle's own resources
+        let bundle_path = Path::new(&post.file_path)
+            .parent()
+            .filter(|_| !value.starts_with('/') && !post.resources.is_empty())
+            .map(|dir| dir.join(&value));
+        let expected_path = match bundle_path {
+            Some(path) if path.is_file() => Some(path),
+            _ => crate::frontmatter_config::resolve_static_path(&static_dir, &value),
+        };
+        // External URLs can't be checked locally
+        let Some(expected_path) = expected_path else {
+            continue;
+        };
+
+        let missing = !expected_path.is_file();
+        let (width, height) = if missing {
+            (None, None)
+        } else {
+            get_image_dimensions(&expected_path)
+        };
+
+        reports.push(PreviewImageReport {
+            post_id: post.id,
+            title: post.title,
+            value,
+            expected_path: expected_path.to_string_lossy().to_string(),
+            missing,
+            width,
+            height,
+        });
     }
 
-    fs::remove_file(&file_path)
-        .map_err(|e| format!("Failed to delete image: {}", e))?;
+    Ok(reports)
+}
 
-    Ok(())
+/// Resolve an `image` frontmatter value to a file on disk for a thumbnail.
+/// Root-absolute values resolve under `static/`; relative ones try the
+/// bundle of `file_id` first, like `check_preview_images`.
+#[command]
+pub fn resolve_image_field(
+    project_path: String,
+    value: String,
+    file_id: Option<String>,
+) -> Result<ResolvedImage, String> {
+    let value = value.trim();
+    let static_dir = HugoProject::new(PathBuf::from(&project_path)).get_static_dir();
+
+    let bundle_path = match file_id {
+        Some(file_id) if !value.starts_with('/') => {
+            let file_path = Path::new(&project_path).join(validate_relative_path(&file_id)?);
+            file_path.parent().map(|dir| dir.join(value))
+        }
+        _ => None,
+    };
+    let path = match bundle_path {
+        Some(path) if path.is_file() => path,
+        _ => crate::frontmatter_config::resolve_static_path(&static_dir, value)
+            .ok_or("Not a local image reference")?,
+    };
+
+    if path.components().any(|c| matches!(c, Component::ParentDir)) {
+        return Err("Invalid image path".to_string());
+    }
+    if !path.is_file() {
+        return Err(format!("Image not found: {}", path.display()));
+    }
+
+    let (width, height) = get_image_dimensions(&path);
+    Ok(ResolvedImage {
+        full_path: path.to_string_lossy().to_string(),
+        width,
+        height,
+    })
 }
 
 // ====================
-// App Config Commands
+// Preview Commands
 // ====================
 
+/// Render a post body to HTML for an in-app preview. `file_id` locates the
+/// post so bundle-relative images resolve; shortcodes are reported as warnings.
 #[command]
-pub fn get_app_config() -> Result<crate::config::AppConfig, String> {
-    crate::config::AppConfig::load()
-}
-
+pub fn render_markdown_to_html(
+    project_path: String,
+    content: String,
+    file_id: Option<String>,
+) -> Result<crate::preview::RenderedHtml, String> {
+    let project = HugoProject::new(PathBuf::from(&project_path));
+    let bundle_dir = match file_id {
+        Some(id) => {
+            let relative = validate_relative_path(&id)?;
+            project.path.join(relative).parent().map(Path::to_path_buf)
+        }
+        None => None,
+    };
+
+    Ok(crate::preview::render_html(
+        &content,
+        &project.get_static_dir(),
+        bundle_dir.as_deref(),
+    ))
+}
+
+// ====================
+// Shortcode Commands
+// ====================
+
+/// Shortcodes used across all content, most used first, with the files
+/// that use each one
+#[command]
+pub fn list_shortcodes_used(project_path: String) -> Result<Vec<ShortcodeUsage>, String> {
+    let project = HugoProject::new(PathBuf::from(&project_path));
+    let mut usage: HashMap<String, ShortcodeUsage> = HashMap::new();
+
+    for path in content_markdown_files(&project.get_content_dir()) {
+        let Ok(raw) = crate::files::read_file(&path) else {
+            continue;
+        };
+        let Ok((doc, _)) = crate::markdown::MarkdownDocument::parse(&raw) else {
+            continue;
+        };
+        let file_id = path
+            .strip_prefix(&project.path)
+            .map(|p| p.to_string_lossy().replace('\\', "/"))
+            .unwrap_or_default();
+
+        for shortcode in crate::markdown::extract_shortcodes(&doc.content) {
+            let entry = usage.entry(shortcode.name.clone()).or_insert_with(|| ShortcodeUsage {
+                name: shortcode.name,
+                count: 0,
+                files: Vec::new(),
+            });
+            entry.count += 1;
+            if !entry.files.contains(&file_id) {
+                entry.files.push(file_id.clone());
+            }
+        }
+    }
+
+    let mut result: Vec<ShortcodeUsage> = usage.into_values().collect();
+    result.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.name.cmp(&b.name)));
+
+    Ok(result)
+}
+
+// ====================
+// Translation Commands
+// ====================
+
+/// Other-language versions of a post: files sharing its path once the
+/// language tree and `.<lang>` suffix are removed, or sharing its
+/// `translationKey` frontmatter value
+#[command]
+pub fn list_translations(project_path: String, post_id: String) -> Result<Vec<Post>, String> {
+    let relative = validate_relative_path(&post_id)?;
+    let file_path = Path::new(&project_path).join(relative);
+    if !file_path.is_file() {
+        return Err(localized(ErrorCode::PostNotFound));
+    }
+
+    let languages = ContentLanguages::load(&project_path);
+    let source = Post::from_file(&file_path, Path::new(&project_path))?;
+    let key = languages.translation_key(&file_path);
+    let translation_key = source
+        .frontmatter
+        .custom_fields
+        .get("translationKey")
+        .and_then(|v| v.as_str())
+        .map(str::to_string);
+
+    let mut translations = Vec::new();
+    for path in content_markdown_files(&languages.content_dir) {
+        if path == file_path {
+            continue;
+        }
+
+        let same_path = key.is_some() && languages.translation_key(&path) == key;
+        let post = match Post::from_file(&path, Path::new(&project_path)) {
+            Ok(post) => post,
+            Err(_) => continue,
+        };
+        let same_key = translation_key.is_some()
+            && post.frontmatter.custom_fields.get("translationKey").and_then(|v| v.as_str())
+                == translation_key.as_deref();
+
+        if same_path || same_key {
+            let mut post = post;
+            post.language = Some(languages.language_of(&path));
+            translations.push(post);
+        }
+    }
+
+    translations.sort_by(|a, b| a.language.cmp(&b.language));
+
+    Ok(translations)
+}
+
+// ====================
+// Taxonomy Commands
+// ====================
+
+#[command]
+pub fn list_tags(project_path: String) -> Result<Vec<TermCount>, String> {
+    count_taxonomy_terms(&project_path, |frontmatter| &frontmatter.tags)
+}
+
+#[command]
+pub fn list_categories(project_path: String) -> Result<Vec<TermCount>, String> {
+    count_taxonomy_terms(&project_path, |frontmatter| &frontmatter.categories)
+}
+
+/// Count term usage over the same posts and pages the list commands return,
+/// most used first
+fn count_taxonomy_terms(
+    project_path: &str,
+    terms: fn(&crate::markdown::Frontmatter) -> &Vec<String>,
+) -> Result<Vec<TermCount>, String> {
+    let posts = collect_posts(project_path, true, &PostFilter::default())?;
+    let pages = pages_besides_posts(list_pages(project_path.to_string(), None)?, &posts);
+
+    let frontmatters = posts
+        .iter()
+        .map(|post| &post.frontmatter)
+        .chain(pages.iter().map(|page| &page.frontmatter));
+    Ok(term_counts(frontmatters, terms))
+}
+
+/// Pages that aren't also in `posts`; the two overlap when posts live in the
+/// content root
+fn pages_besides_posts(pages: Vec<Page>, posts: &[Post]) -> Vec<Page> {
+    let post_ids: HashSet<&str> = posts.iter().map(|post| post.id.as_str()).collect();
+    pages
+        .into_iter()
+        .filter(|page| !post_ids.contains(page.id.as_str()))
+        .collect()
+}
+
+/// Usage of each term across `frontmatters`, most used first
+fn term_counts<'a>(
+    frontmatters: impl Iterator<Item = &'a crate::markdown::Frontmatter>,
+    terms: fn(&crate::markdown::Frontmatter) -> &Vec<String>,
+) -> Vec<TermCount> {
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for frontmatter in frontmatters {
+        for term in terms(frontmatter) {
+            *counts.entry(term.clone()).or_insert(0) += 1;
+        }
+    }
+
+    let mut result: Vec<TermCount> = counts
+        .into_iter()
+        .map(|(name, count)| TermCount { name, count })
+        .collect();
+    result.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.name.cmp(&b.name)));
+
+    result
+}
+
+#[command]
+pub fn rename_tag(project_path: String, old_tag: String, new_tag: String) -> Result<usize, String> {
+    rename_taxonomy_term(&project_path, &old_tag, &new_tag, |frontmatter| &mut frontmatter.tags)
+}
+
+#[command]
+pub fn rename_category(
+    project_path: String,
+    old_category: String,
+    new_category: String,
+) -> Result<usize, String> {
+    rename_taxonomy_term(&project_path, &old_category, &new_category, |frontmatter| {
+        &mut frontmatter.categories
+    })
+}
+
+/// Replace a term in one taxonomy list across all content, merging it into
+/// `new_term` when a file already has both. Returns the number of files changed.
+fn rename_taxonomy_term(
+    project_path: &str,
+    old_term: &str,
+    new_term: &str,
+    terms: fn(&mut crate::markdown::Frontmatter) -> &mut Vec<String>,
+) -> Result<usize, String> {
+    let new_term = new_term.trim();
+    if new_term.is_empty() {
+        return Err("New name is required".to_string());
+    }
+    if old_term == new_term {
+        return Ok(0);
+    }
+
+    let project = HugoProject::new(PathBuf::from(project_path));
+
+    // Every file is rewritten in memory before any is written, so one
+    // unreadable file leaves the whole rename unapplied instead of half done
+    let mut updates = Vec::new();
+    for path in content_markdown_files(&project.get_content_dir()) {
+        let raw = crate::files::read_file(&path)?;
+        let (mut doc, had_no_frontmatter) = crate::markdown::MarkdownDocument::parse(&raw)
+            .map_err(|e| format!("Failed to parse {:?}: {}", path, e))?;
+        if had_no_frontmatter {
+            continue;
+        }
+
+        let list = terms(&mut doc.frontmatter);
+        if !list.iter().any(|term| term == old_term) {
+            continue;
+        }
+
+        let mut renamed: Vec<String> = Vec::with_capacity(list.len());
+        for term in list.drain(..) {
+            let term = if term == old_term { new_term.to_string() } else { term };
+            if !renamed.contains(&term) {
+                renamed.push(term);
+            }
+        }
+        *list = renamed;
+
+        ensure_utf8_on_disk(&path).map_err(|e| format!("Failed to update {:?}: {}", path, e))?;
+        let markdown = crate::markdown::render_markdown(&doc.frontmatter, &doc.content, doc.format, &doc.layout, final_newline())?;
+        updates.push((path, markdown));
+    }
+
+    for (path, markdown) in &updates {
+        write_content_file(Path::new(project_path), path, markdown)
+            .map_err(|e| format!("Failed to update {:?}: {}", path, e))?;
+    }
+
+    Ok(updates.len())
+}
+
+// ====================
+// Stats Commands
+// ====================
+
+/// Dashboard summary in one call. Missing folders count as empty.
+#[command]
+pub fn get_content_stats(project_path: String) -> Result<ContentStats, String> {
+    let project = HugoProject::new(PathBuf::from(&project_path));
+
+    let posts = collect_posts(&project_path, false, &PostFilter::default())?;
+    let total_words = posts.iter().map(|post| post.word_count).sum();
+
+    let mut static_images = 0;
+    let mut static_size = 0;
+    for entry in walkdir::WalkDir::new(project.get_static_dir())
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+    {
+        static_size += entry.metadata().map(|m| m.len()).unwrap_or(0);
+        let is_image = entry
+            .path()
+            .extension()
+            .and_then(|s| s.to_str())
+            .is_some_and(|ext| is_static_image_extension(&ext.to_lowercase()));
+        if is_image {
+            static_images += 1;
+        }
+    }
+
+    // Terms are counted from the posts and pages already listed rather than
+    // through list_tags/list_categories, which would walk content again
+    let pages = pages_besides_posts(list_pages(project_path.clone(), None)?, &posts);
+    let frontmatters = || {
+        posts
+            .iter()
+            .map(|post| &post.frontmatter)
+            .chain(pages.iter().map(|page| &page.frontmatter))
+    };
+
+    Ok(ContentStats {
+        post_count: posts.len(),
+        page_count: pages.len(),
+        draft_count: list_drafts(project_path)?.len(),
+        total_words,
+        tag_count: term_counts(frontmatters(), |frontmatter| &frontmatter.tags).len(),
+        category_count: term_counts(frontmatters(), |frontmatter| &frontmatter.categories).len(),
+        static_images,
+        static_size,
+    })
+}
+
+/// Write metadata of all published posts and pages as a JSON array, e.g. for
+/// a client-side search index. `output_path` is relative to the project.
+/// Returns the number of entries written.
+#[command]
+pub fn export_content_index(project_path: String, output_path: String) -> Result<usize, String> {
+    let relative = validate_relative_path(&output_path)?;
+    if relative.as_os_str().is_empty() {
+        return Err("Output path is required".to_string());
+    }
+    let output = Path::new(&project_path).join(relative);
+
+    let preview_field = load_frontmatter_config(Path::new(&project_path))?.preview_image_field;
+    let preview_image = |frontmatter: &crate::markdown::Frontmatter| {
+        preview_field
+            .as_ref()
+            .and_then(|field| frontmatter.custom_fields.get(field))
+            .and_then(|value| value.as_str())
+            .map(str::to_string)
+    };
+
+    let posts = collect_posts(&project_path, false, &PostFilter::default())?;
+    let pages = list_pages(project_path.clone(), None)?;
+    let entries: Vec<ContentIndexEntry> = posts
+        .into_iter()
+        .map(|post| ContentIndexEntry {
+            preview_image: preview_image(&post.frontmatter),
+            id: post.id,
+            kind: "post".to_string(),
+            title: post.title,
+            date: post.date,
+            tags: post.frontmatter.tags,
+            categories: post.frontmatter.categories,
+            word_count: post.word_count,
+        })
+        .chain(pages.into_iter().map(|page| ContentIndexEntry {
+            preview_image: preview_image(&page.frontmatter),
+            id: page.id,
+            kind: "page".to_string(),
+            title: page.title,
+            date: page.frontmatter.date,
+            tags: page.frontmatter.tags,
+            categories: page.frontmatter.categories,
+            word_count: page.word_count,
+        }))
+        .collect();
+
+    let json = serde_json::to_string_pretty(&entries)
+        .map_err(|e| format!("Failed to serialize content index: {}", e))?;
+    if let Some(parent) = output.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create directory: {}", e))?;
+    }
+    crate::files::write_file_atomic(&output, json.as_bytes())
+        .map_err(|e| format!("Failed to write content index: {}", e))?;
+
+    Ok(entries.len())
+}
+
+// ====================
+// Link Checker
+// ====================
+
+#[command]
+pub fn check_links(
+    project_path: String,
+    include_external: Option<bool>,
+) -> Result<Vec<BrokenLink>, String> {
+    let project = HugoProject::new(PathBuf::from(&project_path));
+    let content_dir = project.get_content_dir();
+    let static_dir = project.get_static_dir();
+    let include_external = include_external.unwrap_or(false);
+
+    let mut documents: Vec<(String, String, String)> =
+        collect_posts(&project_path, false, &PostFilter::default())?
+            .into_iter()
+            .map(|post| (post.id, post.file_path, post.content))
+            .collect();
+    for page in list_pages(project_path.clone(), None)? {
+        if !documents.iter().any(|(id, _, _)| id == &page.id) {
+            documents.push((page.id, page.file_path, page.content));
+        }
+    }
+
+    let mut broken = Vec::new();
+
+    for (file_id, file_path, content) in documents {
+        let file_dir = Path::new(&file_path).parent().unwrap_or(Path::new(""));
+        let line_offset = crate::files::read_file(Path::new(&file_path))
+            .map(|raw| crate::markdown::body_line_offset(&raw, &content))
+            .unwrap_or(0);
+
+        for link in crate::markdown::extract_links(&content) {
+            let line = link.line + line_offset;
+            let target = link.target.trim();
+            if target.is_empty() || target.starts_with('#') {
+                continue;
+            }
+
+            let is_external = target.starts_with("http://")
+                || target.starts_with("https://")
+                || target.starts_with("mailto:")
+                || target.starts_with("//");
+            if is_external {
+                if include_external {
+                    broken.push(BrokenLink {
+                        file_id: file_id.clone(),
+                        target: target.to_string(),
+                        line,
+                        kind: "external".to_string(),
+                    });
+                }
+                continue;
+            }
+            // Other schemes (tel:, data:, ...) can't be checked on disk
+            if target.contains(':') && !target.starts_with('/') {
+                continue;
+            }
+
+            if !link_target_exists(target, file_dir, &content_dir, &static_dir) {
+                broken.push(BrokenLink {
+                    file_id: file_id.clone(),
+                    target: target.to_string(),
+                    line,
+                    kind: if link.is_image { "image" } else { "link" }.to_string(),
+                });
+            }
+        }
+    }
+
+    Ok(broken)
+}
+
+/// Check a local link against the files Hugo would serve it from.
+/// Root-absolute targets are looked up in `static/` and `content/`,
+/// relative ones next to the linking file.
+fn link_target_exists(target: &str, file_dir: &Path, content_dir: &Path, static_dir: &Path) -> bool {
+    let path = target.split(['#', '?']).next().unwrap_or("");
+    let decoded = path.replace("%20", " ");
+    if decoded.is_empty() {
+        return true;
+    }
+
+    let bases: Vec<PathBuf> = match decoded.strip_prefix('/') {
+        Some(rooted) => vec![static_dir.join(rooted), content_dir.join(rooted)],
+        None => vec![file_dir.join(&decoded)],
+    };
+
+    bases.iter().any(|base| {
+        let trimmed = PathBuf::from(base.to_string_lossy().trim_end_matches(['/', '\\']));
+        base.is_file()
+            || trimmed.with_extension("md").is_file()
+            || trimmed.join("index.md").is_file()
+            || trimmed.join("_index.md").is_file()
+    })
+}
+
+#[command]
+pub fn delete_image(project_path: String, image_path: String) -> Result<(), String> {
+    let file_path = resolve_project_path(Path::new(&project_path), &image_path)
+        .map_err(|_| "Image not found".to_string())?;
+
+    if !file_path.exists() {
+        return Err("Image not found".to_string());
+    }
+
+    delete_content_file(Path::new(&project_path), &file_path)
+        .map_err(|e| format!("Failed to delete image: {}", e))?;
+
+    Ok(())
+}
+
+/// Modification time in milliseconds, as reported in `modified_at`
+fn file_modified_at(file_path: &Path) -> i64 {
+    fs::metadata(file_path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+        .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+        .map(|d| d.as_millis() as i64)
+        .unwrap_or(0)
+}
+
+/// Reject a save if the file's mtime no longer matches the one the editor
+/// loaded (or the file is gone), with a `file_changed` or `file_deleted`
+/// error the frontend turns into a diff or an overwrite prompt. Without an
+/// expected time, saves overwrite.
+fn ensure_unchanged_on_disk(file_path: &Path, expected_modified_at: Option<i64>) -> Result<(), String> {
+    let Some(expected) = expected_modified_at else {
+        return Ok(());
+    };
+
+    if !file_path.exists() {
+        return Err(localized(ErrorCode::FileDeleted));
+    }
+    if file_modified_at(file_path) != expected {
+        return Err(localized(ErrorCode::FileChanged));
+    }
+    Ok(())
+}
+
+/// Whether rendered content files end with exactly one line break, from
+/// the app config
+fn final_newline() -> bool {
+    crate::config::AppConfig::load()
+        .map(|config| config.ensure_final_newline)
+        .unwrap_or(true)
+}
+
+/// Refuse to touch a file that isn't clean UTF-8 on disk, since writing
+/// would convert it. Missing files are fine.
+fn ensure_utf8_on_disk(file_path: &Path) -> Result<(), String> {
+    match fs::read(file_path) {
+        Ok(bytes) if std::str::from_utf8(&bytes).is_err() => Err(localized(ErrorCode::NotUtf8)),
+        _ => Ok(()),
+    }
+}
+
+/// Write a content file atomically, backing up the previous version first.
+/// Files in another encoding are refused rather than silently converted.
+fn write_content_file(project_path: &Path, file_path: &Path, contents: &str) -> Result<(), String> {
+    ensure_utf8_on_disk(file_path)?;
+    replace_content_file(project_path, file_path, contents)
+}
+
+/// `write_content_file` that also converts a file in another encoding to
+/// UTF-8; for saves the user confirmed by clearing the entry's `encoding`
+fn replace_content_file(project_path: &Path, file_path: &Path, contents: &str) -> Result<(), String> {
+    let max_backups = crate::config::AppConfig::load()
+        .map(|config| config.max_backups_per_file)
+        .unwrap_or(0);
+
+    crate::backups::backup_file(project_path, file_path, max_backups)?;
+
+    crate::files::write_file_atomic(file_path, contents.as_bytes())?;
+    crate::content_cache::invalidate_file(file_path);
+    Ok(())
+}
+
+/// Content files that reference a static image, so the UI can warn before
+/// `delete_image`. `image_url` is the site URL (`/images/x.png`); references
+/// without the leading slash or behind `./`/`../` count too, in bodies and
+/// frontmatter alike.
+#[command]
+pub fn get_image_usage(project_path: String, image_url: String) -> Result<Vec<ImageUsage>, String> {
+    let relative = image_url
+        .split(['?', '#'])
+        .next()
+        .unwrap_or("")
+        .trim()
+        .trim_start_matches('/');
+    if relative.is_empty() {
+        return Err("Image URL is required".to_string());
+    }
+
+    let pattern = regex::Regex::new(&format!(
+        r"(^|[^\w./-])(\.{{1,2}}/)*/?{}($|[^\w.-])",
+        regex::escape(relative)
+    ))
+    .map_err(|e| format!("Invalid image URL: {}", e))?;
+
+    let project = HugoProject::new(PathBuf::from(&project_path));
+    let content_dir = project.get_content_dir();
+    let paths: Vec<PathBuf> = walkdir::WalkDir::new(&content_dir)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .map(|entry| entry.into_path())
+        .filter(|path| {
+            path.is_file()
+                && matches!(path.extension().and_then(|s| s.to_str()), Some("md") | Some("markdown"))
+        })
+        .collect();
+
+    let mut usages: Vec<ImageUsage> = paths
+        .par_iter()
+        .filter_map(|path| {
+            let text = fs::read_to_string(path).ok()?;
+            let lines: Vec<usize> = text
+                .lines()
+                .enumerate()
+                .filter(|(_, line)| pattern.is_match(line))
+                .map(|(index, _)| index + 1)
+                .collect();
+            if lines.is_empty() {
+                return None;
+            }
+            let id = path
+                .strip_prefix(&project_path)
+                .unwrap_or(path)
+                .to_string_lossy()
+                .replace('\\', "/");
+            Some(ImageUsage { id, lines })
+        })
+        .collect();
+    usages.sort_by(|a, b| a.id.cmp(&b.id));
+
+    Ok(usages)
+}
+
+/// Delete a project file, moving it to the trash when enabled in the
+/// project settings or, failing that, the app config
+fn delete_content_file(project_path: &Path, file_path: &Path) -> Result<(), String> {
+    let use_trash = crate::project_settings::ProjectSettings::load(project_path)
+        .ok()
+        .and_then(|settings| settings.use_trash)
+        .unwrap_or_else(|| {
+            crate::config::AppConfig::load()
+                .map(|config| config.use_trash)
+                .unwrap_or(true)
+        });
+
+    if use_trash {
+        crate::trash::move_to_trash(project_path, file_path)
+    } else {
+        fs::remove_file(file_path).map_err(|e| e.to_string())
+    }
+}
+
+// ====================
+// Trash Commands
+// ====================
+
+#[command]
+pub fn list_trash(project_path: String) -> Result<Vec<crate::trash::TrashEntry>, String> {
+    crate::trash::list_trash(Path::new(&project_path))
+}
+
+#[command]
+pub fn restore_trash_entry(project_path: String, entry_id: String) -> Result<String, String> {
+    let entry = validate_relative_path(&entry_id)?;
+    if entry.as_os_str().is_empty() {
+        return Err("Trash entry is required".to_string());
+    }
+    crate::trash::restore_entry(Path::new(&project_path), &entry)
+}
+
+// ====================
+// Backup Commands
+// ====================
+
+#[command]
+pub fn list_backups(project_path: String, file_id: String) -> Result<Vec<crate::backups::BackupEntry>, String> {
+    let file = validate_relative_path(&file_id)?;
+    Ok(crate::backups::list_backups(Path::new(&project_path), &file))
+}
+
+/// Restore a backup over its original file and return the file id
+#[command]
+pub fn restore_backup(project_path: String, backup_id: String) -> Result<String, String> {
+    let backup = validate_relative_path(&backup_id)?;
+    if backup.as_os_str().is_empty() {
+        return Err("Backup id is required".to_string());
+    }
+    let max_backups = crate::config::AppConfig::load()
+        .map(|config| config.max_backups_per_file)
+        .unwrap_or(0);
+    crate::backups::restore_backup(Path::new(&project_path), &backup, max_backups)
+}
+
+// ====================
+// Import Commands
+// ====================
+
+/// Copy markdown files from a folder (and its subfolders) into a content
+/// section. Frontmatter is normalized on the way in: a missing title comes
+/// from the first heading or file name, a missing date from the file's mtime.
+/// File names are sanitized and made unique; other files are skipped.
+#[command]
+pub fn import_markdown_files(
+    project_path: String,
+    source_dir: String,
+    target_section: String,
+) -> Result<Vec<ImportResult>, String> {
+    let project = HugoProject::new(PathBuf::from(&project_path));
+    let source_root = Path::new(&source_dir);
+    if !source_root.is_dir() {
+        return Err("Source folder not found".to_string());
+    }
+    let section = validate_relative_path(&target_section)?;
+    let target_dir = project.get_content_dir().join(section);
+
+    // Collect first, so files written into the target are never re-imported
+    let sources: Vec<PathBuf> = walkdir::WalkDir::new(source_root)
+        .min_depth(1)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .map(|entry| entry.into_path())
+        .filter(|path| {
+            path.is_file()
+                && path
+                    .extension()
+                    .and_then(|s| s.to_str())
+                    .is_some_and(|ext| ext.eq_ignore_ascii_case("md") || ext.eq_ignore_ascii_case("markdown"))
+        })
+        .collect();
+
+    fs::create_dir_all(&target_dir)
+        .map_err(|e| format!("Failed to create target directory: {}", e))?;
+
+    let results = sources
+        .iter()
+        .map(|source| {
+            let result = import_markdown_file(source, &target_dir, Path::new(&project_path));
+            ImportResult {
+                source: source.to_string_lossy().to_string(),
+                success: result.is_ok(),
+                id: result.as_ref().ok().cloned(),
+                error: result.err(),
+            }
+        })
+        .collect();
+
+    Ok(results)
+}
+
+/// Import one file and return its new id
+fn import_markdown_file(source: &Path, target_dir: &Path, project_root: &Path) -> Result<String, String> {
+    let post = Post::from_imported_file(source, project_root)?;
+
+    // Bundles are named after their folder rather than `index`
+    let stem = match source.file_stem().and_then(|s| s.to_str()) {
+        Some("index" | "_index") => source.parent().and_then(|p| p.file_name()).and_then(|s| s.to_str()),
+        stem => stem,
+    };
+    let stem = content_file_stem(project_root, stem.unwrap_or(""), "post");
+    let dest = unique_path(target_dir, &stem, "md");
+
+    crate::files::write_file_atomic(&dest, post.to_markdown(final_newline())?.as_bytes())
+        .map_err(|e| format!("Failed to write file: {}", e))?;
+
+    Ok(dest
+        .strip_prefix(project_root)
+        .map(|p| p.to_string_lossy().replace('\\', "/"))
+        .unwrap_or_default())
+}
+
+// ====================
+// Git Commands
+// ====================
+
+/// Changed and untracked files under content/, relative to the project
+#[command]
+pub fn git_status(project_path: String) -> Result<Vec<crate::git::GitFileStatus>, String> {
+    crate::git::status(Path::new(&project_path))
+}
+
+/// Stage the given project-relative paths and commit them. Returns the
+/// commit id.
+#[command]
+pub fn git_commit(project_path: String, message: String, paths: Vec<String>) -> Result<String, String> {
+    crate::git::commit(Path::new(&project_path), &message, &paths)
+}
+
+// ====================
+// Watcher Commands
+// ====================
+
+/// Watch content/ and static/ for outside changes, emitting `project://changed`.
+/// Starting a watch for another project stops the previous one.
+#[command]
+pub fn start_watching_project(app: AppHandle, project_path: String) -> Result<(), String> {
+    let project = HugoProject::new(PathBuf::from(&project_path));
+    project.validate()?;
+    crate::watcher::start(app, &project.path)
+}
+
+#[command]
+pub fn stop_watching_project() -> Result<(), String> {
+    crate::watcher::stop();
+    Ok(())
+}
+
+/// Forget cached post metadata, forcing the next listing to re-read files
+#[command]
+pub fn invalidate_content_cache(project_path: String) -> Result<(), String> {
+    crate::content_cache::invalidate(Path::new(&project_path));
+    Ok(())
+}
+
+// ====================
+// App Config Commands
+// ====================
+
+#[command]
+pub fn get_app_config() -> Result<crate::config::AppConfig, String> {
+    crate::config::AppConfig::load()
+}
+
 #[command]
 pub fn save_app_config(config: crate::config::AppConfig) -> Result<(), String> {
     config.save()
 }
 
+/// Where the app config lives, for support and hand-editing
+#[command]
+pub fn get_app_config_path() -> Result<String, String> {
+    crate::config::AppConfig::get_config_path().map(|path| path.to_string_lossy().to_string())
+}
+
+/// Replace the app config with the defaults, e.g. to recover from a broken file
+#[command]
+pub fn reset_app_config() -> Result<crate::config::AppConfig, String> {
+    let config = crate::config::AppConfig::default();
+    config.save()?;
+    Ok(config)
+}
+
+#[command]
+pub fn pin_project(project_path: String) -> Result<Vec<String>, String> {
+    let mut config = crate::config::AppConfig::load()?;
+    config.pin_project(project_path);
+    config.save()?;
+    Ok(config.pinned_projects)
+}
+
+#[command]
+pub fn unpin_project(project_path: String) -> Result<Vec<String>, String> {
+    let mut config = crate::config::AppConfig::load()?;
+    config.unpin_project(&project_path);
+    config.save()?;
+    Ok(config.pinned_projects)
+}
+
+#[command]
+pub fn remove_recent_project(project_path: String) -> Result<Vec<String>, String> {
+    let mut config = crate::config::AppConfig::load()?;
+    config.remove_recent_project(&project_path);
+    config.save()?;
+    Ok(config.recent_projects)
+}
+
+#[command]
+pub fn clear_recent_projects() -> Result<Vec<String>, String> {
+    let mut config = crate::config::AppConfig::load()?;
+    config.clear_recent_projects();
+    config.save()?;
+    Ok(config.recent_projects)
+}
+
+#[command]
+pub fn prune_recent_projects() -> Result<Vec<String>, String> {
+    let mut config = crate::config::AppConfig::load()?;
+    config.prune_recent_projects();
+    config.save()?;
+    Ok(config.recent_projects)
+}
+
 // ====================
 // Helper Functions
 // ====================
 
-fn sanitize_filename(title: &str) -> String {
+/// File name for `title` in the project's slug mode, which falls back to
+/// the app config's
+fn sanitize_filename(project_path: &Path, title: &str) -> String {
+    let mode = crate::project_settings::ProjectSettings::load(project_path)
+        .ok()
+        .and_then(|settings| settings.slug_mode)
+        .unwrap_or_else(|| {
+            crate::config::AppConfig::load()
+                .map(|config| config.slug_mode)
+                .unwrap_or_default()
+        });
+    slugify(title, mode)
+}
+
+fn slugify(title: &str, mode: SlugMode) -> String {
     use regex::Regex;
 
-    // Transliterate Russian to Latin (basic)
-    let transliterated = transliterate_russian(title);
+    let result = match mode {
+        // Transliterate Cyrillic to Latin
+        SlugMode::Ascii => transliterate_cyrillic(title),
+        SlugMode::Unicode => title.to_string(),
+    };
 
     // Convert to lowercase, replace spaces with hyphens
-    let result = transliterated
+    let result = result
         .to_lowercase()
         .replace(&[' ', '_', '+'][..], "-");
 
     // Remove special characters except alphanumerics and hyphens
-    let re = Regex::new(r"[^a-z0-9-]").unwrap();
-    let cleaned = re.replace_all(&result, "");
+    let cleaned = match mode {
+        SlugMode::Ascii => Regex::new(r"[^a-z0-9-]").unwrap().replace_all(&result, "").to_string(),
+        SlugMode::Unicode => result
+            .chars()
+            .map(|ch| if ch.is_whitespace() { '-' } else { ch })
+            .filter(|ch| ch.is_alphanumeric() || *ch == '-')
+            .collect(),
+    };
 
     // Remove consecutive hyphens
     let re = Regex::new(r"-+").unwrap();
     let deduped = re.replace_all(&cleaned, "-");
 
     // Trim hyphens from start and end
-    deduped.trim_matches('-').to_string()
+    let slug = deduped.trim_matches('-').to_string();
+
+    // Windows refuses these names with any extension, so `con.md` can't exist
+    if is_windows_reserved_name(&slug) {
+        return format!("{}-post", slug);
+    }
+    slug
+}
+
+fn is_windows_reserved_name(name: &str) -> bool {
+    let name = name.to_ascii_lowercase();
+    match name.as_str() {
+        "con" | "prn" | "aux" | "nul" => true,
+        _ => ["com", "lpt"].iter().any(|prefix| {
+            name.strip_prefix(prefix)
+                .is_some_and(|n| n.len() == 1 && matches!(n.as_bytes()[0], b'1'..=b'9'))
+        }),
+    }
 }
 
-fn transliterate_russian(text: &str) -> String {
-    let mapping = [
+/// Cyrillic to Latin, covering Russian, Ukrainian, Belarusian and the
+/// Serbian/Macedonian letters. Uppercase letters map to capitalized output;
+/// anything not in the table passes through unchanged.
+fn transliterate_cyrillic(text: &str) -> String {
+    const MAPPING: [(char, &str); 50] = [
+        // Russian
         ('а', "a"), ('б', "b"), ('в', "v"), ('г', "g"), ('д', "d"), ('е', "e"),
         ('ё', "yo"), ('ж', "zh"), ('з', "z"), ('и', "i"), ('й', "y"), ('к', "k"),
         ('л', "l"), ('м', "m"), ('н', "n"), ('о', "o"), ('п', "p"), ('р', "r"),
         ('с', "s"), ('т', "t"), ('у', "u"), ('ф', "f"), ('х', "h"), ('ц', "ts"),
         ('ч', "ch"), ('ш', "sh"), ('щ', "shch"), ('ъ', ""), ('ы', "y"), ('ь', ""),
         ('э', "e"), ('ю', "yu"), ('я', "ya"),
-        ('А', "A"), ('Б', "B"), ('В', "V"), ('Г', "G"), ('Д', "D"), ('Е', "E"),
-        ('Ё', "Yo"), ('Ж', "Zh"), ('З', "Z"), ('И', "I"), ('Й', "Y"), ('К', "K"),
-        ('Л', "L"), ('М', "M"), ('Н', "N"), ('О', "O"), ('П', "P"), ('Р', "R"),
-        ('С', "S"), ('Т', "T"), ('У', "U"), ('Ф', "F"), ('Х', "H"), ('Ц', "Ts"),
-        ('Ч', "Ch"), ('Ш', "Sh"), ('Щ', "Shch"), ('Ъ', ""), ('Ы', "Y"), ('Ь', ""),
-        ('Э', "E"), ('Ю', "Yu"), ('Я', "Ya"),
+        // Ukrainian and Belarusian
+        ('і', "i"), ('ї', "yi"), ('є', "ye"), ('ґ', "g"), ('ў', "u"),
+        // Serbian and Macedonian
+        ('ђ', "dj"), ('ј', "j"), ('љ', "lj"), ('њ', "nj"), ('ћ', "c"), ('џ', "dz"),
+        ('ѓ', "gj"), ('ќ', "kj"), ('ѕ', "dz"),
+        // Apostrophes used as hard signs in Ukrainian and Belarusian
+        ('ʼ', ""), ('’', ""), ('\'', ""),
     ];
 
-    let mut result = text.to_string();
-
-    for (from, to) in &mapping {
-        result = result.replace(*from, to);
+    let mut result = String::with_capacity(text.len());
+    for ch in text.chars() {
+        let lower = ch.to_lowercase().next().unwrap_or(ch);
+        match MAPPING.iter().find(|(from, _)| *from == lower) {
+            Some((_, to)) if lower != ch => {
+                let mut chars = to.chars();
+                if let Some(first) = chars.next() {
+                    result.extend(first.to_uppercase());
+                    result.push_str(chars.as_str());
+                }
+            }
+            Some((_, to)) => result.push_str(to),
+            None => result.push(ch),
+        }
     }
 
     result
@@ -955,7 +3490,7 @@ fn create_image_info(
         .ok()
         .or(metadata.modified().ok())
         .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
-        .map(|d: std::time::Duration| d.as_secs() as i64)
+        .map(|d: std::time::Duration| d.as_millis() as i64)
         .unwrap_or(0);
 
     // Get image dimensions if possible
@@ -973,9 +3508,47 @@ fn create_image_info(
     })
 }
 
-fn get_image_dimensions(_path: &Path) -> (Option<u32>, Option<u32>) {
-    // For now, return None. Can be implemented with image crate later
-    (None, None)
+fn get_image_dimensions(path: &Path) -> (Option<u32>, Option<u32>) {
+    // Only reads the header; SVG and unknown formats have no raster size
+    match image::image_dimensions(path) {
+        Ok((width, height)) => (Some(width), Some(height)),
+        Err(_) => (None, None),
+    }
+}
+
+/// Group a line diff into added, removed and changed runs
+fn diff_lines(old: &str, new: &str) -> Vec<DiffChange> {
+    use similar::{DiffOp, TextDiff};
+
+    let diff = TextDiff::from_lines(old, new);
+    let old_lines: Vec<&str> = diff.old_slices().to_vec();
+    let new_lines: Vec<&str> = diff.new_slices().to_vec();
+    let lines = |slices: &[&str], range: std::ops::Range<usize>| -> Vec<String> {
+        slices[range]
+            .iter()
+            .map(|line| line.trim_end_matches(['\n', '\r']).to_string())
+            .collect()
+    };
+
+    diff.ops()
+        .iter()
+        .filter_map(|op| {
+            let kind = match op {
+                DiffOp::Equal { .. } => return None,
+                DiffOp::Delete { .. } => DiffKind::Removed,
+                DiffOp::Insert { .. } => DiffKind::Added,
+                DiffOp::Replace { .. } => DiffKind::Changed,
+            };
+            let (old_range, new_range) = (op.old_range(), op.new_range());
+            Some(DiffChange {
+                kind,
+                old_start: old_range.start + 1,
+                old_lines: lines(&old_lines, old_range),
+                new_start: new_range.start + 1,
+                new_lines: lines(&new_lines, new_range),
+            })
+        })
+        .collect()
 }
 
 // ====================
@@ -984,7 +3557,7 @@ fn get_image_dimensions(_path: &Path) -> (Option<u32>, Option<u32>) {
 
 impl Page {
     pub fn from_file(file_path: &Path, project_path: &Path) -> Result<Self, String> {
-        let content = crate::files::read_file(file_path)?;
+        let (content, encoding) = crate::files::read_file_with_encoding(file_path)?;
         let (doc, _) = crate::markdown::MarkdownDocument::parse(&content)?;
 
         let metadata = fs::metadata(file_path)
@@ -995,14 +3568,14 @@ impl Page {
             .ok()
             .or(metadata.modified().ok())
             .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
-            .map(|d: std::time::Duration| d.as_secs() as i64)
+            .map(|d: std::time::Duration| d.as_millis() as i64)
             .unwrap_or(0);
 
         let modified_at = metadata
             .modified()
             .ok()
             .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
-            .map(|d: std::time::Duration| d.as_secs() as i64)
+            .map(|d: std::time::Duration| d.as_millis() as i64)
             .unwrap_or(0);
 
         let id = file_path
@@ -1012,6 +3585,8 @@ impl Page {
             .unwrap_or("")
             .to_string();
 
+        let word_count = crate::markdown::count_words(&doc.content);
+
         Ok(Self {
             id,
             title: doc.frontmatter.title.clone(),
@@ -1020,13 +3595,22 @@ impl Page {
             file_path: file_path.to_string_lossy().to_string(),
             created_at,
             modified_at,
+            frontmatter_format: doc.format,
+            layout: doc.layout,
+            word_count,
+            reading_time_minutes: crate::markdown::reading_time_minutes(
+                word_count,
+                crate::config::words_per_minute(),
+            ),
+            language: None,
+            encoding: encoding.map(str::to_string),
         })
     }
 }
 
 impl Draft {
     pub fn from_file(file_path: &Path, project_path: &Path) -> Result<Self, String> {
-        let content = crate::files::read_file(file_path)?;
+        let (content, encoding) = crate::files::read_file_with_encoding(file_path)?;
         let (doc, _) = crate::markdown::MarkdownDocument::parse(&content)?;
 
         let metadata = fs::metadata(file_path)
@@ -1037,14 +3621,14 @@ impl Draft {
             .ok()
             .or(metadata.modified().ok())
             .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
-            .map(|d: std::time::Duration| d.as_secs() as i64)
+            .map(|d: std::time::Duration| d.as_millis() as i64)
             .unwrap_or(0);
 
         let modified_at = metadata
             .modified()
             .ok()
             .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
-            .map(|d: std::time::Duration| d.as_secs() as i64)
+            .map(|d: std::time::Duration| d.as_millis() as i64)
             .unwrap_or(0);
 
         let id = file_path
@@ -1054,6 +3638,8 @@ impl Draft {
             .unwrap_or("")
             .to_string();
 
+        let word_count = crate::markdown::count_words(&doc.content);
+
         Ok(Self {
             id,
             title: doc.frontmatter.title.clone(),
@@ -1062,25 +3648,444 @@ impl Draft {
             file_path: file_path.to_string_lossy().to_string(),
             created_at,
             modified_at,
+            frontmatter_format: doc.format,
+            layout: doc.layout,
+            word_count,
+            reading_time_minutes: crate::markdown::reading_time_minutes(
+                word_count,
+                crate::config::words_per_minute(),
+            ),
+            encoding: encoding.map(str::to_string),
         })
     }
 }
 
-// ====================
-// Data Types
-// ====================
-
+// ====================
+// Data Types
+// ====================
+
+/// Which files the static browser lists. Folders are always included.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub enum StaticFilter {
+    #[default]
+    Images,
+    All,
+    /// Only files with one of these extensions (with or without the dot)
+    Extensions(Vec<String>),
+}
+
+impl StaticFilter {
+    fn matches(&self, extension: &str, is_image: bool) -> bool {
+        match self {
+            StaticFilter::Images => is_image,
+            StaticFilter::All => true,
+            StaticFilter::Extensions(extensions) => !extension.is_empty()
+                && extensions
+                    .iter()
+                    .any(|ext| ext.trim_start_matches('.').eq_ignore_ascii_case(extension)),
+        }
+    }
+}
+
+fn is_static_image_extension(extension: &str) -> bool {
+    matches!(extension, "png" | "jpg" | "jpeg" | "gif" | "webp" | "svg" | "ico")
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct StaticEntry {
+    pub name: String,
+    pub path: String,
+    pub kind: String,
+    pub size: u64,
+    /// Milliseconds since the epoch
+    pub created_at: i64,
+    /// Milliseconds since the epoch
+    pub modified_at: i64,
+    pub url: Option<String>,
+    pub full_path: String,
+    /// Nested entries, only filled in by `list_static_tree`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub children: Option<Vec<StaticEntry>>,
+    /// Files under a folder, only set when directory sizes were requested
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file_count: Option<u64>,
+}
+
+#[derive(serde::Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct HugoOutputEvent {
+    pub run_id: String,
+    pub stream: crate::hugo::OutputStream,
+    pub line: String,
+}
+
+#[derive(serde::Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct HugoCompleteEvent {
+    pub run_id: String,
+    pub success: bool,
+    pub exit_code: i32,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RelatedPost {
+    pub id: String,
+    pub title: String,
+    /// Number of shared tags and categories
+    pub score: usize,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TermCount {
+    pub name: String,
+    pub count: usize,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ContentStats {
+    pub post_count: usize,
+    pub page_count: usize,
+    pub draft_count: usize,
+    /// Words across published posts
+    pub total_words: usize,
+    pub tag_count: usize,
+    pub category_count: usize,
+    pub static_images: usize,
+    /// Bytes of every file under `static/`
+    pub static_size: u64,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BrokenLink {
+    pub file_id: String,
+    pub target: String,
+    /// 1-based line in the file, counting its frontmatter
+    pub line: usize,
+    pub kind: String,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum SortKey {
+    /// Frontmatter date, falling back to the file's mtime
+    Date,
+    Title,
+    Modified,
+    /// Frontmatter weight, lowest first; unweighted content sorts last
+    Weight,
+}
+
+/// Listing order. Without one, posts are newest-dated first and pages
+/// most recently modified first.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+pub struct SortSpec {
+    pub key: SortKey,
+    #[serde(default)]
+    pub ascending: bool,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum DraftState {
+    #[default]
+    Published,
+    Drafts,
+    All,
+}
+
+/// Narrows `list_posts`. Tags must all be present; terms compare
+/// case-insensitively, as Hugo lowercases them.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct PostFilter {
+    #[serde(default)]
+    pub tags: Vec<String>,
+    pub category: Option<String>,
+    #[serde(default)]
+    pub draft_state: DraftState,
+}
+
+impl PostFilter {
+    fn matches(&self, state: ContentState, frontmatter: &crate::markdown::Frontmatter) -> bool {
+        let is_draft = state == ContentState::Draft;
+        let state_matches = match self.draft_state {
+            DraftState::Published => !is_draft,
+            DraftState::Drafts => is_draft,
+            DraftState::All => true,
+        };
+        let has_term = |terms: &[String], wanted: &str| {
+            terms.iter().any(|term| term.to_lowercase() == wanted.to_lowercase())
+        };
+
+        state_matches
+            && self.tags.iter().all(|tag| has_term(&frontmatter.tags, tag))
+            && self
+                .category
+                .as_deref()
+                .is_none_or(|category| has_term(&frontmatter.categories, category))
+    }
+}
+
+/// One edit made by `batch_update_frontmatter`
+#[derive(serde::Serialize, serde::Deserialize, Clone)]
+#[serde(tag = "op", rename_all = "camelCase")]
+pub enum FrontmatterChange {
+    /// Set a key, replacing any current value
+    Set { key: String, value: serde_json::Value },
+    /// Remove a key if present
+    Remove { key: String },
+    /// Add a value to a list (e.g. a category) unless it's already there
+    Append { key: String, value: serde_json::Value },
+}
+
+impl FrontmatterChange {
+    fn key(&self) -> &str {
+        match self {
+            Self::Set { key, .. } | Self::Remove { key } | Self::Append { key, .. } => key,
+        }
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        let key = self.key();
+        if key.trim().is_empty() {
+            return Err("Frontmatter key is required".to_string());
+        }
+        if matches!(self, Self::Remove { .. }) && matches!(key, "title" | "date") {
+            return Err(format!("'{}' can't be removed", key));
+        }
+        Ok(())
+    }
+
+    fn apply(&self, mapping: &mut serde_yaml::Mapping) -> Result<(), String> {
+        let to_yaml = |value: &serde_json::Value| {
+            serde_yaml::to_value(value).map_err(|e| format!("Invalid value for '{}': {}", self.key(), e))
+        };
+
+        match self {
+            Self::Set { key, value } => {
+                mapping.insert(key.as_str().into(), to_yaml(value)?);
+            }
+            Self::Remove { key } => {
+                mapping.remove(key.as_str());
+            }
+            Self::Append { key, value } => {
+                let value = to_yaml(value)?;
+                let entry = mapping
+                    .entry(key.as_str().into())
+                    .or_insert_with(|| serde_yaml::Value::Sequence(Vec::new()));
+                if entry.is_null() {
+                    *entry = serde_yaml::Value::Sequence(Vec::new());
+                }
+                let serde_yaml::Value::Sequence(items) = entry else {
+                    return Err(format!("'{}' is not a list", key));
+                };
+                if !items.contains(&value) {
+                    items.push(value);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchUpdateResult {
+    pub id: String,
+    pub success: bool,
+    pub error: Option<String>,
+}
+
+/// One entry of the file written by `export_content_index`
+#[derive(serde::Serialize, serde::Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ContentIndexEntry {
+    pub id: String,
+    /// `post` or `page`
+    pub kind: String,
+    pub title: String,
+    pub date: String,
+    pub tags: Vec<String>,
+    pub categories: Vec<String>,
+    pub word_count: usize,
+    /// Raw value of the configured preview image field
+    pub preview_image: Option<String>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportResult {
+    /// Absolute path of the imported file
+    pub source: String,
+    pub success: bool,
+    /// Id of the new content file
+    pub id: Option<String>,
+    pub error: Option<String>,
+}
+
+/// A content file referencing an image
+#[derive(serde::Serialize, serde::Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ImageUsage {
+    /// Id of the post, page or draft
+    pub id: String,
+    /// 1-based lines of the file, frontmatter included
+    pub lines: Vec<usize>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Copy, PartialEq, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum DiffKind {
+    Added,
+    Removed,
+    Changed,
+}
+
+/// A run of differing lines. Line numbers are 1-based; `old_start` is in the
+/// on-disk text and `new_start` in the editor's.
+#[derive(serde::Serialize, serde::Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DiffChange {
+    pub kind: DiffKind,
+    pub old_start: usize,
+    pub old_lines: Vec<String>,
+    pub new_start: usize,
+    pub new_lines: Vec<String>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PostDiff {
+    /// Modification time of the file on disk, in milliseconds since the epoch
+    pub modified_at: i64,
+    pub changes: Vec<DiffChange>,
+}
+
+/// One page of `list_posts`, with the number of posts across all pages
+#[derive(serde::Serialize, serde::Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PostList {
+    pub posts: Vec<Post>,
+    pub total: usize,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MenuEntry {
+    /// Menu the entry belongs to, e.g. `main`
+    pub menu: String,
+    pub name: String,
+    pub url: Option<String>,
+    pub weight: i64,
+    pub parent: Option<String>,
+    pub identifier: Option<String>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigIssue {
+    /// Config key the issue is about, if any
+    pub key: Option<String>,
+    pub severity: IssueSeverity,
+    pub message: String,
+}
+
+impl ConfigIssue {
+    fn error(key: Option<&str>, message: &str) -> Self {
+        Self {
+            key: key.map(str::to_string),
+            severity: IssueSeverity::Error,
+            message: message.to_string(),
+        }
+    }
+
+    fn warning(key: Option<&str>, message: &str) -> Self {
+        Self {
+            key: key.map(str::to_string),
+            severity: IssueSeverity::Warning,
+            message: message.to_string(),
+        }
+    }
+}
+
 #[derive(serde::Serialize, serde::Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
-pub struct StaticEntry {
+pub struct ShortcodeUsage {
     pub name: String,
-    pub path: String,
-    pub kind: String,
+    pub count: usize,
+    pub files: Vec<String>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CopiedImage {
+    pub url: String,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
     pub size: u64,
-    pub created_at: i64,
-    pub modified_at: i64,
-    pub url: Option<String>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ImageVerification {
+    pub path: String,
+    /// Format the extension implies, e.g. `png`; `None` for non-raster
+    /// extensions like `.svg`
+    pub extension_format: Option<String>,
+    /// Format detected from the file's content
+    pub actual_format: Option<String>,
+    pub matches: bool,
+    /// Extension to rename to when they don't match
+    pub suggested_extension: Option<String>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ImageCopyResult {
+    /// Source path as given
+    pub source: String,
+    pub success: bool,
+    pub image: Option<CopiedImage>,
+    pub error: Option<String>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SectionInfo {
+    /// Folder relative to content/; empty for the home page
+    pub path: String,
+    /// Page id of the section's `_index.md`, whether or not it exists yet
+    pub index_id: String,
+    pub has_index: bool,
+    pub title: Option<String>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ResolvedImage {
     pub full_path: String,
+    /// Unset for SVG and other formats without a raster size
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PreviewImageReport {
+    pub post_id: String,
+    pub title: String,
+    pub value: String,
+    pub expected_path: String,
+    pub missing: bool,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
 }
 
 #[derive(serde::Serialize, serde::Deserialize, Clone)]
@@ -1091,6 +4096,9 @@ pub struct HugoConfig {
     pub language_code: Option<String>,
     pub default_content_language: Option<String>,
     pub theme: Option<String>,
+    /// Language keys from the `languages` table, in config order
+    #[serde(default)]
+    pub languages: Vec<String>,
     pub raw: serde_json::Value,
 }
 
@@ -1102,6 +4110,11 @@ impl HugoConfig {
         let default_content_language =
             extract_string(&raw, &["defaultContentLanguage", "default_content_language"]);
         let theme = extract_string(&raw, &["theme"]);
+        let languages = raw
+            .get("languages")
+            .and_then(|v| v.as_object())
+            .map(|table| table.keys().map(|k| k.to_lowercase()).collect())
+            .unwrap_or_default();
 
         Self {
             title,
@@ -1109,11 +4122,152 @@ impl HugoConfig {
             language_code,
             default_content_language,
             theme,
+            languages,
             raw,
         }
     }
 }
 
+impl HugoConfig {
+    /// The `params` table, or an empty object when the site has none
+    pub fn params(&self) -> serde_json::Value {
+        self.raw
+            .get("params")
+            .filter(|v| v.is_object())
+            .cloned()
+            .unwrap_or_else(|| serde_json::Value::Object(serde_json::Map::new()))
+    }
+
+    /// Menu entries from `menu`/`menus`, flattened to one list sorted by menu
+    /// and weight. Both `[[menu.main]]` lists and single-entry tables are accepted.
+    pub fn menus(&self) -> Vec<MenuEntry> {
+        let mut entries = Vec::new();
+
+        for key in ["menu", "menus"] {
+            let Some(menus) = self.raw.get(key).and_then(|v| v.as_object()) else {
+                continue;
+            };
+            for (menu, items) in menus {
+                let items: Vec<&serde_json::Value> = match items {
+                    serde_json::Value::Array(items) => items.iter().collect(),
+                    item @ serde_json::Value::Object(_) => vec![item],
+                    _ => continue,
+                };
+                for item in items {
+                    let Some(name) = extract_string(item, &["name", "identifier"]) else {
+                        continue;
+                    };
+                    entries.push(MenuEntry {
+                        menu: menu.clone(),
+                        name,
+                        url: extract_string(item, &["url", "pageRef", "pageref"]),
+                        weight: item.get("weight").and_then(|w| w.as_i64()).unwrap_or(0),
+                        parent: extract_string(item, &["parent"]),
+                        identifier: extract_string(item, &["identifier"]),
+                    });
+                }
+            }
+        }
+
+        entries.sort_by(|a, b| a.menu.cmp(&b.menu).then(a.weight.cmp(&b.weight)));
+        entries
+    }
+}
+
+/// Language layout of a project's content, used to tell translations apart
+struct ContentLanguages {
+    content_dir: PathBuf,
+    default_language: String,
+    languages: Vec<String>,
+}
+
+impl ContentLanguages {
+    fn load(project_path: &str) -> Self {
+        let config = get_project_config(project_path.to_string(), None).ok();
+        let default_language = config
+            .as_ref()
+            .and_then(|c| c.default_content_language.clone())
+            .unwrap_or_else(|| "en".to_string())
+            .to_lowercase();
+
+        Self {
+            content_dir: HugoProject::new(PathBuf::from(project_path)).get_content_dir(),
+            default_language,
+            languages: config.map(|c| c.languages).unwrap_or_default(),
+        }
+    }
+
+    fn is_language(&self, code: &str) -> bool {
+        let code = code.to_lowercase();
+        if self.languages.is_empty() {
+            // Without a languages table, accept anything shaped like a code
+            // (en, pt-br, zh-hans) so `post.ru.md` is still recognized
+            let mut parts = code.split('-');
+            let primary = parts.next().unwrap_or_default();
+            (2..=3).contains(&primary.len())
+                && primary.chars().all(|c| c.is_ascii_lowercase())
+                && parts.all(|p| (2..=4).contains(&p.len()) && p.chars().all(|c| c.is_ascii_alphanumeric()))
+        } else {
+            code == self.default_language || self.languages.contains(&code)
+        }
+    }
+
+    /// Language from a `.<lang>.md` suffix, else a `content/<lang>/` tree,
+    /// else the default content language
+    fn language_of(&self, file_path: &Path) -> String {
+        let suffix = file_path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .and_then(|stem| stem.rsplit_once('.'))
+            .map(|(_, lang)| lang)
+            .filter(|lang| self.is_language(lang));
+        if let Some(lang) = suffix {
+            return lang.to_lowercase();
+        }
+
+        self.language_dir(file_path)
+            .unwrap_or_else(|| self.default_language.clone())
+    }
+
+    fn language_dir(&self, file_path: &Path) -> Option<String> {
+        // Only explicitly configured languages count as content trees, so a
+        // section named e.g. "go" isn't mistaken for one
+        let first = file_path
+            .strip_prefix(&self.content_dir)
+            .ok()?
+            .components()
+            .next()?
+            .as_os_str()
+            .to_str()?
+            .to_lowercase();
+        let configured = first == self.default_language || self.languages.contains(&first);
+        (configured && !self.languages.is_empty()).then_some(first)
+    }
+
+    /// Path shared by all translations of a document: relative to content/,
+    /// without the language tree, language suffix or extension
+    fn translation_key(&self, file_path: &Path) -> Option<String> {
+        let relative = file_path.strip_prefix(&self.content_dir).ok()?;
+        let mut components: Vec<String> = relative
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy().to_string())
+            .collect();
+        if self.language_dir(file_path).is_some() && components.len() > 1 {
+            components.remove(0);
+        }
+
+        let file_name = components.pop()?;
+        let stem = file_name.strip_suffix(".md").unwrap_or(&file_name);
+        let stem = match stem.rsplit_once('.') {
+            Some((base, lang)) if self.is_language(lang) => base,
+            _ => stem,
+        };
+        components.push(stem.to_string());
+
+        Some(components.join("/"))
+    }
+}
+
 // ====================
 // Hugo Commands
 // ====================
@@ -1122,15 +4276,69 @@ impl HugoConfig {
 pub fn run_hugo_command(
     project_path: String,
     args: Vec<String>,
+    env: Option<HashMap<String, String>>,
 ) -> Result<crate::hugo::CommandOutput, String> {
     let project = HugoProject::new(PathBuf::from(&project_path));
-    project.run_command(&args)
+    project.run_command(&args, &env.unwrap_or_default())
+}
+
+/// Run a hugo command in the background, emitting `hugo://output` for each
+/// line and `hugo://complete` with the exit code. Returns the run id that
+/// tags those events.
+#[command]
+pub fn run_hugo_command_streaming(
+    app: AppHandle,
+    project_path: String,
+    args: Vec<String>,
+    env: Option<HashMap<String, String>>,
+) -> Result<String, String> {
+    let project = HugoProject::new(PathBuf::from(&project_path));
+    let child = project.spawn_command(&args, &env.unwrap_or_default())?;
+    let run_id = format!("{}-{}", child.id(), chrono::Utc::now().timestamp_millis());
+
+    let line_app = app.clone();
+    let line_run_id = run_id.clone();
+    let complete_run_id = run_id.clone();
+    std::thread::spawn(move || {
+        let exit_code = crate::hugo::stream_output(child, move |stream, line| {
+            let _ = line_app.emit(
+                "hugo://output",
+                HugoOutputEvent {
+                    run_id: line_run_id.clone(),
+                    stream,
+                    line,
+                },
+            );
+        });
+        let _ = app.emit(
+            "hugo://complete",
+            HugoCompleteEvent {
+                run_id: complete_run_id,
+                success: exit_code == 0,
+                exit_code,
+            },
+        );
+    });
+
+    Ok(run_id)
 }
 
 #[command]
-pub fn start_hugo_server(project_path: String) -> Result<String, String> {
+pub fn start_hugo_server(
+    project_path: String,
+    options: Option<crate::hugo::ServerOptions>,
+) -> Result<crate::hugo::ServerStart, String> {
     let project = HugoProject::new(PathBuf::from(&project_path));
-    project.start_server()
+    project.start_server(&options.unwrap_or_default())
+}
+
+/// Replace the environment variables every Hugo command in the project gets
+#[command]
+pub fn set_hugo_env_defaults(project_path: String, env: HashMap<String, String>) -> Result<(), String> {
+    let project_root = Path::new(&project_path);
+    let mut settings = crate::project_settings::ProjectSettings::load(project_root)?;
+    settings.env = env;
+    settings.save(project_root)
 }
 
 #[command]
@@ -1138,19 +4346,146 @@ pub fn stop_hugo_server(server_id: String) -> Result<(), String> {
     HugoProject::stop_server(&server_id)
 }
 
+/// Open a running server's site in the default browser
+#[command]
+pub fn open_preview(server_id: String) -> Result<(), String> {
+    let url = HugoProject::server_url(&server_id)?;
+    tauri_plugin_opener::open_url(&url, None::<&str>)
+        .map_err(|e| format!("Failed to open browser: {}", e))
+}
+
+#[command]
+pub fn get_hugo_server_logs(server_id: String) -> Result<Vec<String>, String> {
+    Ok(HugoProject::server_logs(&server_id))
+}
+
+#[command]
+pub fn get_hugo_server_info(project_path: String) -> Result<Option<crate::hugo::ServerInfo>, String> {
+    let project = HugoProject::new(PathBuf::from(&project_path));
+    Ok(project.server_info())
+}
+
 #[command]
 pub fn is_hugo_server_running(project_path: String) -> Result<bool, String> {
     let project = HugoProject::new(PathBuf::from(&project_path));
     Ok(project.is_server_running())
 }
 
+#[command]
+pub fn get_hugo_version(project_path: String) -> Result<crate::hugo::HugoVersion, String> {
+    let project = HugoProject::new(PathBuf::from(&project_path));
+    let output = project.run_command(&["version".to_string()], &HashMap::new())?;
+
+    if !output.success {
+        return Err(format!("hugo version failed: {}", output.stderr.trim()));
+    }
+
+    crate::hugo::parse_hugo_version(&output.stdout)
+        .ok_or_else(|| "Failed to parse hugo version output".to_string())
+}
+
+#[command]
+pub fn detect_hugo_binary() -> Result<crate::hugo::HugoBinary, String> {
+    crate::hugo::detect_hugo_binary()
+}
+
+/// Read the site config the way Hugo does: the root config file, then every
+/// file in `config/_default/`, then `config/<environment>/`. Later sources
+/// are merged over earlier ones; nested tables merge key by key.
+fn load_hugo_config(
+    project: &HugoProject,
+    environment: Option<&str>,
+) -> Result<serde_json::Value, String> {
+    let mut merged = serde_json::Value::Object(serde_json::Map::new());
+    let mut found = false;
+
+    if let Some(config_path) = project.find_config_path() {
+        let content = fs::read_to_string(&config_path)
+            .map_err(|e| format!("Failed to read config: {}", e))?;
+        merge_config(&mut merged, parse_hugo_config(&config_path, &content)?);
+        found = true;
+    }
+
+    let config_root = project.path.join("config");
+    let mut dirs = vec![config_root.join("_default")];
+    if let Some(environment) = environment {
+        validate_folder_name(environment)?;
+        dirs.push(config_root.join(environment));
+    }
+
+    for dir in dirs.iter().filter(|dir| dir.is_dir()) {
+        let mut files: Vec<PathBuf> = fs::read_dir(dir)
+            .map_err(|e| format!("Failed to read config directory: {}", e))?
+            .filter_map(|e| e.ok().map(|e| e.path()))
+            .filter(|path| {
+                path.is_file()
+                    && matches!(
+                        path.extension().and_then(|s| s.to_str()),
+                        Some("toml" | "yaml" | "yml" | "json")
+                    )
+            })
+            .collect();
+        files.sort();
+
+        for path in files {
+            let content = fs::read_to_string(&path)
+                .map_err(|e| format!("Failed to read config: {}", e))?;
+            let value = parse_hugo_config(&path, &content)?;
+            merge_config(&mut merged, nest_config_file(&path, value));
+            found = true;
+        }
+    }
+
+    if !found {
+        return Err(localized(ErrorCode::HugoConfigNotFound));
+    }
+
+    Ok(merged)
+}
+
+/// Place a config directory file under the key its name implies:
+/// `hugo.toml` is the root, `params.toml` becomes `params`, and
+/// `menus.en.toml` becomes `languages.en.menus`
+fn nest_config_file(path: &Path, value: serde_json::Value) -> serde_json::Value {
+    let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or_default();
+    let (name, language) = match stem.split_once('.') {
+        Some((name, language)) => (name, Some(language)),
+        None => (stem, None),
+    };
+
+    let mut value = if matches!(name, "hugo" | "config") {
+        value
+    } else {
+        serde_json::json!({ name: value })
+    };
+    if let Some(language) = language {
+        value = serde_json::json!({ "languages": { language: value } });
+    }
+    value
+}
+
+fn merge_config(base: &mut serde_json::Value, overlay: serde_json::Value) {
+    match (base, overlay) {
+        (serde_json::Value::Object(base), serde_json::Value::Object(overlay)) => {
+            for (key, value) in overlay {
+                match base.get_mut(&key) {
+                    Some(existing) => merge_config(existing, value),
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, overlay) => *base = overlay,
+    }
+}
+
 fn parse_hugo_config(path: &Path, content: &str) -> Result<serde_json::Value, String> {
     match path.extension().and_then(|s| s.to_str()) {
         Some("toml") => {
             let value: toml::Value = toml::from_str(content)
                 .map_err(|e| format!("Failed to parse TOML config: {}", e))?;
-            serde_json::to_value(value)
-                .map_err(|e| format!("Failed to convert TOML config: {}", e))
+            Ok(crate::markdown::toml_to_json(value))
         }
         Some("yml") | Some("yaml") => {
             serde_yaml::from_str(content)
@@ -1164,6 +4499,89 @@ fn parse_hugo_config(path: &Path, content: &str) -> Result<serde_json::Value, St
     }
 }
 
+fn serialize_hugo_config(path: &Path, value: serde_json::Value) -> Result<String, String> {
+    match path.extension().and_then(|s| s.to_str()) {
+        Some("toml") => {
+            // TOML has no null
+            let value = toml::Value::try_from(strip_json_nulls(value))
+                .map_err(|e| format!("Failed to convert config to TOML: {}", e))?;
+            toml::to_string_pretty(&value)
+                .map_err(|e| format!("Failed to serialize TOML config: {}", e))
+        }
+        Some("yml") | Some("yaml") => serde_yaml::to_string(&value)
+            .map_err(|e| format!("Failed to serialize YAML config: {}", e)),
+        Some("json") => serde_json::to_string_pretty(&value)
+            .map(|json| json + "\n")
+            .map_err(|e| format!("Failed to serialize JSON config: {}", e)),
+        _ => Err("Unsupported Hugo config format".to_string()),
+    }
+}
+
+fn strip_json_nulls(value: serde_json::Value) -> serde_json::Value {
+    match value {
+        serde_json::Value::Object(map) => serde_json::Value::Object(
+            map.into_iter()
+                .filter(|(_, v)| !v.is_null())
+                .map(|(k, v)| (k, strip_json_nulls(v)))
+                .collect(),
+        ),
+        serde_json::Value::Array(items) => serde_json::Value::Array(
+            items
+                .into_iter()
+                .filter(|v| !v.is_null())
+                .map(strip_json_nulls)
+                .collect(),
+        ),
+        other => other,
+    }
+}
+
+/// Top-level keys contributed by `config/_default/` files other than `except`
+fn split_config_keys(project: &HugoProject, except: &Path) -> Result<Vec<String>, String> {
+    let Some(dir) = project.find_config_dir() else {
+        return Ok(Vec::new());
+    };
+
+    let mut keys = Vec::new();
+    for entry in fs::read_dir(&dir).map_err(|e| format!("Failed to read config directory: {}", e))? {
+        let path = entry.map_err(|e| format!("Failed to read config directory: {}", e))?.path();
+        if path == except || !path.is_file() {
+            continue;
+        }
+        let Ok(content) = fs::read_to_string(&path) else {
+            continue;
+        };
+        let Ok(value) = parse_hugo_config(&path, &content) else {
+            continue;
+        };
+        if let serde_json::Value::Object(table) = nest_config_file(&path, value) {
+            keys.extend(table.into_iter().map(|(key, _)| key));
+        }
+    }
+    Ok(keys)
+}
+
+/// Set a string key, reusing whichever alias the file already spells it with.
+/// `None` removes every alias.
+fn set_config_string(
+    table: &mut serde_json::Map<String, serde_json::Value>,
+    aliases: &[&str],
+    value: Option<String>,
+) {
+    let existing = aliases.iter().find(|key| table.contains_key(**key)).copied();
+    match value {
+        Some(value) => {
+            let key = existing.unwrap_or(aliases[0]);
+            table.insert(key.to_string(), serde_json::Value::String(value));
+        }
+        None => {
+            for key in aliases {
+                table.shift_remove(*key);
+            }
+        }
+    }
+}
+
 fn extract_string(value: &serde_json::Value, keys: &[&str]) -> Option<String> {
     for key in keys {
         if let Some(found) = value.get(*key) {
@@ -1174,3 +4592,846 @@ fn extract_string(value: &serde_json::Value, keys: &[&str]) -> Option<String> {
     }
     None
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn languages(configured: &[&str]) -> ContentLanguages {
+        ContentLanguages {
+            content_dir: PathBuf::from("/site/content"),
+            default_language: "en".to_string(),
+            languages: configured.iter().map(|l| l.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn load_hugo_config_merges_config_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        let config_dir = dir.path().join("config");
+        fs::create_dir_all(config_dir.join("_default")).unwrap();
+        fs::create_dir_all(config_dir.join("production")).unwrap();
+        fs::write(config_dir.join("_default/hugo.toml"), "title = \"Site\"\nbaseURL = \"http://localhost/\"").unwrap();
+        fs::write(config_dir.join("_default/params.toml"), "author = \"Rail\"\nshowToc = true").unwrap();
+        fs::write(config_dir.join("_default/menus.en.toml"), "[[main]]\nname = \"Home\"\nurl = \"/\"").unwrap();
+        fs::write(config_dir.join("production/hugo.toml"), "baseURL = \"https://example.com/\"").unwrap();
+        fs::write(config_dir.join("production/params.toml"), "showToc = false").unwrap();
+
+        let project = HugoProject::new(dir.path().to_path_buf());
+
+        let config = HugoConfig::from_value(load_hugo_config(&project, None).unwrap());
+        assert_eq!(config.title.as_deref(), Some("Site"));
+        assert_eq!(config.base_url.as_deref(), Some("http://localhost/"));
+        assert_eq!(config.raw["params"]["showToc"], true);
+        assert_eq!(config.raw["languages"]["en"]["menus"]["main"][0]["name"], "Home");
+
+        let config = HugoConfig::from_value(load_hugo_config(&project, Some("production")).unwrap());
+        assert_eq!(config.base_url.as_deref(), Some("https://example.com/"));
+        assert_eq!(config.raw["params"]["showToc"], false);
+        assert_eq!(config.raw["params"]["author"], "Rail");
+    }
+
+    #[test]
+    fn save_project_config_round_trips_format() {
+        let dir = tempfile::tempdir().unwrap();
+        let project_path = dir.path().to_string_lossy().to_string();
+        fs::write(
+            dir.path().join("hugo.toml"),
+            "baseURL = \"https://old.example/\"\ntitle = \"Old\"\ntheme = [\"a\", \"b\"]\n\n[params]\nauthor = \"Me\"\n",
+        )
+        .unwrap();
+
+        let mut config = get_project_config(project_path.clone(), None).unwrap();
+        config.title = Some("New".to_string());
+        config.base_url = Some("https://new.example/".to_string());
+        save_project_config(project_path.clone(), config).unwrap();
+
+        let saved = fs::read_to_string(dir.path().join("hugo.toml")).unwrap();
+        assert!(saved.starts_with("baseURL = \"https://new.example/\"\ntitle = \"New\""));
+        let reloaded = get_project_config(project_path, None).unwrap();
+        assert_eq!(reloaded.raw["theme"], serde_json::json!(["a", "b"]));
+        assert_eq!(reloaded.params()["author"], "Me");
+    }
+
+    #[test]
+    fn sort_content_by_title_and_date() {
+        let page = |id: &str, title: &str, date: &str, modified_at: i64| {
+            let (doc, _) = crate::markdown::MarkdownDocument::parse(&format!(
+                "---\ntitle: {}\ndate: \"{}\"\n---\n",
+                title, date
+            ))
+            .unwrap();
+            Page {
+                id: id.to_string(),
+                title: title.to_string(),
+                content: String::new(),
+                frontmatter: doc.frontmatter,
+                file_path: id.to_string(),
+                created_at: 0,
+                modified_at,
+                frontmatter_format: Default::default(),
+                layout: Default::default(),
+                word_count: 0,
+                reading_time_minutes: 0,
+                language: None,
+                encoding: None,
+            }
+        };
+        let pages = || {
+            vec![
+                page("b.md", "beta", "2024-03-01", 1),
+                page("a.md", "Alpha", "not a date", 2),
+                page("c.md", "Alpha", "2024-01-01", 3),
+            ]
+        };
+        let ids = |pages: Vec<Page>| pages.into_iter().map(|p| p.id).collect::<Vec<_>>();
+
+        let by_title = sort_content(pages(), SortSpec { key: SortKey::Title, ascending: true });
+        assert_eq!(ids(by_title), vec!["a.md", "c.md", "b.md"]);
+
+        // The unparseable date falls back to mtime (the epoch here), so it sorts last
+        let by_date = sort_content(pages(), SortSpec { key: SortKey::Date, ascending: false });
+        assert_eq!(ids(by_date), vec!["b.md", "c.md", "a.md"]);
+
+        let mut weighted = pages();
+        weighted[0].frontmatter.weight = Some(2);
+        weighted[2].frontmatter.weight = Some(1);
+        let by_weight = sort_content(weighted, SortSpec { key: SortKey::Weight, ascending: true });
+        assert_eq!(ids(by_weight), vec!["c.md", "b.md", "a.md"]);
+    }
+
+    #[test]
+    fn post_filter_matches_terms_and_draft_state() {
+        let (doc, _) = crate::markdown::MarkdownDocument::parse(
+            "---\ntitle: T\ndate: 2024-01-01\ntags: [Rust, Tauri]\ncategories: [Dev]\ndraft: true\n---\n",
+        )
+        .unwrap();
+        let filter = |tags: &[&str], category: Option<&str>, draft_state| PostFilter {
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            category: category.map(str::to_string),
+            draft_state,
+        };
+
+        let draft = ContentState::Draft;
+
+        assert!(!PostFilter::default().matches(draft, &doc.frontmatter));
+        assert!(filter(&["rust", "tauri"], Some("dev"), DraftState::Drafts).matches(draft, &doc.frontmatter));
+        assert!(!filter(&["rust", "hugo"], None, DraftState::All).matches(draft, &doc.frontmatter));
+        assert!(!filter(&[], Some("ops"), DraftState::All).matches(draft, &doc.frontmatter));
+    }
+
+    #[test]
+    fn sanitize_filename_avoids_windows_reserved_names() {
+        for title in ["CON", "con", "Prn", "AUX", "nul", "Com1", "COM9", "lpt1", "LpT9"] {
+            assert_eq!(slugify(title, SlugMode::Ascii), format!("{}-post", title.to_lowercase()));
+        }
+        for title in ["com0", "com10", "console", "lpt", "null"] {
+            assert_eq!(slugify(title, SlugMode::Ascii), title);
+        }
+        assert!(is_windows_reserved_name("Lpt3"));
+    }
+
+    #[test]
+    fn sanitize_filename_transliterates_cyrillic() {
+        let slug = |title| slugify(title, SlugMode::Ascii);
+        assert_eq!(slug("Привет, мир"), "privet-mir");
+        assert_eq!(slug("Їжак і ґанок"), "yizhak-i-ganok");
+        assert_eq!(slug("Європа в Києві"), "yevropa-v-kiyevi");
+        assert_eq!(slug("Пʼять об'єктів"), "pyat-obyektiv");
+        assert_eq!(slug("Щастя і счастье"), "shchastya-i-schaste");
+        assert_eq!(slug("Беларуская мова ўсюды"), "belaruskaya-mova-usyudy");
+        assert_eq!(transliterate_cyrillic("ЁЖИК Ґ"), "YoZhIK G");
+    }
+
+    #[test]
+    fn unicode_slug_mode_keeps_other_scripts() {
+        assert_eq!(slugify("你好 世界", SlugMode::Unicode), "你好-世界");
+        assert_eq!(slugify("Καλημέρα: κόσμε!", SlugMode::Unicode), "καλημέρα-κόσμε");
+        assert_eq!(slugify("a/b\\c?<d>", SlugMode::Unicode), "abcd");
+        assert_eq!(slugify("你好 世界", SlugMode::Ascii), "");
+    }
+
+    #[test]
+    fn project_slug_mode_overrides_app_config() {
+        let dir = tempfile::tempdir().unwrap();
+        let settings = crate::project_settings::ProjectSettings {
+            slug_mode: Some(SlugMode::Unicode),
+            ..Default::default()
+        };
+        settings.save(dir.path()).unwrap();
+
+        assert_eq!(sanitize_filename(dir.path(), "你好 世界"), "你好-世界");
+    }
+
+    #[test]
+    fn create_and_delete_single_file_and_bundle_pages() {
+        let dir = tempfile::tempdir().unwrap();
+        let project_path = dir.path().to_string_lossy().to_string();
+        let content_dir = dir.path().join("content");
+
+        let single = create_page(project_path.clone(), "About".to_string(), Some(false)).unwrap();
+        assert_eq!(single.file_path, content_dir.join("about.md").to_string_lossy());
+
+        // The bundle can't reuse the name the single page already has
+        let bundle = create_page(project_path.clone(), "About".to_string(), None).unwrap();
+        let bundle_dir = Path::new(&bundle.file_path).parent().unwrap();
+        assert!(bundle.file_path.ends_with("index.md"));
+        assert!(bundle_dir.file_name().unwrap().to_string_lossy().starts_with("about_"));
+
+        delete_page(project_path.clone(), single.id).unwrap();
+        delete_page(project_path, bundle.id).unwrap();
+        assert!(!content_dir.join("about.md").exists());
+        let remaining: Vec<_> = fs::read_dir(&content_dir).unwrap().collect();
+        assert!(remaining.is_empty());
+    }
+
+    #[test]
+    fn sections_skip_leaf_bundles_and_save_new_index() {
+        let dir = tempfile::tempdir().unwrap();
+        let project_path = dir.path().to_string_lossy().to_string();
+        let content_dir = dir.path().join("content");
+        fs::create_dir_all(content_dir.join("blog/my-post")).unwrap();
+        fs::write(content_dir.join("blog/my-post/index.md"), "---\ntitle: Post\ndate: 2024-01-01\n---\n").unwrap();
+        fs::write(content_dir.join("_index.md"), "---\ntitle: Welcome\ndate: 2024-01-01\n---\nHi").unwrap();
+
+        let sections = list_sections(project_path.clone()).unwrap();
+        let paths: Vec<&str> = sections.iter().map(|s| s.path.as_str()).collect();
+        assert_eq!(paths, vec!["", "blog"]);
+        assert_eq!(sections[0].title.as_deref(), Some("Welcome"));
+        assert!(!sections[1].has_index);
+
+        let mut section = get_section(project_path.clone(), "blog".to_string()).unwrap();
+        assert_eq!(section.id, "content/blog/_index.md");
+        assert_eq!(section.title, "Blog");
+        section.content = "All posts".to_string();
+        let modified_at = save_section(project_path.clone(), section, None).unwrap();
+
+        let mut saved = get_section(project_path.clone(), "blog/".to_string()).unwrap();
+        assert_eq!(saved.content, "All posts");
+        assert_eq!(saved.modified_at, modified_at);
+
+        fs::write(content_dir.join("blog/_index.md"), "---\ntitle: Edited\ndate: 2024-01-01\n---\n").unwrap();
+        saved.content = "Stale".to_string();
+        let err = save_section(project_path.clone(), saved.clone(), Some(modified_at - 1)).unwrap_err();
+        assert!(err.contains("file_changed"));
+        saved.id = "content/blog/my-post/index.md".to_string();
+        assert!(save_section(project_path.clone(), saved, None).is_err());
+        assert!(get_section(project_path, "../outside".to_string()).is_err());
+    }
+
+    #[test]
+    fn menus_normalize_shapes() {
+        let config = HugoConfig::from_value(serde_json::json!({
+            "menu": {
+                "main": [
+                    { "name": "Blog", "url": "/posts/", "weight": 20 },
+                    { "name": "Home", "pageRef": "/", "weight": 10 },
+                    { "name": "Rust", "url": "/tags/rust/", "parent": "Blog" }
+                ],
+                "footer": { "identifier": "about", "url": "/about/" }
+            }
+        }));
+
+        let menus = config.menus();
+        let names: Vec<(&str, &str)> = menus.iter().map(|m| (m.menu.as_str(), m.name.as_str())).collect();
+        assert_eq!(names, vec![("footer", "about"), ("main", "Rust"), ("main", "Home"), ("main", "Blog")]);
+        assert_eq!(menus[2].url.as_deref(), Some("/"));
+        assert_eq!(menus[1].parent.as_deref(), Some("Blog"));
+        assert!(config.params().as_object().unwrap().is_empty());
+    }
+
+    #[test]
+    fn language_from_suffix_and_tree() {
+        let langs = languages(&["en", "ru"]);
+
+        assert_eq!(langs.language_of(Path::new("/site/content/posts/hello.ru.md")), "ru");
+        assert_eq!(langs.language_of(Path::new("/site/content/ru/posts/hello.md")), "ru");
+        assert_eq!(langs.language_of(Path::new("/site/content/posts/hello.md")), "en");
+        // Not a configured language, so just part of the name
+        assert_eq!(langs.language_of(Path::new("/site/content/posts/v1.de.md")), "en");
+    }
+
+    #[test]
+    fn translation_key_matches_across_layouts() {
+        let langs = languages(&["en", "ru"]);
+        let key = |path: &str| langs.translation_key(Path::new(path));
+
+        assert_eq!(key("/site/content/posts/hello/index.md"), key("/site/content/posts/hello/index.ru.md"));
+        assert_eq!(key("/site/content/en/posts/hello.md"), key("/site/content/ru/posts/hello.md"));
+        assert_ne!(key("/site/content/posts/hello.md"), key("/site/content/posts/world.md"));
+
+        // Without a languages table, code-shaped suffixes still count
+        let langs = languages(&[]);
+        assert_eq!(
+            langs.translation_key(Path::new("/site/content/posts/hello.pt-br.md")),
+            Some("posts/hello".to_string())
+        );
+    }
+
+    #[test]
+    fn resolve_project_path_accepts_absolute_ids_inside_project() {
+        let dir = tempfile::tempdir().unwrap();
+        let project = dir.path().join("site");
+        fs::create_dir_all(project.join("content/posts")).unwrap();
+        fs::write(project.join("content/posts/a.md"), "x").unwrap();
+        fs::write(dir.path().join("secret.md"), "x").unwrap();
+
+        let expected = project.join("content/posts/a.md");
+        assert_eq!(resolve_project_path(&project, "content/posts/a.md").unwrap(), expected);
+        let absolute = expected.canonicalize().unwrap();
+        assert_eq!(resolve_project_path(&project, absolute.to_str().unwrap()).unwrap(), expected);
+        assert_eq!(
+            resolve_project_path(&project, "content/posts/new.md").unwrap(),
+            project.join("content/posts/new.md")
+        );
+
+        assert!(resolve_project_path(&project, "../secret.md").is_err());
+        let outside = dir.path().join("secret.md");
+        assert!(resolve_project_path(&project, outside.to_str().unwrap()).is_err());
+    }
+
+    #[test]
+    fn content_commands_reject_paths_outside_project() {
+        let dir = tempfile::tempdir().unwrap();
+        let project = dir.path().join("site");
+        fs::create_dir_all(project.join("content")).unwrap();
+        fs::write(dir.path().join("outside.md"), "---\ntitle: x\ndate: 2024-01-01\n---\n").unwrap();
+        let project_path = project.to_string_lossy().to_string();
+
+        assert!(get_page(project_path.clone(), "../outside.md".to_string()).is_err());
+        assert!(get_draft(project_path.clone(), "../outside.md".to_string()).is_err());
+
+        let mut page = Page::from_file(&dir.path().join("outside.md"), dir.path()).unwrap();
+        page.file_path = dir.path().join("written.md").to_string_lossy().to_string();
+        assert!(save_page(project_path, page, None).is_err());
+        assert!(!dir.path().join("written.md").exists());
+    }
+
+    #[test]
+    fn publish_draft_moves_into_posts_and_back() {
+        let dir = tempfile::tempdir().unwrap();
+        let project = dir.path();
+        fs::create_dir_all(project.join("content/drafts")).unwrap();
+        fs::create_dir_all(project.join("content/posts")).unwrap();
+        fs::write(project.join("content/posts/my-draft.md"), "---\ntitle: Taken\ndate: 2024-01-01\n---\n").unwrap();
+        fs::write(
+            project.join("content/drafts/wip.md"),
+            "---\ntitle: My Draft\ndate: 2024-01-01\ndraft: true\n---\n\nBody\n",
+        )
+        .unwrap();
+        let project_path = project.to_string_lossy().to_string();
+
+        let post = publish_draft(project_path.clone(), "content/drafts/wip.md".to_string(), None).unwrap();
+        assert!(post.id.starts_with("content/posts/my-draft_"));
+        // The draft key was spelled out, so it stays as `draft: false`
+        assert_eq!(post.frontmatter.draft, Some(false));
+        assert_eq!(post.frontmatter.date, "2024-01-01");
+        assert_eq!(post.content.trim(), "Body");
+        assert!(!project.join("content/drafts/wip.md").exists());
+
+        let draft = unpublish_post(project_path, post.id).unwrap();
+        assert_eq!(draft.id, "content/drafts/my-draft.md");
+        assert_eq!(draft.frontmatter.draft, Some(true));
+    }
+
+    #[test]
+    fn batch_update_frontmatter_applies_changes_per_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let posts = dir.path().join("content/posts");
+        fs::create_dir_all(&posts).unwrap();
+        fs::write(posts.join("a.md"), "---\ntitle: A\ndate: 2024-01-01\ncomments: true\ncategories: [Dev]\n---\n\nA").unwrap();
+        fs::write(posts.join("b.md"), "---\ntitle: B\ndate: 2024-01-01\nkeywords: rust\n---\n\nB").unwrap();
+        let project_path = dir.path().to_string_lossy().to_string();
+        let changes: Vec<FrontmatterChange> = serde_json::from_str(
+            r#"[{"op": "set", "key": "comments", "value": false},
+                {"op": "append", "key": "categories", "value": "News"},
+                {"op": "append", "key": "keywords", "value": "tauri"},
+                {"op": "remove", "key": "cover"}]"#,
+        )
+        .unwrap();
+        let ids = vec!["content/posts/a.md".to_string(), "content/posts/b.md".to_string()];
+
+        let missing = vec!["content/posts/a.md".to_string(), "content/posts/nope.md".to_string()];
+        assert!(batch_update_frontmatter(project_path.clone(), missing, changes.clone()).is_err());
+        assert!(fs::read_to_string(posts.join("a.md")).unwrap().contains("comments: true"));
+
+        let results = batch_update_frontmatter(project_path, ids, changes).unwrap();
+        assert!(results[0].success);
+        assert_eq!(
+            fs::read_to_string(posts.join("a.md")).unwrap(),
+            "---\ntitle: A\ndate: 2024-01-01\ncomments: false\ncategories:\n- Dev\n- News\nkeywords:\n- tauri\n---\n\nA\n"
+        );
+        // b.md's keywords is a plain string, so it can't be appended to
+        assert!(!results[1].success);
+        assert!(results[1].error.as_deref().unwrap().contains("not a list"));
+    }
+
+    #[test]
+    fn toggle_draft_round_trip_leaves_explicit_false() {
+        let dir = tempfile::tempdir().unwrap();
+        let posts = dir.path().join("content/posts");
+        fs::create_dir_all(&posts).unwrap();
+        fs::write(posts.join("explicit.md"), "---\ntitle: A\ndate: 2024-01-01\ndraft: false\n---\n\nA").unwrap();
+        fs::write(posts.join("implicit.md"), "---\ntitle: B\ndate: 2024-01-01\n---\n\nB").unwrap();
+        let project_path = dir.path().to_string_lossy().to_string();
+
+        for _ in 0..2 {
+            toggle_draft(project_path.clone(), "content/posts/explicit.md".to_string()).unwrap();
+        }
+        assert!(fs::read_to_string(posts.join("explicit.md")).unwrap().contains("draft: false"));
+
+        let mut post = Post::from_file(&posts.join("implicit.md"), dir.path()).unwrap();
+        post.frontmatter.mark_published(&post.layout);
+        assert_eq!(post.frontmatter.draft, None);
+        let post = toggle_draft(project_path.clone(), "content/posts/implicit.md".to_string()).unwrap();
+        assert_eq!(post.frontmatter.draft, Some(true));
+
+        // Once toggled on, the key is in the file, so toggling off keeps it
+        let post = toggle_draft(project_path, "content/posts/implicit.md".to_string()).unwrap();
+        assert_eq!(post.frontmatter.draft, Some(false));
+        assert!(fs::read_to_string(posts.join("implicit.md")).unwrap().contains("draft: false"));
+    }
+
+    #[test]
+    fn import_markdown_files_normalizes_and_dedupes() {
+        let dir = tempfile::tempdir().unwrap();
+        let project = dir.path().join("site");
+        let source = dir.path().join("export");
+        fs::create_dir_all(project.join("content/posts")).unwrap();
+        fs::create_dir_all(source.join("Old Post")).unwrap();
+        fs::write(project.join("content/posts/hello.md"), "---\ntitle: Existing\ndate: 2024-01-01\n---\n").unwrap();
+        fs::write(source.join("Hello.md"), "---\ntitle: Hello\nauthor: Rail\n---\n\nBody").unwrap();
+        fs::write(source.join("Old Post/index.md"), "# Old Post\n\nText").unwrap();
+        fs::write(source.join("cover.png"), b"png").unwrap();
+
+        let mut results = import_markdown_files(
+            project.to_string_lossy().to_string(),
+            source.to_string_lossy().to_string(),
+            "posts".to_string(),
+        )
+        .unwrap();
+        results.sort_by(|a, b| a.source.cmp(&b.source));
+
+        assert_eq!(results.len(), 2);
+        assert!(results.iter().all(|r| r.success));
+        let hello_id = results[0].id.clone().unwrap();
+        assert!(hello_id.starts_with("content/posts/hello_"));
+        assert_eq!(results[1].id.as_deref(), Some("content/posts/old-post.md"));
+
+        let hello = Post::from_file(&project.join(&hello_id), &project).unwrap();
+        assert_eq!(hello.frontmatter.title, "Hello");
+        assert!(!hello.frontmatter.date.is_empty());
+        assert_eq!(hello.content, "Body");
+        let old = fs::read_to_string(project.join("content/posts/old-post.md")).unwrap();
+        assert!(old.starts_with("---\ntitle: Old Post\ndate: "));
+    }
+
+    #[test]
+    fn export_content_index_writes_posts_and_pages() {
+        let dir = tempfile::tempdir().unwrap();
+        let project = dir.path();
+        fs::create_dir_all(project.join("content/posts")).unwrap();
+        fs::write(
+            project.join("content/posts/a.md"),
+            "---\ntitle: A\ndate: 2024-01-01\ntags: [rust]\n---\n\nOne two three",
+        )
+        .unwrap();
+        fs::write(project.join("content/about.md"), "---\ntitle: About\ndate: 2024-01-01\n---\n\nHi").unwrap();
+        let project_path = project.to_string_lossy().to_string();
+
+        assert!(export_content_index(project_path.clone(), "../index.json".to_string()).is_err());
+        let count = export_content_index(project_path, "static/search/index.json".to_string()).unwrap();
+        assert_eq!(count, 2);
+
+        let written: Vec<serde_json::Value> =
+            serde_json::from_str(&fs::read_to_string(project.join("static/search/index.json")).unwrap()).unwrap();
+        assert_eq!(written[0]["id"], "content/posts/a.md");
+        assert_eq!(written[0]["wordCount"], 3);
+        assert_eq!(written[0]["tags"][0], "rust");
+        assert_eq!(written[1]["kind"], "page");
+    }
+
+    #[test]
+    fn create_draft_from_post_keeps_original() {
+        let dir = tempfile::tempdir().unwrap();
+        let project = dir.path();
+        fs::create_dir_all(project.join("content/posts/trip")).unwrap();
+        fs::create_dir_all(project.join("content/drafts")).unwrap();
+        fs::write(project.join("content/posts/trip/index.md"), "---\ntitle: Trip\ndate: 2024-01-01\n---\n\n![](map.png)").unwrap();
+        fs::write(project.join("content/posts/trip/map.png"), b"png").unwrap();
+        fs::write(project.join("content/posts/note.md"), "---\ntitle: Note\ndate: 2024-01-01\n---\n\nText").unwrap();
+        fs::write(project.join("content/drafts/note.md"), "---\ntitle: Old\ndate: 2024-01-01\n---\n").unwrap();
+        let project_path = project.to_string_lossy().to_string();
+
+        let draft = create_draft_from_post(project_path.clone(), "content/posts/trip/index.md".to_string()).unwrap();
+        assert_eq!(draft.id, "content/drafts/trip/index.md");
+        assert_eq!(draft.frontmatter.draft, Some(true));
+        assert!(project.join("content/drafts/trip/map.png").is_file());
+        assert!(project.join("content/posts/trip/index.md").is_file());
+
+        let draft = create_draft_from_post(project_path, "content/posts/note.md".to_string()).unwrap();
+        assert!(draft.id.starts_with("content/drafts/note_"));
+        assert_eq!(draft.content, "Text");
+    }
+
+    #[test]
+    fn diff_lines_groups_changes() {
+        let changes = diff_lines("one\ntwo\nthree\nfour\n", "one\n2\nthree\nfour\nfive\n");
+        assert_eq!(changes.len(), 2);
+        assert_eq!(changes[0].kind, DiffKind::Changed);
+        assert_eq!((changes[0].old_start, changes[0].new_start), (2, 2));
+        assert_eq!(changes[0].old_lines, vec!["two".to_string()]);
+        assert_eq!(changes[0].new_lines, vec!["2".to_string()]);
+        assert_eq!(changes[1].kind, DiffKind::Added);
+        assert_eq!(changes[1].new_start, 5);
+        assert!(changes[1].old_lines.is_empty());
+
+        assert!(diff_lines("same\n", "same\n").is_empty());
+    }
+
+    #[test]
+    fn save_rejects_stale_modified_at() {
+        let dir = tempfile::tempdir().unwrap();
+        let project = dir.path();
+        fs::create_dir_all(project.join("content/posts")).unwrap();
+        fs::write(project.join("content/posts/a.md"), "---\ntitle: A\ndate: 2024-01-01\n---\n\nOriginal").unwrap();
+        let project_path = project.to_string_lossy().to_string();
+
+        let mut post = get_post(project_path.clone(), "content/posts/a.md".to_string()).unwrap();
+        post.content = "Edited".to_string();
+
+        let error = save_post(project_path.clone(), post.clone(), Some(post.modified_at - 10)).unwrap_err();
+        assert!(error.starts_with("[file_changed]"));
+        assert!(fs::read_to_string(project.join("content/posts/a.md")).unwrap().contains("Original"));
+
+        let modified_at = save_post(project_path.clone(), post.clone(), Some(post.modified_at)).unwrap();
+        assert!(save_post(project_path, post, Some(modified_at)).is_ok());
+    }
+
+    #[test]
+    fn save_converts_legacy_encoding_only_when_confirmed() {
+        let dir = tempfile::tempdir().unwrap();
+        let project = dir.path();
+        let path = project.join("content/posts/a.md");
+        fs::create_dir_all(project.join("content/posts")).unwrap();
+        let (bytes, _, _) = encoding_rs::WINDOWS_1251.encode("---\ntitle: Привет\ndate: 2024-01-01\n---\n\nТекст");
+        fs::write(&path, &bytes).unwrap();
+        let project_path = project.to_string_lossy().to_string();
+
+        let mut post = get_post(project_path.clone(), "content/posts/a.md".to_string()).unwrap();
+        assert_eq!(post.encoding.as_deref(), Some("windows-1251"));
+
+        let error = save_post(project_path.clone(), post.clone(), None).unwrap_err();
+        assert!(error.starts_with("[not_utf8]"));
+        assert!(write_content_file(project, &path, "x").unwrap_err().starts_with("[not_utf8]"));
+        assert_eq!(fs::read(&path).unwrap(), bytes.as_ref());
+
+        post.encoding = None;
+        save_post(project_path, post, None).unwrap();
+        assert!(fs::read_to_string(&path).unwrap().contains("Текст"));
+    }
+
+    #[test]
+    fn list_posts_metadata_skips_bodies() {
+        let dir = tempfile::tempdir().unwrap();
+        let project = dir.path();
+        fs::create_dir_all(project.join("content/posts")).unwrap();
+        fs::write(
+            project.join("content/posts/a.md"),
+            "<!-- note -->\n+++\ntitle = \"A\"\ndate = \"2024-01-01\"\n+++\n\nBody",
+        )
+        .unwrap();
+
+        let posts = list_posts_metadata(project.to_string_lossy().to_string()).unwrap();
+        assert_eq!(posts.len(), 1);
+        assert_eq!(posts[0].title, "A");
+        let json = serde_json::to_value(&posts[0]).unwrap();
+        assert!(json.get("content").is_none());
+    }
+
+    #[test]
+    fn preview_frontmatter_config_does_not_write() {
+        let dir = tempfile::tempdir().unwrap();
+        let project = dir.path();
+        fs::create_dir_all(project.join("content/posts")).unwrap();
+        fs::write(project.join("content/posts/a.md"), "---\ntitle: A\ndate: 2024-01-01\nrating: 5\n---\n").unwrap();
+        let project_path = project.to_string_lossy().to_string();
+
+        let config = preview_frontmatter_config(project_path.clone(), false, None).unwrap();
+        assert_eq!(config.custom_fields[0].name, "rating");
+        assert!(!frontmatter_config_path(project).exists());
+
+        regenerate_frontmatter_config(project_path.clone(), false, None).unwrap();
+        let config = preview_frontmatter_config(project_path, true, None).unwrap();
+        assert_eq!(config.custom_fields.len(), 1);
+    }
+
+    #[test]
+    fn get_image_usage_matches_url_forms() {
+        let dir = tempfile::tempdir().unwrap();
+        let project = dir.path();
+        fs::create_dir_all(project.join("content/posts")).unwrap();
+        fs::write(
+            project.join("content/posts/a.md"),
+            "---\ntitle: A\ndate: 2024-01-01\ncover: /images/x.png\n---\n\n![x](../images/x.png)\n![y](/images/x.png.bak)",
+        )
+        .unwrap();
+        fs::write(project.join("content/about.md"), "---\ntitle: About\ndate: 2024-01-01\n---\n\n<img src=\"images/x.png?v=2\">").unwrap();
+        fs::write(project.join("content/posts/b.md"), "---\ntitle: B\ndate: 2024-01-01\n---\n\n![z](/images/other/x.png)").unwrap();
+
+        let usages = get_image_usage(project.to_string_lossy().to_string(), "/images/x.png".to_string()).unwrap();
+        let found: Vec<(&str, &[usize])> = usages.iter().map(|u| (u.id.as_str(), u.lines.as_slice())).collect();
+        assert_eq!(found, vec![("content/about.md", &[6][..]), ("content/posts/a.md", &[4, 7][..])]);
+    }
+
+    #[test]
+    fn get_image_thumbnail_caches_small_jpeg() {
+        let dir = tempfile::tempdir().unwrap();
+        let project = dir.path();
+        fs::create_dir_all(project.join("static/images")).unwrap();
+        image::RgbaImage::new(400, 200).save(project.join("static/images/wide.png")).unwrap();
+        fs::write(project.join("static/images/logo.svg"), "<svg/>").unwrap();
+        let project_path = project.to_string_lossy().to_string();
+
+        let thumbnail = get_image_thumbnail(project_path.clone(), "static/images/wide.png".to_string(), 100)
+            .unwrap()
+            .unwrap();
+        assert!(thumbnail.ends_with(".jpg"));
+        assert_eq!(image::image_dimensions(&thumbnail).unwrap(), (100, 50));
+        let again = get_image_thumbnail(project_path.clone(), "static/images/wide.png".to_string(), 100).unwrap();
+        assert_eq!(again.as_deref(), Some(thumbnail.as_str()));
+
+        assert!(get_image_thumbnail(project_path.clone(), "static/images/wide.png".to_string(), 500).unwrap().is_none());
+        assert!(get_image_thumbnail(project_path, "static/images/logo.svg".to_string(), 100).unwrap().is_none());
+    }
+
+    #[test]
+    fn copy_images_to_project_keeps_same_named_files_apart() {
+        let dir = tempfile::tempdir().unwrap();
+        let project = dir.path().join("site");
+        fs::create_dir_all(project.join("static/images")).unwrap();
+        fs::write(project.join("static/images/photo.jpg"), b"existing").unwrap();
+        for folder in ["a", "b"] {
+            fs::create_dir_all(dir.path().join(folder)).unwrap();
+            fs::write(dir.path().join(folder).join("photo.jpg"), folder.as_bytes()).unwrap();
+        }
+        let sources = vec![
+            dir.path().join("a/photo.jpg").to_string_lossy().to_string(),
+            dir.path().join("b/photo.jpg").to_string_lossy().to_string(),
+            dir.path().join("missing.jpg").to_string_lossy().to_string(),
+        ];
+
+        let results = copy_images_to_project(
+            project.to_string_lossy().to_string(),
+            sources,
+            Some("images".to_string()),
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+
+        assert!(results[0].success && results[1].success && !results[2].success);
+        let urls: Vec<&str> = results[..2].iter().map(|r| r.image.as_ref().unwrap().url.as_str()).collect();
+        assert_ne!(urls[0], urls[1]);
+        assert_ne!(urls[0], "/images/photo.jpg");
+        assert_eq!(fs::read(project.join("static/images/photo.jpg")).unwrap(), b"existing");
+        let copied = fs::read(project.join("static").join(urls[1].trim_start_matches('/'))).unwrap();
+        assert_eq!(copied, b"b");
+    }
+
+    #[test]
+    fn verify_and_fix_image_extension() {
+        let dir = tempfile::tempdir().unwrap();
+        let project = dir.path();
+        fs::create_dir_all(project.join("static/images")).unwrap();
+        image::RgbImage::new(4, 4)
+            .save_with_format(project.join("static/images/photo.png"), image::ImageFormat::Jpeg)
+            .unwrap();
+        image::RgbImage::new(4, 4).save(project.join("static/images/ok.jpeg")).unwrap();
+        let project_path = project.to_string_lossy().to_string();
+
+        let report = verify_image(project_path.clone(), "static/images/photo.png".to_string()).unwrap();
+        assert!(!report.matches);
+        assert_eq!(report.extension_format.as_deref(), Some("png"));
+        assert_eq!(report.actual_format.as_deref(), Some("jpeg"));
+        assert!(verify_image(project_path.clone(), "static/images/ok.jpeg".to_string()).unwrap().matches);
+
+        let fixed = fix_image_extension(project_path.clone(), "static/images/photo.png".to_string()).unwrap();
+        assert_eq!(fixed, "static/images/photo.jpg");
+        assert!(verify_image(project_path, fixed).unwrap().matches);
+    }
+
+    #[test]
+    fn get_related_posts_ranks_by_shared_terms() {
+        let dir = tempfile::tempdir().unwrap();
+        let project = dir.path();
+        let posts = project.join("content/posts");
+        fs::create_dir_all(&posts).unwrap();
+        let write = |name: &str, tags: &str, extra: &str| {
+            fs::write(
+                posts.join(name),
+                format!("---\ntitle: {}\ndate: 2024-01-01\ntags: [{}]\ncategories: [Dev]\n{}---\n", name, tags, extra),
+            )
+            .unwrap();
+        };
+        write("source.md", "rust, tauri", "");
+        write("both.md", "Rust, tauri", "");
+        write("one.md", "go", "");
+        write("draft.md", "rust, tauri", "draft: true\n");
+        fs::write(posts.join("none.md"), "---\ntitle: None\ndate: 2024-01-01\n---\n").unwrap();
+
+        let related = get_related_posts(
+            project.to_string_lossy().to_string(),
+            "content/posts/source.md".to_string(),
+            None,
+        )
+        .unwrap();
+        let ids: Vec<(&str, usize)> = related.iter().map(|post| (post.id.as_str(), post.score)).collect();
+        assert_eq!(ids, vec![("content/posts/both.md", 3), ("content/posts/one.md", 1)]);
+    }
+
+    #[test]
+    fn draft_path_and_flag_classify_consistently() {
+        let dir = tempfile::tempdir().unwrap();
+        let project = dir.path();
+        let write = |path: &str, extra: &str| {
+            let path = project.join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, format!("---\ntitle: T\ndate: 2024-01-01\n{}---\n", extra)).unwrap();
+        };
+        write("content/posts/published.md", "");
+        write("content/posts/flagged.md", "draft: true\n");
+        write("content/drafts/unflagged.md", "draft: false\n");
+        write("content/drafts/_index.md", "");
+        write("content/about.md", "draft: true\n");
+        write("content/contact.md", "");
+
+        let path = project.to_string_lossy().to_string();
+        let post_ids = |draft_state| -> Vec<String> {
+            let filter = PostFilter { draft_state, ..PostFilter::default() };
+            let list = list_posts(path.clone(), None, None, Some(true), None, Some(filter)).unwrap();
+            let mut ids: Vec<String> = list.posts.into_iter().map(|post| post.id).collect();
+            ids.sort();
+            ids
+        };
+        assert_eq!(post_ids(DraftState::Published), vec!["content/posts/published.md"]);
+        assert_eq!(post_ids(DraftState::Drafts), vec!["content/posts/flagged.md"]);
+
+        let mut draft_ids: Vec<String> = list_drafts(path.clone()).unwrap().into_iter().map(|d| d.id).collect();
+        draft_ids.sort();
+        assert_eq!(
+            draft_ids,
+            vec![
+                "content/about.md",
+                "content/drafts/_index.md",
+                "content/drafts/unflagged.md",
+                "content/posts/flagged.md",
+            ]
+        );
+
+        let page_ids: Vec<String> = list_pages(path.clone(), None).unwrap().into_iter().map(|p| p.id).collect();
+        assert_eq!(page_ids, vec!["content/contact.md"]);
+
+        // Without a posts folder the whole content dir holds posts, drafts folder included
+        fs::rename(project.join("content/posts"), project.join("content/notes")).unwrap();
+        assert_eq!(
+            post_ids(DraftState::Drafts),
+            vec!["content/about.md", "content/drafts/unflagged.md", "content/notes/flagged.md"]
+        );
+    }
+
+    #[test]
+    fn delete_post_rejects_paths_outside_project() {
+        let dir = tempfile::tempdir().unwrap();
+        let project = dir.path().join("site");
+        fs::create_dir_all(project.join("content/posts")).unwrap();
+        let outside = dir.path().join("outside.md");
+        fs::write(&outside, "---\ntitle: x\ndate: 2024-01-01\n---\n").unwrap();
+
+        let project_path = project.to_string_lossy().to_string();
+        assert!(delete_post(project_path.clone(), "../outside.md".to_string()).is_err());
+        assert!(delete_post(project_path, outside.to_string_lossy().to_string()).is_err());
+        assert!(outside.exists());
+    }
+
+    #[test]
+    fn check_links_reports_file_lines() {
+        let dir = tempfile::tempdir().unwrap();
+        let project = dir.path();
+        fs::create_dir_all(project.join("content/posts")).unwrap();
+        fs::write(
+            project.join("content/posts/a.md"),
+            "---\ntitle: A\ndate: 2024-01-01\n---\n\nIntro\n\nSee [gone](/posts/missing/).\n",
+        )
+        .unwrap();
+
+        let broken = check_links(project.to_string_lossy().to_string(), None).unwrap();
+        assert_eq!(broken.len(), 1);
+        assert_eq!(broken[0].target, "/posts/missing/");
+        assert_eq!(broken[0].line, 8);
+    }
+
+    #[test]
+    fn create_post_with_frontmatter_names_unsluggable_titles() {
+        let dir = tempfile::tempdir().unwrap();
+        let project = dir.path();
+        fs::create_dir_all(project.join("content/posts")).unwrap();
+        let (doc, _) = crate::markdown::MarkdownDocument::parse("---\ntitle: \"!!!\"\ndate: 2024-01-01\n---\n").unwrap();
+
+        let post = create_post_with_frontmatter(project.to_string_lossy().to_string(), doc.frontmatter, String::new()).unwrap();
+        assert!(post.file_path.ends_with("post.md"));
+    }
+
+    #[test]
+    fn unpublish_post_stays_put_when_saving_fails() {
+        let dir = tempfile::tempdir().unwrap();
+        let project = dir.path();
+        let path = project.join("content/posts/a.md");
+        fs::create_dir_all(project.join("content/posts")).unwrap();
+        // Saving refuses to convert a windows-1251 file, so the save fails
+        let (bytes, _, _) = encoding_rs::WINDOWS_1251.encode("---\ntitle: Привет\ndate: 2024-01-01\n---\n\nТекст");
+        fs::write(&path, &bytes).unwrap();
+
+        let result = unpublish_post(project.to_string_lossy().to_string(), "content/posts/a.md".to_string());
+        assert!(result.is_err());
+        assert_eq!(fs::read(&path).unwrap(), bytes.as_ref());
+        assert_eq!(fs::read_dir(project.join("content/drafts")).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn content_stats_counts_root_posts_once() {
+        let dir = tempfile::tempdir().unwrap();
+        let content = dir.path().join("content");
+        fs::create_dir_all(content.join("about")).unwrap();
+        fs::write(content.join("hello.md"), "---\ntitle: Hello\ndate: 2024-01-01\ntags: [a]\n---\n\nHi").unwrap();
+        fs::write(content.join("about/index.md"), "---\ntitle: About\ndate: 2024-01-01\ntags: [a, b]\n---\n").unwrap();
+
+        let stats = get_content_stats(dir.path().to_string_lossy().to_string()).unwrap();
+        assert_eq!(stats.post_count + stats.page_count, 2);
+        assert_eq!(stats.tag_count, 2);
+        assert_eq!(stats.category_count, 0);
+    }
+
+    #[test]
+    fn rename_tag_leaves_files_alone_when_one_is_broken() {
+        let dir = tempfile::tempdir().unwrap();
+        let posts = dir.path().join("content/posts");
+        fs::create_dir_all(&posts).unwrap();
+        let good = "---\ntitle: A\ndate: 2024-01-01\ntags: [rust]\n---\n\nA";
+        fs::write(posts.join("a.md"), good).unwrap();
+        // Latin-1, which the rename would have to convert
+        fs::write(posts.join("b.md"), b"---\ntitle: Caf\xe9\ndate: 2024-01-01\ntags: [rust]\n---\n\nB").unwrap();
+        fs::write(posts.join("c.md"), good).unwrap();
+
+        let result = rename_tag(
+            dir.path().to_string_lossy().to_string(),
+            "rust".to_string(),
+            "Rust".to_string(),
+        );
+
+        assert!(result.unwrap_err().contains("b.md"));
+        assert_eq!(fs::read_to_string(posts.join("a.md")).unwrap(), good);
+        assert_eq!(fs::read_to_string(posts.join("c.md")).unwrap(), good);
+    }
+}
@@ -2,20 +2,57 @@
 
 use serde::{Deserialize, Serialize};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
 
+use crate::hugo::HugoProject;
+
+/// Number of recent projects kept, not counting pinned ones
+const MAX_RECENT_PROJECTS: usize = 10;
+
+/// Missing keys take their value from `AppConfig::default()`, so config
+/// files written by older or newer versions of the app still load
 #[derive(Debug, Serialize, Deserialize, Clone)]
-#[serde(rename_all = "camelCase")]
+#[serde(rename_all = "camelCase", default)]
 pub struct AppConfig {
+    /// App version that last wrote the file; see `migrate`
     pub version: String,
     pub last_project_path: Option<String>,
     pub recent_projects: Vec<String>,
+    pub pinned_projects: Vec<String>,
     pub ui_language: String,
     pub theme: String,
     pub auto_save_enabled: bool,
     pub auto_save_interval: u32,
     pub editor_font_size: u32,
     pub editor_line_height: f32,
+    pub use_trash: bool,
+    pub hugo_binary_path: Option<String>,
+    pub max_backups_per_file: usize,
+    pub slug_mode: SlugMode,
+    /// End saved markdown files with exactly one line break
+    pub ensure_final_newline: bool,
+    /// Reading speed behind `reading_time_minutes`
+    pub words_per_minute: u32,
+}
+
+/// How titles become file names
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum SlugMode {
+    /// Transliterate Cyrillic and keep only `[a-z0-9-]`
+    #[default]
+    Ascii,
+    /// Keep letters and digits from any script
+    Unicode,
+}
+
+fn default_use_trash() -> bool {
+    true
+}
+
+fn default_max_backups() -> usize {
+    10
 }
 
 impl Default for AppConfig {
@@ -24,18 +61,41 @@ impl Default for AppConfig {
             version: env!("CARGO_PKG_VERSION").to_string(),
             last_project_path: None,
             recent_projects: Vec::new(),
+            pinned_projects: Vec::new(),
             ui_language: "en".to_string(),
             theme: "auto".to_string(),
             auto_save_enabled: true,
             auto_save_interval: 30,
             editor_font_size: 16,
             editor_line_height: 1.5,
+            use_trash: default_use_trash(),
+            hugo_binary_path: None,
+            max_backups_per_file: default_max_backups(),
+            slug_mode: SlugMode::default(),
+            ensure_final_newline: true,
+            words_per_minute: crate::markdown::DEFAULT_WORDS_PER_MINUTE,
         }
     }
 }
 
+lazy_static::lazy_static! {
+    /// `words_per_minute` read on first use; `AppConfig::save` keeps it
+    /// current, so parsing many posts doesn't read the config file each time
+    static ref WORDS_PER_MINUTE: Mutex<Option<u32>> = Mutex::new(None);
+}
+
+/// Configured reading speed for `reading_time_minutes`
+pub fn words_per_minute() -> u32 {
+    *WORDS_PER_MINUTE.lock().unwrap().get_or_insert_with(|| {
+        AppConfig::load()
+            .map(|config| config.words_per_minute)
+            .unwrap_or(crate::markdown::DEFAULT_WORDS_PER_MINUTE)
+    })
+}
+
 impl AppConfig {
-    fn get_config_path() -> Result<PathBuf, String> {
+    /// Location of `config.json`, creating its folder if needed
+    pub fn get_config_path() -> Result<PathBuf, String> {
         let config_dir = dirs::config_dir()
             .ok_or("Failed to get config directory")?;
         let app_config_dir = config_dir.join("hugo-bros");
@@ -50,27 +110,67 @@ impl AppConfig {
     }
 
     pub fn load() -> Result<Self, String> {
-        let config_path = Self::get_config_path()?;
+        Self::load_from(&Self::get_config_path()?)
+    }
 
+    /// Read the config, falling back to the defaults when the file is
+    /// missing or can't be parsed. A broken file is kept next to it as
+    /// `config.json.corrupt` so it can be inspected, and the app still starts.
+    fn load_from(config_path: &Path) -> Result<Self, String> {
         if !config_path.exists() {
             // Return default config if file doesn't exist
             return Ok(Self::default());
         }
 
-        let config_str = fs::read_to_string(&config_path)
+        let config_str = fs::read_to_string(config_path)
             .map_err(|e| format!("Failed to read config file: {}", e))?;
 
-        serde_json::from_str(&config_str)
-            .map_err(|e| format!("Failed to parse config file: {}", e))
+        match serde_json::from_str::<Self>(&config_str) {
+            Ok(mut config) => {
+                if config.migrate() {
+                    if let Err(e) = config.save_to(config_path) {
+                        eprintln!("Failed to save migrated config file: {}", e);
+                    }
+                }
+                Ok(config)
+            }
+            Err(e) => {
+                eprintln!("Failed to parse config file, using defaults: {}", e);
+                let mut corrupt_path = config_path.as_os_str().to_owned();
+                corrupt_path.push(".corrupt");
+                if let Err(e) = fs::rename(config_path, &corrupt_path) {
+                    eprintln!("Failed to back up corrupt config file: {}", e);
+                }
+                Ok(Self::default())
+            }
+        }
+    }
+
+    /// Bring a config written by an older app version up to date. Missing
+    /// fields were already filled by serde; this stamps the current version.
+    /// Files from a newer version are left alone, so downgrading doesn't
+    /// overwrite them. Returns whether anything changed.
+    fn migrate(&mut self) -> bool {
+        let current = env!("CARGO_PKG_VERSION");
+        if version_parts(&self.version) >= version_parts(current) {
+            return false;
+        }
+        self.version = current.to_string();
+        true
     }
 
     pub fn save(&self) -> Result<(), String> {
-        let config_path = Self::get_config_path()?;
+        self.save_to(&Self::get_config_path()?)?;
+        crate::errors::set_ui_language(&self.ui_language);
+        *WORDS_PER_MINUTE.lock().unwrap() = Some(self.words_per_minute);
+        Ok(())
+    }
 
+    fn save_to(&self, config_path: &Path) -> Result<(), String> {
         let config_str = serde_json::to_string_pretty(self)
             .map_err(|e| format!("Failed to serialize config: {}", e))?;
 
-        fs::write(&config_path, config_str)
+        crate::files::write_file_atomic(config_path, config_str.as_bytes())
             .map_err(|e| format!("Failed to write config file: {}", e))
     }
 
@@ -81,12 +181,148 @@ impl AppConfig {
         // Add to front of list
         self.recent_projects.insert(0, project_path.clone());
 
-        // Keep only last 10 projects
-        if self.recent_projects.len() > 10 {
-            self.recent_projects.truncate(10);
-        }
+        // Keep only last 10 unpinned projects; pinned ones are never evicted
+        let mut unpinned = 0;
+        let pinned = &self.pinned_projects;
+        self.recent_projects.retain(|p| {
+            if pinned.contains(p) {
+                return true;
+            }
+            unpinned += 1;
+            unpinned <= MAX_RECENT_PROJECTS
+        });
 
         // Update last_project_path
         self.last_project_path = Some(project_path);
     }
+
+    /// Pin a project; pins keep the order they were added in
+    pub fn pin_project(&mut self, project_path: String) {
+        if !self.pinned_projects.contains(&project_path) {
+            self.pinned_projects.push(project_path);
+        }
+    }
+
+    pub fn unpin_project(&mut self, project_path: &str) {
+        self.pinned_projects.retain(|p| p != project_path);
+    }
+
+    pub fn remove_recent_project(&mut self, project_path: &str) {
+        self.recent_projects.retain(|p| p != project_path);
+
+        if self.last_project_path.as_deref() == Some(project_path) {
+            self.last_project_path = None;
+        }
+    }
+
+    pub fn clear_recent_projects(&mut self) {
+        self.recent_projects.clear();
+        self.last_project_path = None;
+    }
+
+    /// Drop recent projects that were deleted, moved, or are no longer Hugo sites
+    pub fn prune_recent_projects(&mut self) {
+        self.recent_projects
+            .retain(|p| HugoProject::new(PathBuf::from(p)).validate().is_ok());
+
+        if let Some(last) = &self.last_project_path {
+            if !self.recent_projects.contains(last) {
+                self.last_project_path = None;
+            }
+        }
+    }
+}
+
+/// Numeric parts of a `1.2.3` version; pre-release suffixes are ignored and
+/// a missing or malformed version sorts before every real one
+fn version_parts(version: &str) -> Vec<u64> {
+    version
+        .split('.')
+        .map_while(|part| {
+            let digits: String = part.chars().take_while(char::is_ascii_digit).collect();
+            digits.parse().ok()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn add_recent_project_keeps_pinned() {
+        let mut config = AppConfig::default();
+        config.add_recent_project("/sites/pinned".to_string());
+        config.pin_project("/sites/pinned".to_string());
+
+        for i in 0..15 {
+            config.add_recent_project(format!("/sites/{}", i));
+        }
+
+        assert_eq!(config.recent_projects.len(), MAX_RECENT_PROJECTS + 1);
+        assert_eq!(config.recent_projects[0], "/sites/14");
+        assert!(config.recent_projects.contains(&"/sites/pinned".to_string()));
+        assert!(!config.recent_projects.contains(&"/sites/4".to_string()));
+    }
+
+    #[test]
+    fn old_config_without_pins_loads() {
+        let json = r#"{
+            "version": "0.1.0",
+            "lastProjectPath": null,
+            "recentProjects": ["/sites/blog"],
+            "uiLanguage": "en",
+            "theme": "auto",
+            "autoSaveEnabled": true,
+            "autoSaveInterval": 30,
+            "editorFontSize": 16,
+            "editorLineHeight": 1.5
+        }"#;
+
+        let config: AppConfig = serde_json::from_str(json).unwrap();
+        assert!(config.pinned_projects.is_empty());
+        assert!(config.use_trash);
+    }
+
+    #[test]
+    fn partial_config_loads_and_stamps_version() {
+        let dir = tempfile::tempdir().unwrap();
+        let config_path = dir.path().join("config.json");
+        fs::write(&config_path, r#"{"version": "0.0.1", "theme": "dark"}"#).unwrap();
+
+        let config = AppConfig::load_from(&config_path).unwrap();
+        assert_eq!(config.theme, "dark");
+        assert_eq!(config.editor_font_size, 16);
+        assert_eq!(config.max_backups_per_file, 10);
+        assert_eq!(config.version, env!("CARGO_PKG_VERSION"));
+
+        let saved: serde_json::Value = serde_json::from_str(&fs::read_to_string(&config_path).unwrap()).unwrap();
+        assert_eq!(saved["version"], env!("CARGO_PKG_VERSION"));
+    }
+
+    #[test]
+    fn newer_config_is_not_rewritten() {
+        let dir = tempfile::tempdir().unwrap();
+        let config_path = dir.path().join("config.json");
+        let raw = r#"{"version": "999.0.0", "theme": "dark", "futureSetting": 1}"#;
+        fs::write(&config_path, raw).unwrap();
+
+        let config = AppConfig::load_from(&config_path).unwrap();
+        assert_eq!(config.theme, "dark");
+        assert_eq!(config.version, "999.0.0");
+        assert_eq!(fs::read_to_string(&config_path).unwrap(), raw);
+    }
+
+    #[test]
+    fn corrupt_config_falls_back_to_defaults() {
+        let dir = tempfile::tempdir().unwrap();
+        let config_path = dir.path().join("config.json");
+        fs::write(&config_path, "{\"version\": \"0.1.0\", garbage").unwrap();
+
+        let config = AppConfig::load_from(&config_path).unwrap();
+        assert_eq!(config.theme, "auto");
+        assert!(config.recent_projects.is_empty());
+        assert!(!config_path.exists());
+        assert!(dir.path().join("config.json.corrupt").exists());
+    }
 }
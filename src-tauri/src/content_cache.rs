@@ -0,0 +1,104 @@
+// In-memory cache of parsed posts, keyed by file path
+// Entries are reused while the file's mtime and size are unchanged
+
+use crate::markdown::Post;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+struct CachedPost {
+    modified: SystemTime,
+    len: u64,
+    /// False when only the frontmatter was read
+    full: bool,
+    post: Post,
+}
+
+lazy_static::lazy_static! {
+    static ref POST_CACHE: Mutex<HashMap<PathBuf, CachedPost>> = Mutex::new(HashMap::new());
+}
+
+/// Parse a post, reusing the cached copy when the file hasn't changed.
+/// A full entry also serves metadata-only requests, but not the reverse.
+pub fn load_post(file_path: &Path, project_path: &Path, metadata_only: bool) -> Result<Post, String> {
+    let metadata = fs::metadata(file_path)
+        .map_err(|e| format!("Failed to get file metadata: {}", e))?;
+    let Ok(modified) = metadata.modified() else {
+        return parse(file_path, project_path, metadata_only);
+    };
+
+    if let Some(cached) = POST_CACHE.lock().unwrap().get(file_path) {
+        if cached.modified == modified
+            && cached.len == metadata.len()
+            && (cached.full || metadata_only)
+        {
+            // The reading speed may have changed since the post was cached
+            let mut post = cached.post.clone();
+            post.reading_time_minutes =
+                crate::markdown::reading_time_minutes(post.word_count, crate::config::words_per_minute());
+            return Ok(post);
+        }
+    }
+
+    let post = parse(file_path, project_path, metadata_only)?;
+    POST_CACHE.lock().unwrap().insert(
+        file_path.to_path_buf(),
+        CachedPost {
+            modified,
+            len: metadata.len(),
+            full: !metadata_only,
+            post: post.clone(),
+        },
+    );
+
+    Ok(post)
+}
+
+fn parse(file_path: &Path, project_path: &Path, metadata_only: bool) -> Result<Post, String> {
+    if metadata_only {
+        Post::from_file_metadata(file_path, project_path)
+    } else {
+        Post::from_file(file_path, project_path)
+    }
+}
+
+/// Drop cached entries for files under `project_path`
+pub fn invalidate(project_path: &Path) {
+    POST_CACHE
+        .lock()
+        .unwrap()
+        .retain(|path, _| !path.starts_with(project_path));
+}
+
+/// Drop the cached entry for one file, e.g. after the app writes it. Edits
+/// within the filesystem's mtime resolution would otherwise go unnoticed.
+pub fn invalidate_file(file_path: &Path) {
+    POST_CACHE.lock().unwrap().remove(file_path);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn load_post_refreshes_after_change() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("post.md");
+        fs::write(&path, "---\ntitle: First\ndate: 2024-01-01\n---\nBody").unwrap();
+
+        let post = load_post(&path, dir.path(), true).unwrap();
+        assert_eq!(post.title, "First");
+        assert!(post.content.is_empty());
+
+        // A metadata-only entry can't serve a full read
+        assert_eq!(load_post(&path, dir.path(), false).unwrap().content, "Body");
+
+        fs::write(&path, "---\ntitle: Second title\ndate: 2024-01-01\n---\nBody").unwrap();
+        assert_eq!(load_post(&path, dir.path(), false).unwrap().title, "Second title");
+
+        invalidate(dir.path());
+        assert!(POST_CACHE.lock().unwrap().get(&path).is_none());
+    }
+}
@@ -0,0 +1,106 @@
+// Localized error messages
+// Common errors carry a code the frontend can translate itself, and are
+// worded in the app's UI language: `[post_not_found] Пост не найден`
+
+use std::sync::Mutex;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorCode {
+    PostNotFound,
+    PageNotFound,
+    DraftNotFound,
+    TitleRequired,
+    HugoConfigNotFound,
+    ContentDirNotFound,
+    ServerAlreadyRunning,
+    NotGitRepository,
+    /// A save was rejected because the file changed on disk since it was loaded
+    FileChanged,
+    /// A save was rejected because the file was deleted on disk
+    FileDeleted,
+    /// A write was refused because it would convert a non-UTF-8 file
+    NotUtf8,
+}
+
+impl ErrorCode {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ErrorCode::PostNotFound => "post_not_found",
+            ErrorCode::PageNotFound => "page_not_found",
+            ErrorCode::DraftNotFound => "draft_not_found",
+            ErrorCode::TitleRequired => "title_required",
+            ErrorCode::HugoConfigNotFound => "hugo_config_not_found",
+            ErrorCode::ContentDirNotFound => "content_dir_not_found",
+            ErrorCode::ServerAlreadyRunning => "server_already_running",
+            ErrorCode::NotGitRepository => "not_git_repository",
+            ErrorCode::FileChanged => "file_changed",
+            ErrorCode::FileDeleted => "file_deleted",
+            ErrorCode::NotUtf8 => "not_utf8",
+        }
+    }
+
+    /// Message in `language`; languages without a translation get English
+    pub fn message(self, language: &str) -> &'static str {
+        match (self, language) {
+            (ErrorCode::PostNotFound, "ru") => "Пост не найден",
+            (ErrorCode::PostNotFound, _) => "Post not found",
+            (ErrorCode::PageNotFound, "ru") => "Страница не найдена",
+            (ErrorCode::PageNotFound, _) => "Page not found",
+            (ErrorCode::DraftNotFound, "ru") => "Черновик не найден",
+            (ErrorCode::DraftNotFound, _) => "Draft not found",
+            (ErrorCode::TitleRequired, "ru") => "Укажите заголовок",
+            (ErrorCode::TitleRequired, _) => "Title is required",
+            (ErrorCode::HugoConfigNotFound, "ru") => "Конфигурация Hugo не найдена (config.* или hugo.*)",
+            (ErrorCode::HugoConfigNotFound, _) => "Hugo config not found (config.* or hugo.*)",
+            (ErrorCode::ContentDirNotFound, "ru") => "Каталог content/ не найден",
+            (ErrorCode::ContentDirNotFound, _) => "content/ directory not found",
+            (ErrorCode::ServerAlreadyRunning, "ru") => "Сервер уже запущен",
+            (ErrorCode::ServerAlreadyRunning, _) => "Server is already running",
+            (ErrorCode::NotGitRepository, "ru") => "Это не git-репозиторий",
+            (ErrorCode::NotGitRepository, _) => "Not a git repository",
+            (ErrorCode::FileChanged, "ru") => "Файл изменён на диске после загрузки",
+            (ErrorCode::FileChanged, _) => "File changed on disk since it was loaded",
+            (ErrorCode::FileDeleted, "ru") => "Файл удалён на диске",
+            (ErrorCode::FileDeleted, _) => "File was deleted on disk",
+            (ErrorCode::NotUtf8, "ru") => "Файл не в кодировке UTF-8; запись преобразует его",
+            (ErrorCode::NotUtf8, _) => "File is not UTF-8; writing would convert it",
+        }
+    }
+}
+
+lazy_static::lazy_static! {
+    /// UI language read from the app config on first use; `AppConfig::save`
+    /// keeps it current, so error paths don't read the config file each time
+    static ref UI_LANGUAGE: Mutex<Option<String>> = Mutex::new(None);
+}
+
+/// Error string for `code` in the configured UI language, prefixed with the code
+pub fn localized(code: ErrorCode) -> String {
+    let mut language = UI_LANGUAGE.lock().unwrap();
+    let language = language.get_or_insert_with(|| {
+        crate::config::AppConfig::load()
+            .map(|config| config.ui_language)
+            .unwrap_or_default()
+    });
+    format_error(code, language)
+}
+
+/// Remember the UI language after the app config is saved
+pub fn set_ui_language(language: &str) {
+    *UI_LANGUAGE.lock().unwrap() = Some(language.to_string());
+}
+
+fn format_error(code: ErrorCode, language: &str) -> String {
+    format!("[{}] {}", code.as_str(), code.message(language))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_error_falls_back_to_english() {
+        assert_eq!(format_error(ErrorCode::PostNotFound, "ru"), "[post_not_found] Пост не найден");
+        assert_eq!(format_error(ErrorCode::PostNotFound, "de"), "[post_not_found] Post not found");
+    }
+}
@@ -1,8 +1,283 @@
 // File operations module
 
+use sha2::{Digest, Sha256};
 use std::fs;
-use std::path::Path;
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+const UTF8_BOM: char = '\u{FEFF}';
 
 pub fn read_file(path: &Path) -> Result<String, String> {
-    fs::read_to_string(path).map_err(|e| format!("Failed to read file: {}", e))
+    read_file_with_encoding(path).map(|(text, _)| text)
+}
+
+/// Read a text file in any encoding. UTF-8 is tried first; other files are
+/// decoded by their byte order mark or, failing that, a detected legacy
+/// encoding such as windows-1251. UTF-8 files with a few stray bytes are
+/// decoded as UTF-8 with replacement characters rather than guessed at. The
+/// encoding's name is returned whenever the bytes weren't clean UTF-8, so the
+/// app can warn before a save rewrites the file.
+///
+/// A leading byte order mark is never part of the text, so frontmatter
+/// delimiters are found in files from editors that write one.
+pub fn read_file_with_encoding(path: &Path) -> Result<(String, Option<&'static str>), String> {
+    let bytes = fs::read(path).map_err(|e| format!("Failed to read file: {}", e))?;
+    let bytes = match String::from_utf8(bytes) {
+        Ok(mut text) => {
+            if text.starts_with(UTF8_BOM) {
+                text.drain(..UTF8_BOM.len_utf8());
+            }
+            return Ok((text, None));
+        },
+        Err(e) => e.into_bytes(),
+    };
+
+    let encoding = match encoding_rs::Encoding::for_bom(&bytes) {
+        Some((encoding, _)) => encoding,
+        None if is_mostly_utf8(&bytes) => encoding_rs::UTF_8,
+        None => {
+            let mut detector = chardetng::EncodingDetector::new();
+            detector.feed(&bytes, true);
+            detector.guess(None, false)
+        }
+    };
+    let (text, encoding, _) = encoding.decode(&bytes);
+
+    Ok((text.into_owned(), Some(encoding.name())))
+}
+
+/// Whether invalid bytes are strays in UTF-8 text: its valid multibyte
+/// characters outnumber the invalid sequences. Legacy single-byte text
+/// rarely forms even one valid multibyte sequence.
+fn is_mostly_utf8(bytes: &[u8]) -> bool {
+    let (mut multibyte, mut invalid) = (0, 0);
+    for chunk in bytes.utf8_chunks() {
+        multibyte += chunk.valid().chars().filter(|c| !c.is_ascii()).count();
+        if !chunk.invalid().is_empty() {
+            invalid += 1;
+        }
+    }
+    multibyte > invalid
+}
+
+/// SHA-256 of a file's bytes as lowercase hex. Unlike the mtime it only
+/// changes when the content does, so it tells real edits from touches. It is
+/// computed on every call: a cache keyed by mtime would miss the very edits
+/// the hash is meant to catch.
+pub fn content_hash(path: &Path) -> Result<String, String> {
+    let bytes = fs::read(path).map_err(|e| format!("Failed to read file: {}", e))?;
+    Ok(Sha256::digest(&bytes)
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect())
+}
+
+/// Read only the leading `---` or `+++` delimited frontmatter block of a
+/// markdown file, without loading the body. Files in any other layout, or
+/// not in UTF-8, are read whole, since their frontmatter can't be found line
+/// by line.
+pub fn read_frontmatter_block(path: &Path) -> Result<String, String> {
+    match read_delimited_block(path) {
+        Ok(Some(block)) => Ok(block),
+        Ok(None) => read_file(path),
+        Err(e) if e.kind() == std::io::ErrorKind::InvalidData => read_file(path),
+        Err(e) => Err(format!("Failed to read file: {}", e)),
+    }
+}
+
+fn read_delimited_block(path: &Path) -> std::io::Result<Option<String>> {
+    let mut reader = BufReader::new(fs::File::open(path)?);
+    let mut block = String::new();
+
+    reader.read_line(&mut block)?;
+    if block.starts_with(UTF8_BOM) {
+        block.drain(..UTF8_BOM.len_utf8());
+    }
+    let Some(delimiter) = crate::markdown::opening_delimiter(&block) else {
+        return Ok(None);
+    };
+
+    loop {
+        let start = block.len();
+        if reader.read_line(&mut block)? == 0 || crate::markdown::is_delimiter_line(&block[start..], delimiter) {
+            return Ok(Some(block));
+        }
+    }
+}
+
+/// Replace a file's contents atomically: the data goes to a temporary file in
+/// the same directory, which is then renamed over the target. An interrupted
+/// write leaves the original untouched. Existing permissions are kept.
+pub fn write_file_atomic(path: &Path, contents: &[u8]) -> Result<(), String> {
+    write_atomic_with(path, |file| file.write_all(contents))
+}
+
+fn write_atomic_with<F>(path: &Path, write: F) -> Result<(), String>
+where
+    F: FnOnce(&mut fs::File) -> std::io::Result<()>,
+{
+    let temp_path = temp_path_for(path)?;
+
+    let result = (|| {
+        let mut file = fs::File::create(&temp_path)?;
+        write(&mut file)?;
+        file.sync_all()?;
+        drop(file);
+
+        if let Ok(metadata) = fs::metadata(path) {
+            fs::set_permissions(&temp_path, metadata.permissions())?;
+        }
+
+        fs::rename(&temp_path, path)
+    })();
+
+    result.map_err(|e| {
+        let _ = fs::remove_file(&temp_path);
+        format!("Failed to write file: {}", e)
+    })
+}
+
+/// Hidden sibling of `path` used as the write target before the rename
+fn temp_path_for(path: &Path) -> Result<PathBuf, String> {
+    let file_name = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .ok_or("Invalid file name")?;
+
+    Ok(path.with_file_name(format!(
+        ".{}.{}-{}.tmp",
+        file_name,
+        std::process::id(),
+        chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default()
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io;
+
+    #[test]
+    fn write_file_atomic_replaces_contents() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("post.md");
+        fs::write(&path, "old").unwrap();
+
+        write_file_atomic(&path, b"new contents").unwrap();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "new contents");
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn content_hash_follows_content() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("post.md");
+        fs::write(&path, "abc").unwrap();
+
+        let hash = content_hash(&path).unwrap();
+        assert_eq!(hash, "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad");
+
+        // Same size, likely the same mtime: still a different hash
+        fs::write(&path, "abd").unwrap();
+        assert_ne!(content_hash(&path).unwrap(), hash);
+    }
+
+    #[test]
+    fn read_frontmatter_block_stops_at_delimiter() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("post.md");
+        fs::write(&path, "---\ntitle: Hi\n---\nBody text\n").unwrap();
+        assert_eq!(read_frontmatter_block(&path).unwrap(), "---\ntitle: Hi\n---\n");
+
+        fs::write(&path, "No frontmatter\n").unwrap();
+        assert_eq!(read_frontmatter_block(&path).unwrap(), "No frontmatter\n");
+    }
+
+    #[test]
+    fn read_strips_utf8_bom() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("post.md");
+        fs::write(&path, "\u{FEFF}---\ntitle: Hi\ndate: 2024-01-01\n---\nBody\n").unwrap();
+
+        let (doc, had_no_frontmatter) = crate::markdown::MarkdownDocument::parse(&read_file(&path).unwrap()).unwrap();
+        assert!(!had_no_frontmatter);
+        assert_eq!(doc.frontmatter.title, "Hi");
+        assert_eq!(doc.content, "Body");
+
+        assert_eq!(
+            read_frontmatter_block(&path).unwrap(),
+            "---\ntitle: Hi\ndate: 2024-01-01\n---\n"
+        );
+    }
+
+    #[test]
+    fn read_file_decodes_legacy_encodings() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("post.md");
+        let text = "---\ntitle: Привет\n---\nЭто старый пост, сохранённый в кодировке Windows.\n";
+
+        fs::write(&path, text).unwrap();
+        assert_eq!(read_file_with_encoding(&path).unwrap(), (text.to_string(), None));
+
+        let (bytes, _, _) = encoding_rs::WINDOWS_1251.encode(text);
+        fs::write(&path, &bytes).unwrap();
+        assert_eq!(read_file_with_encoding(&path).unwrap(), (text.to_string(), Some("windows-1251")));
+        assert_eq!(read_frontmatter_block(&path).unwrap(), text);
+
+        let utf16: Vec<u8> = [0xFF, 0xFE]
+            .into_iter()
+            .chain(text.encode_utf16().flat_map(u16::to_le_bytes))
+            .collect();
+        fs::write(&path, utf16).unwrap();
+        assert_eq!(read_file_with_encoding(&path).unwrap(), (text.to_string(), Some("UTF-16LE")));
+    }
+
+    #[test]
+    fn read_file_keeps_utf8_with_stray_bytes() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("post.md");
+        let mut bytes = "---\ntitle: Привет\n---\nТекст".as_bytes().to_vec();
+        bytes.push(0xFF);
+        bytes.extend_from_slice("ещё текст\n".as_bytes());
+
+        fs::write(&path, &bytes).unwrap();
+        assert_eq!(
+            read_file_with_encoding(&path).unwrap(),
+            ("---\ntitle: Привет\n---\nТекст\u{FFFD}ещё текст\n".to_string(), Some("UTF-8"))
+        );
+    }
+
+    #[test]
+    fn interrupted_write_leaves_original_intact() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("post.md");
+        fs::write(&path, "original contents").unwrap();
+
+        // Simulate the process dying halfway through writing the new version
+        let result = write_atomic_with(&path, |file| {
+            file.write_all(b"partial")?;
+            Err(io::Error::new(io::ErrorKind::Interrupted, "killed"))
+        });
+
+        assert!(result.is_err());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "original contents");
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn write_file_atomic_preserves_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("post.md");
+        fs::write(&path, "old").unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o640)).unwrap();
+
+        write_file_atomic(&path, b"new").unwrap();
+
+        let mode = fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o640);
+    }
 }
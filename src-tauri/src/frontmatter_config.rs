@@ -1,12 +1,13 @@
 // Frontmatter configuration loading for custom fields
 
 use crate::hugo::HugoProject;
-use crate::markdown::MarkdownDocument;
+use crate::markdown::{Frontmatter, MarkdownDocument};
 use chrono::DateTime;
+use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use walkdir::WalkDir;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -20,6 +21,9 @@ pub struct FrontmatterConfig {
     pub custom_fields: Vec<FrontmatterField>,
     #[serde(default)]
     pub field_groups: Vec<FrontmatterFieldGroup>,
+    /// Refuse to save posts and pages whose frontmatter has validation errors
+    #[serde(default)]
+    pub strict_validation: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -27,10 +31,15 @@ pub struct FrontmatterConfig {
 pub struct FrontmatterField {
     pub name: String,
     pub label: Option<String>,
+    /// One of string, text, number, boolean, date, datetime, image, object
+    /// or array. Lists of one kind are `string[]`, `number[]` or `object[]`;
+    /// `array` is any list.
     #[serde(rename = "type")]
     pub field_type: String,
     pub description: Option<String>,
     pub ui: Option<FrontmatterFieldUi>,
+    #[serde(default)]
+    pub required: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -57,14 +66,36 @@ impl Default for FrontmatterConfig {
             is_default: true,
             custom_fields: Vec::new(),
             field_groups: Vec::new(),
+            strict_validation: false,
         }
     }
 }
 
+/// Frontmatter fields every post has, which groups may list without declaring
+const BUILT_IN_FIELDS: [&str; 15] = [
+    "title",
+    "date",
+    "tags",
+    "categories",
+    "updated",
+    "comments",
+    "layout",
+    "permalink",
+    "slug",
+    "aliases",
+    "description",
+    "draft",
+    "weight",
+    "publishDate",
+    "expiryDate",
+];
+
+pub fn frontmatter_config_path(project_path: &Path) -> PathBuf {
+    project_path.join(".hugo-bros").join("frontmatter-config.json")
+}
+
 pub fn load_frontmatter_config(project_path: &Path) -> Result<FrontmatterConfig, String> {
-    let config_path = project_path
-        .join(".hugo-bros")
-        .join("frontmatter-config.json");
+    let config_path = frontmatter_config_path(project_path);
 
     if !config_path.exists() {
         return Ok(FrontmatterConfig::default());
@@ -79,31 +110,52 @@ pub fn load_frontmatter_config(project_path: &Path) -> Result<FrontmatterConfig,
     Ok(config)
 }
 
-pub fn generate_frontmatter_config(project_path: &Path) -> Result<FrontmatterConfig, String> {
+/// Infer custom fields from the frontmatter of posts and pages, and of
+/// drafts when `include_drafts` is set
+pub fn generate_frontmatter_config(
+    project_path: &Path,
+    include_drafts: bool,
+) -> Result<FrontmatterConfig, String> {
     let project = HugoProject::new(project_path.to_path_buf());
-    let posts_dir = project.get_posts_dir();
+    let drafts_dir = project.get_drafts_dir();
 
     let mut stats: HashMap<String, FieldStats> = HashMap::new();
 
-    if posts_dir.exists() {
-        for entry in WalkDir::new(&posts_dir)
-            .max_depth(1)
-            .into_iter()
-            .filter_map(|e| e.ok())
-        {
-            let path = entry.path();
-            if path.is_file() && path.extension().and_then(|s| s.to_str()) == Some("md") {
-                let content = fs::read_to_string(path)
-                    .map_err(|e| format!("Failed to read post {:?}: {}", path, e))?;
-                if let Ok((doc, _)) = MarkdownDocument::parse(&content) {
-                    for (key, value) in doc.frontmatter.custom_fields {
-                        let entry = stats.entry(key).or_default();
-                        entry.total += 1;
-                        if let Some(field_type) = infer_value_type(&value) {
-                            *entry.type_counts.entry(field_type).or_insert(0) += 1;
-                        }
-                    }
-                }
+    // Posts usually sit inside the pages dir; a set keeps each file counted once
+    let mut roots = vec![project.get_posts_dir(), project.get_pages_dir()];
+    if include_drafts {
+        roots.push(drafts_dir.clone());
+    }
+    let paths: BTreeSet<PathBuf> = roots
+        .iter()
+        .filter(|root| root.exists())
+        .flat_map(|root| {
+            WalkDir::new(root)
+                .max_depth(4)
+                .into_iter()
+                .filter_map(|e| e.ok())
+                .map(|entry| entry.into_path())
+        })
+        .filter(|path| path.is_file() && path.extension().and_then(|s| s.to_str()) == Some("md"))
+        .filter(|path| include_drafts || !path.starts_with(&drafts_dir))
+        .collect();
+
+    // Parse in parallel; tallying stays serial
+    let parsed: Vec<Option<MarkdownDocument>> = paths
+        .par_iter()
+        .map(|path| {
+            let content = crate::files::read_file(path)
+                .map_err(|e| format!("Failed to read {:?}: {}", path, e))?;
+            Ok(MarkdownDocument::parse(&content).ok().map(|(doc, _)| doc))
+        })
+        .collect::<Result<_, String>>()?;
+
+    for doc in parsed.into_iter().flatten() {
+        for (key, value) in doc.frontmatter.custom_fields {
+            let entry = stats.entry(key).or_default();
+            entry.total += 1;
+            if let Some(field_type) = infer_value_type(&value) {
+                *entry.type_counts.entry(field_type).or_insert(0) += 1;
             }
         }
     }
@@ -116,6 +168,7 @@ pub fn generate_frontmatter_config(project_path: &Path) -> Result<FrontmatterCon
             field_type: stat.preferred_type(),
             description: None,
             ui: None,
+            required: false,
         })
         .collect();
     custom_fields.sort_by(|a, b| a.name.cmp(&b.name));
@@ -156,9 +209,282 @@ pub fn generate_frontmatter_config(project_path: &Path) -> Result<FrontmatterCon
         is_default: false,
         custom_fields,
         field_groups,
+        strict_validation: false,
     })
 }
 
+/// Combine a freshly generated config with the existing one. Fields still
+/// found in the content keep their customized label, type and ui; newly
+/// found fields are added and fields no longer used are dropped, from the
+/// groups too. Project settings like strict validation are kept.
+pub fn merge_frontmatter_config(
+    existing: &FrontmatterConfig,
+    generated: FrontmatterConfig,
+) -> FrontmatterConfig {
+    let found: Vec<&str> = generated.custom_fields.iter().map(|f| f.name.as_str()).collect();
+    let is_kept = |name: &str| found.contains(&name) || BUILT_IN_FIELDS.contains(&name);
+
+    let mut custom_fields: Vec<FrontmatterField> = existing
+        .custom_fields
+        .iter()
+        .filter(|field| found.contains(&field.name.as_str()))
+        .cloned()
+        .collect();
+    for field in &generated.custom_fields {
+        if !custom_fields.iter().any(|f| f.name == field.name) {
+            custom_fields.push(field.clone());
+        }
+    }
+
+    let field_groups = if existing.field_groups.is_empty() {
+        generated.field_groups
+    } else {
+        existing
+            .field_groups
+            .iter()
+            .map(|group| FrontmatterFieldGroup {
+                fields: group.fields.iter().filter(|f| is_kept(f)).cloned().collect(),
+                ..group.clone()
+            })
+            .filter(|group| !group.fields.is_empty())
+            .collect()
+    };
+
+    let preview_image_field = existing
+        .preview_image_field
+        .clone()
+        .filter(|field| is_kept(field))
+        .or(generated.preview_image_field);
+
+    FrontmatterConfig {
+        version: existing.version.clone(),
+        preview_image_field,
+        is_default: false,
+        custom_fields,
+        field_groups,
+        strict_validation: existing.strict_validation,
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct FrontmatterIssue {
+    pub field: String,
+    pub severity: IssueSeverity,
+    pub message: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum IssueSeverity {
+    Error,
+    Warning,
+}
+
+/// Write the config to `.hugo-bros/frontmatter-config.json`
+pub fn write_frontmatter_config(project_path: &Path, config: &FrontmatterConfig) -> Result<(), String> {
+    let config_path = frontmatter_config_path(project_path);
+    if let Some(parent) = config_path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create .hugo-bros directory: {}", e))?;
+    }
+
+    let content = serde_json::to_string_pretty(config)
+        .map_err(|e| format!("Failed to serialize frontmatter config: {}", e))?;
+    crate::files::write_file_atomic(&config_path, content.as_bytes())
+        .map_err(|e| format!("Failed to write frontmatter config: {}", e))
+}
+
+/// Check the config itself: every group field must be a declared custom field
+/// or a built-in one, and a field may belong to only one group
+pub fn validate_config(config: &FrontmatterConfig) -> Vec<FrontmatterIssue> {
+    let mut issues = Vec::new();
+    let mut declared: Vec<&str> = Vec::new();
+
+    for field in &config.custom_fields {
+        if declared.contains(&field.name.as_str()) {
+            issues.push(FrontmatterIssue {
+                field: field.name.clone(),
+                severity: IssueSeverity::Error,
+                message: "Custom field is declared more than once".to_string(),
+            });
+        }
+        declared.push(&field.name);
+    }
+
+    let mut grouped: HashMap<&str, &str> = HashMap::new();
+    for group in &config.field_groups {
+        for field in &group.fields {
+            if !declared.contains(&field.as_str()) && !BUILT_IN_FIELDS.contains(&field.as_str()) {
+                issues.push(FrontmatterIssue {
+                    field: field.clone(),
+                    severity: IssueSeverity::Error,
+                    message: format!("Group '{}' references an unknown field", group.name),
+                });
+            }
+            if let Some(other) = grouped.insert(field, &group.name) {
+                issues.push(FrontmatterIssue {
+                    field: field.clone(),
+                    severity: IssueSeverity::Error,
+                    message: if other == group.name {
+                        format!("Listed twice in group '{}'", group.name)
+                    } else {
+                        format!("Listed in both '{}' and '{}'", other, group.name)
+                    },
+                });
+            }
+        }
+    }
+
+    issues
+}
+
+/// Check custom field values against the types declared in the config
+pub fn validate_frontmatter(
+    project_path: &Path,
+    config: &FrontmatterConfig,
+    frontmatter: &Frontmatter,
+) -> Vec<FrontmatterIssue> {
+    let static_dir = HugoProject::new(project_path.to_path_buf()).get_static_dir();
+    let mut issues = Vec::new();
+
+    for field in &config.custom_fields {
+        let value = frontmatter
+            .custom_fields
+            .get(&field.name)
+            .filter(|value| !is_empty_value(value));
+
+        let Some(value) = value else {
+            if field.required {
+                issues.push(FrontmatterIssue {
+                    field: field.name.clone(),
+                    severity: IssueSeverity::Error,
+                    message: "Required field is missing".to_string(),
+                });
+            }
+            continue;
+        };
+
+        if !value_matches_type(value, &field.field_type) {
+            issues.push(FrontmatterIssue {
+                field: field.name.clone(),
+                severity: IssueSeverity::Error,
+                message: format!("Expected a value of type {}", field.field_type),
+            });
+            continue;
+        }
+
+        if field.field_type == "image" {
+            if let Some(image) = value.as_str() {
+                if let Some(image_path) = resolve_static_path(&static_dir, image) {
+                    if !image_path.is_file() {
+                        issues.push(FrontmatterIssue {
+                            field: field.name.clone(),
+                            severity: IssueSeverity::Warning,
+                            message: format!("Image not found: {}", image),
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    issues.extend(schedule_issues(frontmatter, chrono::Utc::now()));
+    issues
+}
+
+/// Problems with `publishDate`/`expiryDate`: unparseable values, and content
+/// that Hugo won't render at `now` without `--buildFuture`/`--buildExpired`
+fn schedule_issues(frontmatter: &Frontmatter, now: DateTime<chrono::Utc>) -> Vec<FrontmatterIssue> {
+    let mut issues = Vec::new();
+    let fields = [
+        ("publishDate", &frontmatter.publish_date),
+        ("expiryDate", &frontmatter.expiry_date),
+    ];
+
+    for (name, value) in fields {
+        let Some(value) = value.as_deref().filter(|v| !v.trim().is_empty()) else {
+            continue;
+        };
+        let Some(date) = crate::markdown::parse_frontmatter_date(value) else {
+            issues.push(FrontmatterIssue {
+                field: name.to_string(),
+                severity: IssueSeverity::Error,
+                message: format!("Invalid date: {}", value),
+            });
+            continue;
+        };
+
+        let message = match name {
+            "publishDate" if date > now => "Scheduled for the future; Hugo skips it unless built with --buildFuture",
+            "expiryDate" if date <= now => "Expired; Hugo skips it unless built with --buildExpired",
+            _ => continue,
+        };
+        issues.push(FrontmatterIssue {
+            field: name.to_string(),
+            severity: IssueSeverity::Warning,
+            message: message.to_string(),
+        });
+    }
+
+    issues
+}
+
+fn is_empty_value(value: &serde_yaml::Value) -> bool {
+    match value {
+        serde_yaml::Value::Null => true,
+        serde_yaml::Value::String(text) => text.trim().is_empty(),
+        _ => false,
+    }
+}
+
+fn value_matches_type(value: &serde_yaml::Value, field_type: &str) -> bool {
+    match field_type {
+        "string" | "text" | "image" => value.is_string(),
+        "number" => value.is_number(),
+        "boolean" => value.is_bool(),
+        "date" | "datetime" => value
+            .as_str()
+            .is_some_and(|text| looks_like_datetime(text) || looks_like_date(text)),
+        "array" => value.is_sequence(),
+        "string[]" => is_sequence_of(value, serde_yaml::Value::is_string),
+        "number[]" => is_sequence_of(value, serde_yaml::Value::is_number),
+        "object[]" => is_sequence_of(value, serde_yaml::Value::is_mapping),
+        "object" => value.is_mapping(),
+        _ => true,
+    }
+}
+
+fn is_sequence_of(value: &serde_yaml::Value, is_kind: fn(&serde_yaml::Value) -> bool) -> bool {
+    value.as_sequence().is_some_and(|items| items.iter().all(is_kind))
+}
+
+/// Resolve a site image reference to its file under `static/`.
+/// External URLs and references that climb out with `..` resolve to `None`.
+pub fn resolve_static_path(static_dir: &Path, reference: &str) -> Option<PathBuf> {
+    let reference = reference.trim();
+    if reference.contains("://") || reference.starts_with("//") || reference.starts_with("data:") {
+        return None;
+    }
+    let relative = reference
+        .split(['?', '#'])
+        .next()
+        .unwrap_or("")
+        .trim_start_matches('/');
+    if relative.is_empty() || has_parent_dir(relative) {
+        return None;
+    }
+    Some(static_dir.join(relative))
+}
+
+/// Whether a reference has a `..` segment, which could point outside the
+/// folder it is resolved against
+pub fn has_parent_dir(reference: &str) -> bool {
+    Path::new(reference)
+        .components()
+        .any(|c| matches!(c, std::path::Component::ParentDir))
+}
+
 #[derive(Default)]
 struct FieldStats {
     total: u32,
@@ -184,7 +510,7 @@ fn infer_value_type(value: &serde_yaml::Value) -> Option<String> {
         serde_yaml::Value::Null => None,
         serde_yaml::Value::Bool(_) => Some("boolean".to_string()),
         serde_yaml::Value::Number(_) => Some("number".to_string()),
-        serde_yaml::Value::Sequence(_) => Some("array".to_string()),
+        serde_yaml::Value::Sequence(items) => Some(infer_sequence_type(items).to_string()),
         serde_yaml::Value::Mapping(_) => Some("object".to_string()),
         serde_yaml::Value::Tagged(tagged) => infer_value_type(&tagged.value),
         serde_yaml::Value::String(value) => {
@@ -208,6 +534,24 @@ fn infer_value_type(value: &serde_yaml::Value) -> Option<String> {
     }
 }
 
+/// `string[]`, `number[]` or `object[]` when every item has that kind;
+/// empty and mixed lists are a plain `array`
+fn infer_sequence_type(items: &[serde_yaml::Value]) -> &'static str {
+    let kinds = [
+        ("string[]", serde_yaml::Value::is_string as fn(&serde_yaml::Value) -> bool),
+        ("number[]", serde_yaml::Value::is_number),
+        ("object[]", serde_yaml::Value::is_mapping),
+    ];
+    if items.is_empty() {
+        return "array";
+    }
+    kinds
+        .into_iter()
+        .find(|(_, is_kind)| items.iter().all(is_kind))
+        .map(|(name, _)| name)
+        .unwrap_or("array")
+}
+
 fn looks_like_image(value: &str) -> bool {
     let lowercase = value.to_lowercase();
     let has_image_path = lowercase.contains("/images/");
@@ -289,3 +633,127 @@ fn pick_preview_image_field(
 
     best.map(|(name, _)| name)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn field(name: &str) -> FrontmatterField {
+        FrontmatterField {
+            name: name.to_string(),
+            label: None,
+            field_type: "string".to_string(),
+            description: None,
+            ui: None,
+            required: false,
+        }
+    }
+
+    fn group(name: &str, fields: &[&str]) -> FrontmatterFieldGroup {
+        FrontmatterFieldGroup {
+            name: name.to_string(),
+            label: None,
+            fields: fields.iter().map(|f| f.to_string()).collect(),
+            collapsed: None,
+        }
+    }
+
+    #[test]
+    fn validate_config_flags_unknown_and_duplicate_group_fields() {
+        let config = FrontmatterConfig {
+            custom_fields: vec![field("cover"), field("cover_alt")],
+            field_groups: vec![
+                group("images", &["cover", "cover_alt", "covr"]),
+                group("meta", &["title", "cover"]),
+            ],
+            ..FrontmatterConfig::default()
+        };
+
+        let issues = validate_config(&config);
+        let fields: Vec<&str> = issues.iter().map(|i| i.field.as_str()).collect();
+        assert_eq!(fields, vec!["covr", "cover"]);
+        assert!(issues[1].message.contains("'images' and 'meta'"));
+    }
+
+    #[test]
+    fn merge_keeps_customized_fields_and_adds_new_ones() {
+        let mut cover = field("cover");
+        cover.label = Some("Cover image".to_string());
+        cover.field_type = "image".to_string();
+        let existing = FrontmatterConfig {
+            preview_image_field: Some("cover".to_string()),
+            custom_fields: vec![cover, field("old")],
+            field_groups: vec![group("media", &["cover", "old", "title"])],
+            strict_validation: true,
+            ..FrontmatterConfig::default()
+        };
+        let generated = FrontmatterConfig {
+            custom_fields: vec![field("cover"), field("rating")],
+            ..FrontmatterConfig::default()
+        };
+
+        let merged = merge_frontmatter_config(&existing, generated);
+        let names: Vec<&str> = merged.custom_fields.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, vec!["cover", "rating"]);
+        assert_eq!(merged.custom_fields[0].label.as_deref(), Some("Cover image"));
+        assert_eq!(merged.custom_fields[0].field_type, "image");
+        assert_eq!(merged.field_groups[0].fields, vec!["cover", "title"]);
+        assert_eq!(merged.preview_image_field.as_deref(), Some("cover"));
+        assert!(merged.strict_validation);
+        assert!(validate_config(&merged).is_empty());
+    }
+
+    #[test]
+    fn schedule_issues_flag_future_expired_and_invalid_dates() {
+        let (mut doc, _) =
+            MarkdownDocument::parse("---\ntitle: T\ndate: 2024-01-01\n---\n").unwrap();
+        let now = chrono::Utc::now();
+        assert!(schedule_issues(&doc.frontmatter, now).is_empty());
+
+        doc.frontmatter.publish_date = Some("2999-01-01".to_string());
+        doc.frontmatter.expiry_date = Some("2000-01-01T00:00:00Z".to_string());
+        let issues = schedule_issues(&doc.frontmatter, now);
+        assert_eq!(issues.len(), 2);
+        assert!(issues.iter().all(|i| i.severity == IssueSeverity::Warning));
+
+        doc.frontmatter.publish_date = Some("soon".to_string());
+        doc.frontmatter.expiry_date = None;
+        let issues = schedule_issues(&doc.frontmatter, now);
+        assert_eq!(issues[0].field, "publishDate");
+        assert_eq!(issues[0].severity, IssueSeverity::Error);
+    }
+
+    #[test]
+    fn generate_scans_nested_posts_pages_and_drafts() {
+        let dir = tempfile::tempdir().unwrap();
+        let content = dir.path().join("content");
+        fs::create_dir_all(content.join("posts/2024/trip")).unwrap();
+        fs::create_dir_all(content.join("about")).unwrap();
+        fs::create_dir_all(content.join("drafts")).unwrap();
+        fs::write(content.join("posts/2024/trip/index.md"), "---\ntitle: T\ndate: 2024-01-01\nrating: 5\n---\n").unwrap();
+        fs::write(content.join("about/index.md"), "---\ntitle: A\ndate: 2024-01-01\nrating: 4\nteam: x\n---\n").unwrap();
+        fs::write(content.join("drafts/d.md"), "---\ntitle: D\ndate: 2024-01-01\nmood: ok\n---\n").unwrap();
+
+        let config = generate_frontmatter_config(dir.path(), false).unwrap();
+        let names: Vec<&str> = config.custom_fields.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, vec!["rating", "team"]);
+        assert_eq!(config.custom_fields[0].field_type, "number");
+
+        let config = generate_frontmatter_config(dir.path(), true).unwrap();
+        assert!(config.custom_fields.iter().any(|f| f.name == "mood"));
+    }
+
+    #[test]
+    fn infer_sequence_types() {
+        let infer = |yaml: &str| infer_value_type(&serde_yaml::from_str(yaml).unwrap());
+        assert_eq!(infer("[a, b]").as_deref(), Some("string[]"));
+        assert_eq!(infer("[1, 2.5]").as_deref(), Some("number[]"));
+        assert_eq!(infer("[{name: a}, {name: b}]").as_deref(), Some("object[]"));
+        assert_eq!(infer("[a, 1]").as_deref(), Some("array"));
+        assert_eq!(infer("[]").as_deref(), Some("array"));
+
+        let value = serde_yaml::from_str("[a, 1]").unwrap();
+        assert!(!value_matches_type(&value, "string[]"));
+        assert!(value_matches_type(&value, "array"));
+    }
+}
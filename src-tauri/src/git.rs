@@ -0,0 +1,289 @@
+// Git integration for projects kept in a git repository
+// The site may live in a subfolder of the repository, so paths are converted
+// between project-relative (as used for content ids) and repository-relative
+
+use crate::errors::{localized, ErrorCode};
+use git2::{Repository, Status, StatusOptions};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum GitChangeKind {
+    Added,
+    Modified,
+    Deleted,
+    Renamed,
+    Untracked,
+    Conflicted,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GitFileStatus {
+    /// Path relative to the project, like content ids
+    pub path: String,
+    pub kind: GitChangeKind,
+    /// Whether the change is already in the index
+    pub staged: bool,
+}
+
+/// Repository containing the project and the project's path inside it
+fn open_repo(project_path: &Path) -> Result<(Repository, PathBuf), String> {
+    let repo = Repository::discover(project_path).map_err(|_| localized(ErrorCode::NotGitRepository))?;
+    let workdir = repo
+        .workdir()
+        .ok_or_else(|| localized(ErrorCode::NotGitRepository))?
+        .canonicalize()
+        .map_err(|e| format!("Failed to resolve repository path: {}", e))?;
+    let project = project_path
+        .canonicalize()
+        .map_err(|e| format!("Failed to resolve project path: {}", e))?;
+    let prefix = project
+        .strip_prefix(&workdir)
+        .map_err(|_| localized(ErrorCode::NotGitRepository))?
+        .to_path_buf();
+
+    Ok((repo, prefix))
+}
+
+/// Changed and untracked files under content/
+pub fn status(project_path: &Path) -> Result<Vec<GitFileStatus>, String> {
+    let (repo, prefix) = open_repo(project_path)?;
+    let content_prefix = prefix.join("content");
+
+    let mut options = StatusOptions::new();
+    options
+        .include_untracked(true)
+        .recurse_untracked_dirs(true)
+        .renames_head_to_index(true)
+        .pathspec(git_path(&content_prefix));
+
+    let statuses = repo
+        .statuses(Some(&mut options))
+        .map_err(|e| format!("Failed to read git status: {}", e))?;
+
+    let mut files = Vec::new();
+    for entry in statuses.iter() {
+        let Some(path) = entry.path() else {
+            continue;
+        };
+        let Ok(relative) = Path::new(path).strip_prefix(&prefix) else {
+            continue;
+        };
+        let Some((kind, staged)) = change_kind(entry.status()) else {
+            continue;
+        };
+        files.push(GitFileStatus {
+            path: git_path(relative),
+            kind,
+            staged,
+        });
+    }
+
+    Ok(files)
+}
+
+fn change_kind(status: Status) -> Option<(GitChangeKind, bool)> {
+    if status.is_conflicted() {
+        return Some((GitChangeKind::Conflicted, false));
+    }
+    let staged = status.intersects(
+        Status::INDEX_NEW
+            | Status::INDEX_MODIFIED
+            | Status::INDEX_DELETED
+            | Status::INDEX_RENAMED
+            | Status::INDEX_TYPECHANGE,
+    );
+    let kind = if status.is_wt_new() {
+        GitChangeKind::Untracked
+    } else if status.intersects(Status::INDEX_NEW) {
+        GitChangeKind::Added
+    } else if status.intersects(Status::INDEX_DELETED | Status::WT_DELETED) {
+        GitChangeKind::Deleted
+    } else if status.intersects(Status::INDEX_RENAMED | Status::WT_RENAMED) {
+        GitChangeKind::Renamed
+    } else if status.intersects(
+        Status::INDEX_MODIFIED | Status::WT_MODIFIED | Status::INDEX_TYPECHANGE | Status::WT_TYPECHANGE,
+    ) {
+        GitChangeKind::Modified
+    } else {
+        return None;
+    };
+    Some((kind, staged))
+}
+
+/// Stage the given project-relative paths (deleted files are removed from
+/// the index) and commit them. The commit holds HEAD plus these paths only;
+/// anything else already staged stays staged. Returns the new commit id.
+pub fn commit(project_path: &Path, message: &str, paths: &[String]) -> Result<String, String> {
+    let message = message.trim();
+    if message.is_empty() {
+        return Err("Commit message is required".to_string());
+    }
+    if paths.is_empty() {
+        return Err("No files to commit".to_string());
+    }
+
+    let (repo, prefix) = open_repo(project_path)?;
+    let mut repo_paths = Vec::with_capacity(paths.len());
+    for path in paths {
+        let relative = Path::new(path);
+        if relative.is_absolute() || relative.components().any(|c| c == std::path::Component::ParentDir) {
+            return Err(format!("Path is outside the project: {}", path));
+        }
+        repo_paths.push((path, prefix.join(relative), project_path.join(relative).exists()));
+    }
+
+    // An unborn branch (no commits yet) has no parent
+    let parent = match repo.head() {
+        Ok(head) => Some(
+            head.peel_to_commit()
+                .map_err(|e| format!("Failed to read HEAD: {}", e))?,
+        ),
+        Err(_) => None,
+    };
+
+    let mut index = repo
+        .index()
+        .map_err(|e| format!("Failed to read git index: {}", e))?;
+    stage_paths(&mut index, &repo_paths)?;
+    index
+        .write()
+        .map_err(|e| format!("Failed to write git index: {}", e))?;
+
+    // Build the tree from HEAD in memory, then reload the index from disk
+    match &parent {
+        Some(parent) => {
+            let head_tree = parent
+                .tree()
+                .map_err(|e| format!("Failed to read HEAD: {}", e))?;
+            index
+                .read_tree(&head_tree)
+                .map_err(|e| format!("Failed to read HEAD: {}", e))?;
+        }
+        None => index
+            .clear()
+            .map_err(|e| format!("Failed to read git index: {}", e))?,
+    }
+    stage_paths(&mut index, &repo_paths)?;
+    let tree_id = index
+        .write_tree()
+        .map_err(|e| format!("Failed to write git tree: {}", e))?;
+    index
+        .read(true)
+        .map_err(|e| format!("Failed to read git index: {}", e))?;
+
+    let tree = repo
+        .find_tree(tree_id)
+        .map_err(|e| format!("Failed to write git tree: {}", e))?;
+    let signature = repo
+        .signature()
+        .map_err(|e| format!("Failed to read git user: {}", e))?;
+    let parents: Vec<&git2::Commit> = parent.iter().collect();
+
+    let commit_id = repo
+        .commit(Some("HEAD"), &signature, &signature, message, &tree, &parents)
+        .map_err(|e| format!("Failed to commit: {}", e))?;
+
+    Ok(commit_id.to_string())
+}
+
+/// Add or remove each path in the index. Paths are taken literally, not as
+/// patterns, so names with `*` or `[` only match themselves.
+fn stage_paths(index: &mut git2::Index, paths: &[(&String, PathBuf, bool)]) -> Result<(), String> {
+    for (path, repo_path, exists) in paths {
+        let result = if *exists {
+            index.add_path(repo_path)
+        } else {
+            index.remove_path(repo_path)
+        };
+        result.map_err(|e| format!("Failed to stage {}: {}", path, e))?;
+    }
+    Ok(())
+}
+
+/// Repository paths always use forward slashes
+fn git_path(path: &Path) -> String {
+    path.to_string_lossy().replace('\\', "/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn init_repo(dir: &Path) -> Repository {
+        let repo = Repository::init(dir).unwrap();
+        let mut config = repo.config().unwrap();
+        config.set_str("user.name", "Test").unwrap();
+        config.set_str("user.email", "test@example.com").unwrap();
+        repo
+    }
+
+    #[test]
+    fn status_and_commit_in_subfolder_site() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = init_repo(dir.path());
+        let project = dir.path().join("site");
+        fs::create_dir_all(project.join("content/posts")).unwrap();
+        fs::write(project.join("content/posts/a.md"), "a").unwrap();
+        fs::write(project.join("content/posts/b.md"), "b").unwrap();
+        fs::write(project.join("hugo.toml"), "").unwrap();
+
+        let files = status(&project).unwrap();
+        assert_eq!(files.len(), 2);
+        assert!(files
+            .iter()
+            .all(|f| f.kind == GitChangeKind::Untracked && f.path.starts_with("content/posts/")));
+
+        commit(&project, "Add a", &["content/posts/a.md".to_string()]).unwrap();
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        assert_eq!(head.message(), Some("Add a"));
+        assert!(head.tree().unwrap().get_path(Path::new("site/content/posts/a.md")).is_ok());
+
+        let files = status(&project).unwrap();
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].path, "content/posts/b.md");
+
+        fs::remove_file(project.join("content/posts/a.md")).unwrap();
+        commit(&project, "Remove a", &["content/posts/a.md".to_string()]).unwrap();
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        assert!(head.tree().unwrap().get_path(Path::new("site/content/posts/a.md")).is_err());
+    }
+
+    #[test]
+    fn commit_leaves_other_staged_files_out() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = init_repo(dir.path());
+        fs::create_dir_all(dir.path().join("content/posts")).unwrap();
+        fs::write(dir.path().join("content/posts/a.md"), "a").unwrap();
+        fs::write(dir.path().join("content/posts/b.md"), "b").unwrap();
+        fs::write(dir.path().join("content/posts/[c].md"), "c").unwrap();
+
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new("content/posts/b.md")).unwrap();
+        index.write().unwrap();
+
+        commit(dir.path(), "Add a", &["content/posts/a.md".to_string(), "content/posts/[c].md".to_string()]).unwrap();
+        let tree = repo.head().unwrap().peel_to_commit().unwrap().tree().unwrap();
+        assert!(tree.get_path(Path::new("content/posts/a.md")).is_ok());
+        assert!(tree.get_path(Path::new("content/posts/[c].md")).is_ok());
+        assert!(tree.get_path(Path::new("content/posts/b.md")).is_err());
+
+        let files = status(dir.path()).unwrap();
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].path, "content/posts/b.md");
+        assert!(files[0].staged);
+    }
+
+    #[test]
+    fn status_outside_repository() {
+        let dir = tempfile::tempdir().unwrap();
+        // A parent directory of the temp dir could itself be a repository
+        if Repository::discover(dir.path()).is_ok() {
+            return;
+        }
+        assert!(status(dir.path()).unwrap_err().starts_with("[not_git_repository]"));
+    }
+}
@@ -1,14 +1,44 @@
 // Hugo integration module
 // Handles Hugo project structure, config parsing, and operations
 
-use std::path::PathBuf;
+use crate::errors::{localized, ErrorCode};
+use std::path::{Path, PathBuf};
 use std::process::{Command, Child, Stdio};
-use std::sync::{Arc, Mutex};
-use std::collections::HashMap;
+use std::sync::{Arc, Mutex, PoisonError, TryLockError};
+use std::collections::{HashMap, VecDeque};
+use std::io::{BufRead, BufReader, Read};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Port `hugo server` binds to when none is given
+pub const DEFAULT_SERVER_PORT: u16 = 1313;
+
+/// Error returned when the Hugo executable can't be found, so the frontend
+/// can offer installation help instead of a generic failure
+pub const HUGO_NOT_FOUND_ERROR: &str = "Hugo executable not found";
+
+/// Maximum number of log lines kept per server
+const MAX_SERVER_LOG_LINES: usize = 500;
+
+/// How long a server gets to shut down cleanly before it is killed
+const SERVER_STOP_TIMEOUT: Duration = Duration::from_secs(3);
 
 // Global state to track running Hugo servers
 lazy_static::lazy_static! {
-    static ref HUGO_SERVERS: Arc<Mutex<HashMap<String, Child>>> = Arc::new(Mutex::new(HashMap::new()));
+    static ref HUGO_SERVERS: Arc<Mutex<HashMap<String, RunningServer>>> = Arc::new(Mutex::new(HashMap::new()));
+    static ref HUGO_SERVER_LOGS: Arc<Mutex<HashMap<String, VecDeque<String>>>> = Arc::new(Mutex::new(HashMap::new()));
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContentState {
+    Published,
+    Draft,
+}
+
+/// A `hugo server` process and what we know about it
+struct RunningServer {
+    child: Child,
+    info: ServerInfo,
 }
 
 pub struct HugoProject {
@@ -22,14 +52,14 @@ impl HugoProject {
 
     pub fn validate(&self) -> Result<bool, String> {
         // Check if Hugo config exists
-        if self.find_config_path().is_none() {
-            return Err("Hugo config not found (config.* or hugo.*)".to_string());
+        if self.find_config_path().is_none() && self.find_config_dir().is_none() {
+            return Err(localized(ErrorCode::HugoConfigNotFound));
         }
 
         // Check if content/ directory exists
         let content_path = self.get_content_dir();
         if !content_path.exists() || !content_path.is_dir() {
-            return Err("content/ directory not found".to_string());
+            return Err(localized(ErrorCode::ContentDirNotFound));
         }
 
         Ok(true)
@@ -65,12 +95,23 @@ impl HugoProject {
         None
     }
 
+    /// `config/_default/`, when the site splits its config into a directory
+    pub fn find_config_dir(&self) -> Option<PathBuf> {
+        let dir = self.path.join("config").join("_default");
+        dir.is_dir().then_some(dir)
+    }
+
     pub fn get_content_dir(&self) -> PathBuf {
         self.path.join("content")
     }
 
     pub fn get_posts_dir(&self) -> PathBuf {
         let content_dir = self.get_content_dir();
+        // An explicit section wins, even before it exists on disk
+        let settings = crate::project_settings::ProjectSettings::load(&self.path);
+        if let Some(section) = settings.ok().and_then(|s| s.posts_section) {
+            return content_dir.join(section);
+        }
         let posts_dir = content_dir.join("posts");
         if posts_dir.exists() {
             return posts_dir;
@@ -86,17 +127,56 @@ impl HugoProject {
         self.get_content_dir()
     }
 
+    /// Folder whose files are drafts regardless of their `draft` flag
+    pub fn get_drafts_dir(&self) -> PathBuf {
+        self.get_content_dir().join("drafts")
+    }
+
+    /// Whether a content file is a draft: it lives under the drafts folder
+    /// or is flagged `draft: true`. Every list command decides with this.
+    pub fn classify_content(&self, path: &Path, frontmatter: &crate::markdown::Frontmatter) -> ContentState {
+        if path.starts_with(self.get_drafts_dir()) || frontmatter.draft.unwrap_or(false) {
+            ContentState::Draft
+        } else {
+            ContentState::Published
+        }
+    }
+
     pub fn get_static_dir(&self) -> PathBuf {
         self.path.join("static")
     }
 
+    /// The Hugo binary, run from the project root with the project's default
+    /// environment variables and then `env` on top of the inherited ones
+    fn hugo_command(&self, env: &HashMap<String, String>) -> Result<Command, String> {
+        let mut vars = crate::project_settings::ProjectSettings::load(&self.path)
+            .map(|settings| settings.env)
+            .unwrap_or_default();
+        vars.extend(env.iter().map(|(key, value)| (key.clone(), value.clone())));
+
+        if let Some(key) = vars.keys().find(|key| key.is_empty() || key.contains(['=', '\0'])) {
+            return Err(format!("Invalid environment variable name: '{}'", key));
+        }
+
+        let mut command = Command::new(hugo_binary());
+        command.current_dir(&self.path).envs(&vars);
+        Ok(command)
+    }
+
     /// Run a hugo command (build, clean, deploy, etc.)
-    pub fn run_command(&self, args: &[String]) -> Result<CommandOutput, String> {
-        let output = Command::new("hugo")
+    pub fn run_command(
+        &self,
+        args: &[String],
+        env: &HashMap<String, String>,
+    ) -> Result<CommandOutput, String> {
+        let output = self
+            .hugo_command(env)?
             .args(args)
-            .current_dir(&self.path)
             .output()
-            .map_err(|e| format!("Failed to execute hugo command: {}", e))?;
+            .map_err(|e| match e.kind() {
+                std::io::ErrorKind::NotFound => HUGO_NOT_FOUND_ERROR.to_string(),
+                _ => format!("Failed to execute hugo command: {}", e),
+            })?;
 
         Ok(CommandOutput {
             success: output.status.success(),
@@ -106,56 +186,441 @@ impl HugoProject {
         })
     }
 
+    /// Spawn a hugo command with piped output for streaming
+    pub fn spawn_command(&self, args: &[String], env: &HashMap<String, String>) -> Result<Child, String> {
+        self.hugo_command(env)?
+            .args(args)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| match e.kind() {
+                std::io::ErrorKind::NotFound => HUGO_NOT_FOUND_ERROR.to_string(),
+                _ => format!("Failed to execute hugo command: {}", e),
+            })
+    }
+
     /// Start hugo server in background
-    pub fn start_server(&self) -> Result<String, String> {
+    ///
+    /// Ports below 1024 are rejected unless `allow_privileged_port` is set.
+    pub fn start_server(&self, options: &ServerOptions) -> Result<ServerStart, String> {
         let server_id = self.path.to_string_lossy().to_string();
+        let port = options.port;
+
+        if let Some(port) = port {
+            if port < 1024 && !options.allow_privileged_port {
+                return Err(format!(
+                    "Port {} is a privileged port; use a port between 1024 and 65535",
+                    port
+                ));
+            }
+        }
+        let server_args = options.args()?;
 
         // Check if server is already running
         {
-            let servers = HUGO_SERVERS.lock().unwrap();
-            if servers.contains_key(&server_id) {
-                return Err("Server is already running".to_string());
+            let mut servers = HUGO_SERVERS.lock().unwrap();
+            if is_child_alive(&mut servers, &server_id) {
+                return Err(localized(ErrorCode::ServerAlreadyRunning));
             }
         }
 
         // Start hugo server
-        let child = Command::new("hugo")
-            .arg("server")
-            .current_dir(&self.path)
+        let mut command = self.hugo_command(&options.env)?;
+        command.arg("server").args(server_args);
+
+        let mut child = command
             .stdin(Stdio::null())
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
             .spawn()
-            .map_err(|e| format!("Failed to start hugo server: {}", e))?;
+            .map_err(|e| match e.kind() {
+                std::io::ErrorKind::NotFound => HUGO_NOT_FOUND_ERROR.to_string(),
+                _ => format!("Failed to start hugo server: {}", e),
+            })?;
 
-        // Store the child process
+        let stdout = child.stdout.take();
+        let stderr = child.stderr.take();
+
+        // Store the child process before the log readers start, so the URL
+        // they parse from Hugo's first lines has an entry to land in
+        let info = ServerInfo {
+            id: server_id.clone(),
+            url: None,
+            port: port.unwrap_or(DEFAULT_SERVER_PORT),
+            pid: child.id(),
+            started_at: chrono::Utc::now().timestamp_millis(),
+        };
         {
             let mut servers = HUGO_SERVERS.lock().unwrap();
-            servers.insert(server_id.clone(), child);
+            servers.insert(server_id.clone(), RunningServer { child, info: info.clone() });
+        }
+
+        // Drain the pipes so the server never blocks on a full buffer
+        {
+            let mut logs = HUGO_SERVER_LOGS.lock().unwrap();
+            logs.insert(server_id.clone(), VecDeque::new());
+        }
+        if let Some(stdout) = stdout {
+            spawn_log_reader(server_id.clone(), stdout);
+        }
+        if let Some(stderr) = stderr {
+            spawn_log_reader(server_id.clone(), stderr);
         }
 
-        Ok(server_id)
+        Ok(ServerStart {
+            server_id,
+            port: info.port,
+        })
     }
 
-    /// Stop running hugo server
+    /// Stop running hugo server, asking it to exit before killing it
     pub fn stop_server(server_id: &str) -> Result<(), String> {
+        // Don't hold the lock while waiting for the process to exit
+        let server = HUGO_SERVERS.lock().unwrap().remove(server_id);
+
+        match server {
+            Some(server) => terminate_process(server.child),
+            None => Err("Server not found".to_string()),
+        }
+    }
+
+    /// Stop every running server, e.g. when the app quits. Servers shut down
+    /// in parallel so quitting isn't delayed by one timeout per server.
+    pub fn stop_all_servers() {
+        let servers: Vec<RunningServer> = HUGO_SERVERS
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .drain()
+            .map(|(_, server)| server)
+            .collect();
+
+        let handles: Vec<_> = servers
+            .into_iter()
+            .map(|server| thread::spawn(move || terminate_process(server.child)))
+            .collect();
+        for handle in handles {
+            let _ = handle.join();
+        }
+    }
+
+    /// Kill every running server without waiting, for use from a panic hook.
+    /// Skipped if the lock is held, since it may be held by the panicking thread.
+    pub fn kill_all_servers() {
+        let mut servers = match HUGO_SERVERS.try_lock() {
+            Ok(servers) => servers,
+            Err(TryLockError::Poisoned(e)) => e.into_inner(),
+            Err(TryLockError::WouldBlock) => return,
+        };
+        for (_, mut server) in servers.drain() {
+            let _ = server.child.kill();
+            let _ = server.child.wait();
+        }
+    }
+
+    /// Get the most recent log lines of a server (kept after it stops)
+    pub fn server_logs(server_id: &str) -> Vec<String> {
+        let logs = HUGO_SERVER_LOGS.lock().unwrap();
+        logs.get(server_id)
+            .map(|lines| lines.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Details of the project's running server, if there is one
+    pub fn server_info(&self) -> Option<ServerInfo> {
+        let server_id = self.path.to_string_lossy().to_string();
         let mut servers = HUGO_SERVERS.lock().unwrap();
+        if !is_child_alive(&mut servers, &server_id) {
+            return None;
+        }
+        servers.get(&server_id).map(|server| server.info.clone())
+    }
 
-        if let Some(mut child) = servers.remove(server_id) {
-            child.kill()
-                .map_err(|e| format!("Failed to kill server process: {}", e))?;
-            Ok(())
-        } else {
-            Err("Server not found".to_string())
+    /// Address a running server serves the site on; the reported URL when
+    /// Hugo has logged it, otherwise localhost on the server's port
+    pub fn server_url(server_id: &str) -> Result<String, String> {
+        let mut servers = HUGO_SERVERS.lock().unwrap();
+        if !is_child_alive(&mut servers, server_id) {
+            return Err("Server is not running".to_string());
         }
+        let info = &servers[server_id].info;
+        Ok(info
+            .url
+            .clone()
+            .unwrap_or_else(|| format!("http://localhost:{}/", info.port)))
     }
 
     /// Check if server is running
     pub fn is_server_running(&self) -> bool {
         let server_id = self.path.to_string_lossy().to_string();
-        let servers = HUGO_SERVERS.lock().unwrap();
-        servers.contains_key(&server_id)
+        let mut servers = HUGO_SERVERS.lock().unwrap();
+        is_child_alive(&mut servers, &server_id)
+    }
+}
+
+/// Check whether a tracked server process is still alive, dropping it from
+/// the map if it has exited on its own
+fn is_child_alive(servers: &mut HashMap<String, RunningServer>, server_id: &str) -> bool {
+    let exited = match servers.get_mut(server_id) {
+        Some(server) => !matches!(server.child.try_wait(), Ok(None)),
+        None => return false,
+    };
+
+    if exited {
+        servers.remove(server_id);
+    }
+
+    !exited
+}
+
+/// Ask a process to exit, give it `SERVER_STOP_TIMEOUT` to do so, then kill
+/// it. The process is always reaped so it doesn't linger as a zombie.
+fn terminate_process(mut child: Child) -> Result<(), String> {
+    if request_exit(&child) {
+        let deadline = Instant::now() + SERVER_STOP_TIMEOUT;
+        while Instant::now() < deadline {
+            match child.try_wait() {
+                Ok(Some(_)) => return Ok(()),
+                Ok(None) => thread::sleep(Duration::from_millis(50)),
+                Err(_) => break,
+            }
+        }
+    }
+
+    force_exit(&child);
+    // Fails if the process has already exited, which is fine
+    let _ = child.kill();
+    child
+        .wait()
+        .map(|_| ())
+        .map_err(|e| format!("Failed to stop server process: {}", e))
+}
+
+#[cfg(unix)]
+fn request_exit(child: &Child) -> bool {
+    let Ok(pid) = libc::pid_t::try_from(child.id()) else {
+        return false;
+    };
+    // SAFETY: kill() has no memory-safety preconditions; the pid belongs to
+    // a child we haven't reaped yet, so it can't have been reused
+    unsafe { libc::kill(pid, libc::SIGTERM) == 0 }
+}
+
+/// Ask the whole process tree to close, which also reaches anything Hugo
+/// spawned. Without /F this can fail for console processes; the forced kill
+/// after the timeout covers that.
+#[cfg(windows)]
+fn request_exit(child: &Child) -> bool {
+    taskkill(child, false)
+}
+
+#[cfg(not(any(unix, windows)))]
+fn request_exit(_child: &Child) -> bool {
+    false
+}
+
+/// Kill the process tree of a server that didn't exit in time
+#[cfg(windows)]
+fn force_exit(child: &Child) {
+    taskkill(child, true);
+}
+
+/// `Child::kill` is enough where the server has no process tree to clean up
+#[cfg(not(windows))]
+fn force_exit(_child: &Child) {}
+
+#[cfg(windows)]
+fn taskkill(child: &Child, force: bool) -> bool {
+    let pid = child.id().to_string();
+    let mut args = vec!["/PID", pid.as_str(), "/T"];
+    if force {
+        args.push("/F");
+    }
+    Command::new("taskkill")
+        .args(args)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .is_ok_and(|status| status.success())
+}
+
+/// Hugo executable to run: the configured path, or `hugo` from PATH
+pub fn hugo_binary() -> String {
+    crate::config::AppConfig::load()
+        .ok()
+        .and_then(|config| config.hugo_binary_path)
+        .map(|path| path.trim().to_string())
+        .filter(|path| !path.is_empty())
+        .unwrap_or_else(|| "hugo".to_string())
+}
+
+/// Probe the configured path, PATH and common install locations for a
+/// working Hugo executable
+pub fn detect_hugo_binary() -> Result<HugoBinary, String> {
+    let executable = if cfg!(windows) { "hugo.exe" } else { "hugo" };
+    let mut candidates: Vec<PathBuf> = Vec::new();
+
+    if let Some(configured) = crate::config::AppConfig::load()
+        .ok()
+        .and_then(|config| config.hugo_binary_path)
+        .filter(|path| !path.trim().is_empty())
+    {
+        candidates.push(PathBuf::from(configured.trim()));
     }
+
+    if let Some(path_var) = std::env::var_os("PATH") {
+        candidates.extend(std::env::split_paths(&path_var).map(|dir| dir.join(executable)));
+    }
+
+    if cfg!(windows) {
+        if let Some(program_data) = std::env::var_os("ProgramData") {
+            candidates.push(Path::new(&program_data).join("chocolatey").join("bin").join(executable));
+        }
+        if let Some(local_app_data) = dirs::data_local_dir() {
+            candidates.push(local_app_data.join("Microsoft").join("WinGet").join("Links").join(executable));
+        }
+        if let Some(home) = dirs::home_dir() {
+            candidates.push(home.join("scoop").join("shims").join(executable));
+        }
+    } else {
+        for dir in ["/opt/homebrew/bin", "/usr/local/bin", "/usr/bin", "/snap/bin"] {
+            candidates.push(Path::new(dir).join(executable));
+        }
+    }
+    if let Some(home) = dirs::home_dir() {
+        candidates.push(home.join("go").join("bin").join(executable));
+    }
+
+    for candidate in candidates {
+        if !candidate.is_file() {
+            continue;
+        }
+        let Ok(output) = Command::new(&candidate).arg("version").output() else {
+            continue;
+        };
+        if output.status.success() {
+            return Ok(HugoBinary {
+                path: candidate.to_string_lossy().to_string(),
+                version: String::from_utf8_lossy(&output.stdout).trim().to_string(),
+            });
+        }
+    }
+
+    Err(HUGO_NOT_FOUND_ERROR.to_string())
+}
+
+/// Parse `hugo version` output, e.g.
+/// `hugo v0.121.1-00b46fed+extended darwin/arm64 BuildDate=...`
+pub fn parse_hugo_version(output: &str) -> Option<HugoVersion> {
+    let tokens: Vec<&str> = output.split_whitespace().collect();
+    let version_index = tokens.iter().position(|token| {
+        let mut chars = token.chars();
+        chars.next() == Some('v') && chars.next().is_some_and(|c| c.is_ascii_digit())
+    })?;
+    let version_token = tokens[version_index];
+
+    let version = version_token[1..]
+        .split(['-', '+', '/'])
+        .next()
+        .unwrap_or("")
+        .to_string();
+    let extended = version_token.contains("extended");
+
+    let (os, arch) = tokens[version_index + 1..]
+        .iter()
+        .find_map(|token| token.split_once('/'))
+        .map(|(os, arch)| (os.to_string(), arch.to_string()))
+        .unwrap_or_default();
+
+    Some(HugoVersion {
+        version,
+        extended,
+        os,
+        arch,
+        raw: output.trim().to_string(),
+    })
+}
+
+/// Feed each output line of a spawned command to `on_line` as it arrives,
+/// then wait for the process and return its exit code
+pub fn stream_output<F>(mut child: Child, on_line: F) -> i32
+where
+    F: Fn(OutputStream, String) + Send + Sync + 'static,
+{
+    let on_line = Arc::new(on_line);
+
+    let stderr_reader = child.stderr.take().map(|stderr| {
+        let on_line = Arc::clone(&on_line);
+        thread::spawn(move || {
+            for line in BufReader::new(stderr).lines().map_while(Result::ok) {
+                on_line(OutputStream::Stderr, line);
+            }
+        })
+    });
+
+    if let Some(stdout) = child.stdout.take() {
+        for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+            on_line(OutputStream::Stdout, line);
+        }
+    }
+
+    if let Some(reader) = stderr_reader {
+        let _ = reader.join();
+    }
+
+    child
+        .wait()
+        .ok()
+        .and_then(|status| status.code())
+        .unwrap_or(-1)
+}
+
+fn spawn_log_reader<R: Read + Send + 'static>(server_id: String, reader: R) {
+    thread::spawn(move || {
+        for line in BufReader::new(reader).lines() {
+            let Ok(line) = line else { break };
+            if let Some(url) = parse_server_url(&line) {
+                record_server_url(&server_id, url);
+            }
+            let mut logs = HUGO_SERVER_LOGS.lock().unwrap();
+            let buffer = logs.entry(server_id.clone()).or_default();
+            if buffer.len() >= MAX_SERVER_LOG_LINES {
+                buffer.pop_front();
+            }
+            buffer.push_back(line);
+        }
+    });
+}
+
+/// URL from Hugo's "Web Server is available at http://localhost:1313/ (...)" line
+fn parse_server_url(line: &str) -> Option<&str> {
+    let (_, rest) = line.split_once("Web Server is available at ")?;
+    rest.split_whitespace().next()
+}
+
+/// Store the reported URL, and the port from it since Hugo picks another one
+/// when the requested port is busy
+fn record_server_url(server_id: &str, url: &str) {
+    let mut servers = HUGO_SERVERS.lock().unwrap();
+    if let Some(server) = servers.get_mut(server_id) {
+        server.info.url = Some(url.to_string());
+        let port = url
+            .split("://")
+            .nth(1)
+            .and_then(|rest| rest.split('/').next())
+            .and_then(|host| host.rsplit_once(':'))
+            .and_then(|(_, port)| port.parse().ok());
+        if let Some(port) = port {
+            server.info.port = port;
+        }
+    }
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputStream {
+    Stdout,
+    Stderr,
 }
 
 #[derive(serde::Serialize, serde::Deserialize)]
@@ -165,3 +630,192 @@ pub struct CommandOutput {
     pub stderr: String,
     pub exit_code: i32,
 }
+
+/// How `hugo server` is started; every field is optional from the frontend
+#[derive(Debug, Default, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ServerOptions {
+    pub port: Option<u16>,
+    pub allow_privileged_port: bool,
+    pub env: HashMap<String, String>,
+    /// `--baseURL`; must be an http(s) URL
+    pub base_url: Option<String>,
+    /// `--bind`, the interface to listen on (Hugo defaults to 127.0.0.1)
+    pub bind: Option<String>,
+    /// `--navigateToChanged`
+    pub navigate_to_changed: bool,
+    /// `--disableFastRender`
+    pub disable_fast_render: bool,
+    /// `-D`: render drafts. Off by default, as in Hugo.
+    pub include_drafts: bool,
+    /// `-F`: render content dated in the future. Off by default, as in Hugo.
+    pub include_future: bool,
+    /// `-E`: render expired content. Off by default, as in Hugo.
+    pub include_expired: bool,
+}
+
+impl ServerOptions {
+    /// Arguments after `hugo server`, validating the values first
+    fn args(&self) -> Result<Vec<String>, String> {
+        let mut args = Vec::new();
+
+        if let Some(port) = self.port {
+            args.extend(["--port".to_string(), port.to_string()]);
+        }
+        if let Some(base_url) = self.base_url.as_deref().map(str::trim).filter(|u| !u.is_empty()) {
+            validate_base_url(base_url)?;
+            args.extend(["--baseURL".to_string(), base_url.to_string()]);
+        }
+        if let Some(bind) = self.bind.as_deref().map(str::trim).filter(|b| !b.is_empty()) {
+            if bind != "localhost" && bind.parse::<std::net::IpAddr>().is_err() {
+                return Err(format!("Invalid bind address '{}'; use an IP address", bind));
+            }
+            args.extend(["--bind".to_string(), bind.to_string()]);
+        }
+        if self.navigate_to_changed {
+            args.push("--navigateToChanged".to_string());
+        }
+        if self.disable_fast_render {
+            args.push("--disableFastRender".to_string());
+        }
+        for (enabled, flag) in [
+            (self.include_drafts, "-D"),
+            (self.include_future, "-F"),
+            (self.include_expired, "-E"),
+        ] {
+            if enabled {
+                args.push(flag.to_string());
+            }
+        }
+
+        Ok(args)
+    }
+}
+
+/// Accept `http(s)://host[:port][/path]`, which is what `--baseURL` needs
+fn validate_base_url(url: &str) -> Result<(), String> {
+    let invalid = || format!("Invalid base URL '{}'; expected e.g. http://localhost:1313/", url);
+
+    let rest = url
+        .strip_prefix("http://")
+        .or_else(|| url.strip_prefix("https://"))
+        .ok_or_else(invalid)?;
+    let host = rest.split(['/', '?', '#']).next().unwrap_or("");
+    if host.is_empty() || url.chars().any(char::is_whitespace) {
+        return Err(invalid());
+    }
+    if let Some((_, port)) = host.rsplit_once(':').filter(|_| !host.ends_with(']')) {
+        port.parse::<u16>().map_err(|_| invalid())?;
+    }
+    Ok(())
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServerStart {
+    pub server_id: String,
+    pub port: u16,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ServerInfo {
+    pub id: String,
+    /// Address Hugo serves on, known once it has logged it
+    pub url: Option<String>,
+    pub port: u16,
+    pub pid: u32,
+    /// Milliseconds since the epoch
+    pub started_at: i64,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HugoVersion {
+    pub version: String,
+    pub extended: bool,
+    pub os: String,
+    pub arch: String,
+    pub raw: String,
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HugoBinary {
+    pub path: String,
+    pub version: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_hugo_version, parse_server_url, terminate_process, ServerOptions, SERVER_STOP_TIMEOUT};
+
+    #[cfg(unix)]
+    #[test]
+    fn terminate_process_stops_child_without_waiting_for_timeout() {
+        let child = std::process::Command::new("sleep").arg("30").spawn().unwrap();
+        let started = std::time::Instant::now();
+        terminate_process(child).unwrap();
+        // `sleep` exits on SIGTERM, so the kill fallback isn't needed
+        assert!(started.elapsed() < SERVER_STOP_TIMEOUT);
+    }
+
+    #[test]
+    fn parse_server_url_from_log_line() {
+        assert_eq!(
+            parse_server_url("Web Server is available at http://localhost:1314/ (bind address 127.0.0.1)"),
+            Some("http://localhost:1314/")
+        );
+        assert_eq!(parse_server_url("Built in 12 ms"), None);
+    }
+
+    #[test]
+    fn server_options_build_flags_and_validate() {
+        let options = ServerOptions {
+            port: Some(1414),
+            base_url: Some("http://192.168.1.5:1414/blog/".to_string()),
+            bind: Some("0.0.0.0".to_string()),
+            disable_fast_render: true,
+            include_drafts: true,
+            include_expired: true,
+            ..ServerOptions::default()
+        };
+        assert_eq!(
+            options.args().unwrap(),
+            vec![
+                "--port", "1414", "--baseURL", "http://192.168.1.5:1414/blog/", "--bind", "0.0.0.0",
+                "--disableFastRender", "-D", "-E",
+            ]
+        );
+        assert!(ServerOptions::default().args().unwrap().is_empty());
+
+        for base_url in ["example.com", "ftp://example.com", "http://", "http://host:99999/", "https://a b/"] {
+            let options = ServerOptions { base_url: Some(base_url.to_string()), ..ServerOptions::default() };
+            assert!(options.args().is_err(), "{} should be rejected", base_url);
+        }
+        let options = ServerOptions { bind: Some("my host".to_string()), ..ServerOptions::default() };
+        assert!(options.args().is_err());
+    }
+
+    #[test]
+    fn parse_modern_extended_version() {
+        let output = "hugo v0.121.1-00b46fed8e47f7bb0a85d7cfc2d9f1356379b740+extended darwin/arm64 BuildDate=2023-12-08T08:47:45Z VendorInfo=brew\n";
+        let version = parse_hugo_version(output).expect("parse failed");
+
+        assert_eq!(version.version, "0.121.1");
+        assert!(version.extended);
+        assert_eq!(version.os, "darwin");
+        assert_eq!(version.arch, "arm64");
+    }
+
+    #[test]
+    fn parse_legacy_standard_version() {
+        let output = "Hugo Static Site Generator v0.80.0-792EF0F4 linux/amd64 BuildDate: 2020-12-31T13:37:57Z";
+        let version = parse_hugo_version(output).expect("parse failed");
+
+        assert_eq!(version.version, "0.80.0");
+        assert!(!version.extended);
+        assert_eq!(version.os, "linux");
+        assert_eq!(version.arch, "amd64");
+    }
+}
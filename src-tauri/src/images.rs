@@ -0,0 +1,284 @@
+// Image processing for media copied into the project
+
+use image::codecs::jpeg::JpegEncoder;
+use image::imageops::FilterType;
+use image::metadata::Orientation;
+use image::{DynamicImage, GenericImageView, ImageDecoder, ImageFormat, ImageReader, RgbImage};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::io::{Cursor, Read};
+use std::path::{Path, PathBuf};
+
+/// JPEG quality used when resizing without an explicit quality
+const DEFAULT_JPEG_QUALITY: u8 = 85;
+
+/// JPEG quality of media browser thumbnails
+const THUMBNAIL_QUALITY: u8 = 75;
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ResizeOptions {
+    pub max_width: Option<u32>,
+    pub max_height: Option<u32>,
+    /// JPEG quality (1-100); PNG and WebP are always re-encoded losslessly
+    pub quality: Option<u8>,
+}
+
+impl ResizeOptions {
+    fn is_empty(&self) -> bool {
+        self.max_width.is_none() && self.max_height.is_none() && self.quality.is_none()
+    }
+
+    fn exceeds_bounds(&self, width: u32, height: u32) -> bool {
+        self.max_width.is_some_and(|max| width > max)
+            || self.max_height.is_some_and(|max| height > max)
+    }
+}
+
+/// Encodable raster format for an extension, if we know how to re-encode it
+fn raster_format(path: &Path) -> Option<ImageFormat> {
+    let ext = path.extension().and_then(|s| s.to_str())?.to_lowercase();
+    match ext.as_str() {
+        "jpg" | "jpeg" => Some(ImageFormat::Jpeg),
+        "png" => Some(ImageFormat::Png),
+        "webp" => Some(ImageFormat::WebP),
+        _ => None,
+    }
+}
+
+/// Resize and/or re-encode `source` into `dest` when the options call for it.
+///
+/// Returns `Ok(false)` without writing anything when the image should be
+/// copied verbatim: SVG, GIF and other formats we don't re-encode, images
+/// already within bounds, or no options at all.
+pub fn optimize_image(source: &Path, dest: &Path, options: &ResizeOptions) -> Result<bool, String> {
+    if options.is_empty() {
+        return Ok(false);
+    }
+    let Some(format) = raster_format(source) else {
+        return Ok(false);
+    };
+    if let Some(quality) = options.quality {
+        if !(1..=100).contains(&quality) {
+            return Err("Quality must be between 1 and 100".to_string());
+        }
+    }
+
+    let (decoder, orientation) = open_decoder(source)?;
+    let (width, height) = upright_dimensions(decoder.dimensions(), orientation);
+    let needs_resize = options.exceeds_bounds(width, height);
+    let reencode_jpeg = format == ImageFormat::Jpeg && options.quality.is_some();
+    if !needs_resize && !reencode_jpeg {
+        return Ok(false);
+    }
+
+    let mut image = decode_upright(decoder, orientation)?;
+    if needs_resize {
+        image = image.resize(
+            options.max_width.unwrap_or(u32::MAX),
+            options.max_height.unwrap_or(u32::MAX),
+            FilterType::Lanczos3,
+        );
+    }
+
+    write_image(
+        &image,
+        dest,
+        format,
+        options.quality.unwrap_or(DEFAULT_JPEG_QUALITY),
+    )?;
+
+    Ok(true)
+}
+
+/// Format of an image file going by its content (magic bytes), regardless of
+/// its extension. `None` for formats the image crate doesn't recognize,
+/// which includes SVG.
+pub fn sniff_format(path: &Path) -> Result<Option<ImageFormat>, String> {
+    let mut header = Vec::with_capacity(64);
+    fs::File::open(path)
+        .and_then(|file| file.take(64).read_to_end(&mut header))
+        .map_err(|e| format!("Failed to read image: {}", e))?;
+    Ok(image::guess_format(&header).ok())
+}
+
+/// Format an extension promises, e.g. `Jpeg` for both `.jpg` and `.jpeg`
+pub fn extension_format(path: &Path) -> Option<ImageFormat> {
+    path.extension().and_then(ImageFormat::from_extension)
+}
+
+/// Where thumbnails are cached: the user's cache directory, which keeps them
+/// out of the project and its repository
+pub fn thumbnails_dir() -> PathBuf {
+    dirs::cache_dir()
+        .unwrap_or_else(std::env::temp_dir)
+        .join("hugo-bros")
+        .join("thumbnails")
+}
+
+/// Cached thumbnail of `source` fitting in `max_edge` pixels, created on
+/// first use in `thumbnails_dir`. The cache key hashes the source path and
+/// includes the file's mtime, so edited images get a fresh thumbnail.
+/// Returns `None` when the original should be shown instead: SVGs and images
+/// that already fit.
+pub fn thumbnail(thumbnails_dir: &Path, source: &Path, max_edge: u32) -> Result<Option<PathBuf>, String> {
+    if max_edge == 0 {
+        return Err("Thumbnail size must be greater than 0".to_string());
+    }
+    let is_svg = source
+        .extension()
+        .and_then(|s| s.to_str())
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("svg"));
+    if is_svg {
+        return Ok(None);
+    }
+
+    let (width, height) =
+        image::image_dimensions(source).map_err(|e| format!("Failed to read image: {}", e))?;
+    if width <= max_edge && height <= max_edge {
+        return Ok(None);
+    }
+
+    let modified = fs::metadata(source)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+        .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    // A stable hash, so keys survive app updates
+    let source_key: String = Sha256::digest(source.to_string_lossy().as_bytes())[..16]
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect();
+
+    let dest = thumbnails_dir.join(format!("{}-{}-{}.jpg", source_key, modified, max_edge));
+    if dest.exists() {
+        return Ok(Some(dest));
+    }
+
+    fs::create_dir_all(thumbnails_dir)
+        .map_err(|e| format!("Failed to create thumbnails directory: {}", e))?;
+    // Drop thumbnails of earlier versions of the image
+    if let Ok(entries) = fs::read_dir(thumbnails_dir) {
+        for entry in entries.filter_map(|e| e.ok()) {
+            let name = entry.file_name();
+            let is_stale = name.to_str().is_some_and(|name| {
+                name.starts_with(&format!("{}-", source_key)) && !name.starts_with(&format!("{}-{}-", source_key, modified))
+            });
+            if is_stale {
+                let _ = fs::remove_file(entry.path());
+            }
+        }
+    }
+
+    let (decoder, orientation) = open_decoder(source)?;
+    let image = decode_upright(decoder, orientation)?;
+    let image = DynamicImage::ImageRgb8(flatten_on_white(&image.thumbnail(max_edge, max_edge)));
+
+    write_image(&image, &dest, ImageFormat::Jpeg, THUMBNAIL_QUALITY)?;
+
+    Ok(Some(dest))
+}
+
+/// Decoder for an image along with its EXIF orientation. Re-encoded images
+/// don't keep EXIF, so the rotation has to be applied to the pixels.
+fn open_decoder(source: &Path) -> Result<(impl ImageDecoder, Orientation), String> {
+    let mut decoder = ImageReader::open(source)
+        .and_then(|reader| reader.with_guessed_format())
+        .map_err(|e| format!("Failed to read image: {}", e))?
+        .into_decoder()
+        .map_err(|e| format!("Failed to read image: {}", e))?;
+    let orientation = decoder.orientation().unwrap_or(Orientation::NoTransforms);
+    Ok((decoder, orientation))
+}
+
+fn decode_upright(decoder: impl ImageDecoder, orientation: Orientation) -> Result<DynamicImage, String> {
+    let mut image =
+        DynamicImage::from_decoder(decoder).map_err(|e| format!("Failed to decode image: {}", e))?;
+    image.apply_orientation(orientation);
+    Ok(image)
+}
+
+/// Size as displayed: orientations that turn the image by 90° swap the sides
+fn upright_dimensions((width, height): (u32, u32), orientation: Orientation) -> (u32, u32) {
+    match orientation {
+        Orientation::Rotate90
+        | Orientation::Rotate270
+        | Orientation::Rotate90FlipH
+        | Orientation::Rotate270FlipH => (height, width),
+        _ => (width, height),
+    }
+}
+
+/// Composite transparent pixels onto white, since JPEG has no alpha channel
+fn flatten_on_white(image: &DynamicImage) -> RgbImage {
+    if !image.color().has_alpha() {
+        return image.to_rgb8();
+    }
+    let (width, height) = image.dimensions();
+    let rgba = image.to_rgba8();
+    RgbImage::from_fn(width, height, |x, y| {
+        let [r, g, b, a] = rgba.get_pixel(x, y).0;
+        let blend = |c: u8| ((c as u32 * a as u32 + 255 * (255 - a as u32)) / 255) as u8;
+        image::Rgb([blend(r), blend(g), blend(b)])
+    })
+}
+
+fn write_image(
+    image: &DynamicImage,
+    dest: &Path,
+    format: ImageFormat,
+    quality: u8,
+) -> Result<(), String> {
+    // Encode in memory first, so a failed encode never leaves a truncated file
+    let mut encoded = Cursor::new(Vec::new());
+    let result = match format {
+        // JPEG has no alpha channel
+        ImageFormat::Jpeg => JpegEncoder::new_with_quality(&mut encoded, quality)
+            .encode_image(&DynamicImage::ImageRgb8(image.to_rgb8())),
+        _ => image.write_to(&mut encoded, format),
+    };
+    result.map_err(|e| format!("Failed to encode image: {}", e))?;
+
+    crate::files::write_file_atomic(dest, encoded.get_ref())
+        .map_err(|e| format!("Failed to write image file: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{ExtendedColorType, ImageEncoder, Rgb};
+
+    /// Minimal big-endian EXIF block holding only an orientation tag
+    fn exif_with_orientation(value: u8) -> Vec<u8> {
+        let mut exif = b"MM\0\x2a\0\0\0\x08\0\x01".to_vec();
+        exif.extend_from_slice(&[0x01, 0x12, 0, 3, 0, 0, 0, 1, 0, value, 0, 0]);
+        exif.extend_from_slice(&[0, 0, 0, 0]);
+        exif
+    }
+
+    #[test]
+    fn optimize_image_applies_exif_orientation() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = dir.path().join("photo.jpg");
+        let dest = dir.path().join("resized.jpg");
+
+        // Left half red, right half blue, flagged "rotate 90° clockwise"
+        let pixels = RgbImage::from_fn(40, 20, |x, _| if x < 20 { Rgb([255, 0, 0]) } else { Rgb([0, 0, 255]) });
+        let mut encoder = JpegEncoder::new_with_quality(fs::File::create(&source).unwrap(), 95);
+        encoder.set_exif_metadata(exif_with_orientation(6)).unwrap();
+        encoder.write_image(&pixels, 40, 20, ExtendedColorType::Rgb8).unwrap();
+
+        let options = ResizeOptions {
+            max_width: Some(15),
+            ..ResizeOptions::default()
+        };
+        assert!(optimize_image(&source, &dest, &options).unwrap());
+
+        let resized = image::open(&dest).unwrap().to_rgb8();
+        assert_eq!(resized.dimensions(), (15, 30));
+        let top = resized.get_pixel(7, 3).0;
+        let bottom = resized.get_pixel(7, 26).0;
+        assert!(top[0] > 200 && top[2] < 60, "top should be red: {:?}", top);
+        assert!(bottom[2] > 200 && bottom[0] < 60, "bottom should be blue: {:?}", bottom);
+    }
+}
@@ -1,34 +1,88 @@
 // Hugo Bros - Tauri Backend
 
+mod backups;
 mod commands;
 mod config;
+mod content_cache;
+mod errors;
 mod files;
 mod frontmatter_config;
+mod git;
 mod hugo;
+mod images;
 mod markdown;
+mod preview;
+mod project_settings;
+mod templates;
+mod trash;
+mod watcher;
 
 use commands::*;
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
+    // Don't leave `hugo server` processes holding ports if the app crashes
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        if std::thread::current().name() == Some("main") {
+            hugo::HugoProject::kill_all_servers();
+        }
+        default_hook(info);
+    }));
+
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_dialog::init())
         .invoke_handler(tauri::generate_handler![
             select_project_folder,
             get_project_config,
+            reveal_in_file_manager,
+            get_project_settings,
+            save_project_settings,
+            set_posts_section,
+            validate_project_config,
+            save_project_config,
+            get_params,
+            get_menus,
             get_frontmatter_config,
             generate_frontmatter_config_command,
+            regenerate_frontmatter_config,
+            preview_frontmatter_config,
+            save_frontmatter_config,
+            validate_frontmatter,
             list_posts,
             get_post,
             save_post,
             create_post,
+            create_post_with_frontmatter,
             delete_post,
+            rename_post,
+            move_post,
+            toggle_draft,
+            publish_draft,
+            batch_update_frontmatter,
+            unpublish_post,
+            create_draft_from_post,
+            list_templates,
+            save_template,
+            create_post_from_template,
+            git_status,
+            git_commit,
+            diff_post,
+            get_content_hash,
+            list_posts_metadata,
+            get_related_posts,
+            get_image_usage,
+            get_image_thumbnail,
+            copy_resource_to_bundle,
             list_pages,
             create_page,
             get_page,
             save_page,
             delete_page,
+            list_sections,
+            get_section,
+            save_section,
             list_drafts,
             create_draft,
             get_draft,
@@ -36,17 +90,61 @@ pub fn run() {
             delete_draft,
             list_images,
             list_static_entries,
+            list_static_tree,
             create_static_folder,
             delete_static_entry,
+            move_static_entry,
             copy_image_to_project,
+            copy_images_to_project,
+            verify_image,
+            fix_image_extension,
             delete_image,
+            check_preview_images,
+            resolve_image_field,
+            check_links,
+            render_markdown_to_html,
+            list_shortcodes_used,
+            list_translations,
+            list_tags,
+            list_categories,
+            get_content_stats,
+            export_content_index,
+            rename_tag,
+            rename_category,
+            list_trash,
+            restore_trash_entry,
+            list_backups,
+            restore_backup,
             get_app_config,
             save_app_config,
+            get_app_config_path,
+            reset_app_config,
+            remove_recent_project,
+            clear_recent_projects,
+            prune_recent_projects,
+            pin_project,
+            unpin_project,
             run_hugo_command,
+            run_hugo_command_streaming,
+            import_markdown_files,
+            start_watching_project,
+            stop_watching_project,
+            invalidate_content_cache,
             start_hugo_server,
+            set_hugo_env_defaults,
             stop_hugo_server,
+            open_preview,
+            get_hugo_server_logs,
+            get_hugo_server_info,
             is_hugo_server_running,
+            detect_hugo_binary,
+            get_hugo_version,
         ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|_app, event| {
+            if let tauri::RunEvent::ExitRequested { .. } | tauri::RunEvent::Exit = event {
+                hugo::HugoProject::stop_all_servers();
+            }
+        });
 }
@@ -1,8 +1,9 @@
 // Markdown and frontmatter parsing
 
 use crate::files;
+use regex::Regex;
+use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
 use std::fs;
 use std::path::Path;
 
@@ -19,11 +20,64 @@ pub struct Frontmatter {
   pub comments: Option<bool>,
   pub layout: Option<String>,
   pub permalink: Option<String>,
+  /// Hugo `slug`: overrides the last segment of the URL
+  #[serde(default)]
+  pub slug: Option<String>,
+  /// Hugo `aliases`: old URLs that redirect to this page
+  #[serde(default)]
+  pub aliases: Vec<String>,
   pub description: Option<String>,
+  /// `None` means the file has no `draft` key, which is not the same as an
+  /// explicit `draft: false`; both are published. See `mark_published`.
   #[serde(skip_serializing_if = "Option::is_none")]
   pub draft: Option<bool>,
+  /// Hugo `weight`: lower weights sort first; unset or 0 sorts last
+  #[serde(default)]
+  pub weight: Option<i64>,
+  /// Hugo `publishDate`: not rendered before this date
   #[serde(default)]
-  pub custom_fields: HashMap<String, serde_yaml::Value>,
+  pub publish_date: Option<String>,
+  /// Hugo `expiryDate`: not rendered after this date
+  #[serde(default)]
+  pub expiry_date: Option<String>,
+  #[serde(default)]
+  pub custom_fields: IndexMap<String, serde_yaml::Value>,
+}
+
+impl Frontmatter {
+    /// Clear the draft flag. A file that spells out `draft` keeps the key as
+    /// `draft: false`; one without it stays without it.
+    pub fn mark_published(&mut self, layout: &DocumentLayout) {
+        self.draft = if layout.key_order.iter().any(|key| key == "draft") {
+            Some(false)
+        } else {
+            None
+        };
+    }
+}
+
+/// How a file was written beyond its frontmatter values, so saves reproduce
+/// it. Content created from scratch uses the default layout.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct DocumentLayout {
+    /// Frontmatter keys in the order they appeared in the file, so saves
+    /// don't reshuffle them
+    #[serde(default)]
+    pub key_order: Vec<String>,
+    /// HTML comments before the opening delimiter, which Hugo allows; written
+    /// back unchanged
+    #[serde(default)]
+    pub preamble: Option<String>,
+    /// Line ending the file used; saves keep it so files edited on Windows
+    /// don't show every line as changed
+    #[serde(default)]
+    pub line_ending: LineEnding,
+    /// Key paths of values that were native TOML datetimes
+    /// (`date = 2024-01-01`, or `seen` in `[params]`). They are read as
+    /// strings and written back unquoted.
+    #[serde(default)]
+    pub toml_datetimes: Vec<Vec<String>>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -44,12 +98,22 @@ struct FrontmatterYaml {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub permalink: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub slug: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub aliases: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub draft: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub weight: Option<i64>,
+    #[serde(rename = "publishDate", alias = "publishdate", skip_serializing_if = "Option::is_none")]
+    pub publish_date: Option<String>,
+    #[serde(rename = "expiryDate", alias = "expirydate", skip_serializing_if = "Option::is_none")]
+    pub expiry_date: Option<String>,
     #[serde(flatten)]
     #[serde(default)]
-    pub custom_fields: HashMap<String, serde_yaml::Value>,
+    pub custom_fields: IndexMap<String, serde_yaml::Value>,
 }
 
 impl From<FrontmatterYaml> for Frontmatter {
@@ -63,8 +127,13 @@ impl From<FrontmatterYaml> for Frontmatter {
             comments: frontmatter.comments,
             layout: frontmatter.layout,
             permalink: frontmatter.permalink,
+            slug: frontmatter.slug,
+            aliases: frontmatter.aliases,
             description: frontmatter.description,
             draft: frontmatter.draft,
+            weight: frontmatter.weight,
+            publish_date: frontmatter.publish_date,
+            expiry_date: frontmatter.expiry_date,
             custom_fields: frontmatter.custom_fields,
         }
     }
@@ -81,79 +150,302 @@ impl From<Frontmatter> for FrontmatterYaml {
             comments: frontmatter.comments,
             layout: frontmatter.layout,
             permalink: frontmatter.permalink,
+            slug: frontmatter.slug,
+            aliases: frontmatter.aliases,
             description: frontmatter.description,
             draft: frontmatter.draft,
+            weight: frontmatter.weight,
+            publish_date: frontmatter.publish_date,
+            expiry_date: frontmatter.expiry_date,
             custom_fields: frontmatter.custom_fields,
         }
     }
 }
 
-pub fn frontmatter_to_yaml(frontmatter: &Frontmatter) -> Result<String, String> {
-    serde_yaml::to_string(&FrontmatterYaml::from(frontmatter.clone()))
+/// Frontmatter syntax a document was written in, kept so saves don't
+/// silently convert the file to another format
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum FrontmatterFormat {
+    #[default]
+    Yaml,
+    Toml,
+    Json,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum LineEnding {
+    #[default]
+    Lf,
+    Crlf,
+}
+
+impl LineEnding {
+    /// The ending most lines of `text` use
+    pub fn detect(text: &str) -> Self {
+        let crlf = text.matches("\r\n").count();
+        let lf = text.matches('\n').count() - crlf;
+        if crlf > lf {
+            LineEnding::Crlf
+        } else {
+            LineEnding::Lf
+        }
+    }
+
+    /// `text` with every line break converted to this ending
+    pub fn apply(self, text: &str) -> String {
+        match self {
+            LineEnding::Lf => text.to_string(),
+            LineEnding::Crlf => text.replace("\r\n", "\n").replace('\n', "\r\n"),
+        }
+    }
+}
+
+/// Frontmatter as a mapping with keys in their original order. Keys missing
+/// from `key_order` (new fields) follow: known fields in declaration order
+/// (title, date, tags, categories, updated, comments, layout, permalink,
+/// slug, aliases, description, draft, weight, publishDate, expiryDate), then custom fields in the order they were added.
+/// Empty tags/categories are only written if the file already had them.
+fn ordered_frontmatter(frontmatter: &Frontmatter, key_order: &[String]) -> Result<serde_yaml::Mapping, String> {
+    let value = serde_yaml::to_value(FrontmatterYaml::from(frontmatter.clone()))
+        .map_err(|e| format!("Failed to serialize frontmatter: {}", e))?;
+    let serde_yaml::Value::Mapping(mapping) = value else {
+        return Err("Failed to serialize frontmatter: not a mapping".to_string());
+    };
+
+    let mut ordered = serde_yaml::Mapping::new();
+    for key in key_order {
+        if let Some(value) = mapping.get(key.as_str()) {
+            ordered.insert(key.as_str().into(), value.clone());
+        }
+    }
+    for (key, value) in mapping {
+        if ordered.contains_key(&key) {
+            continue;
+        }
+        // Don't add empty tags/categories to a file that never had them
+        let is_empty_list = value.as_sequence().is_some_and(|items| items.is_empty());
+        if !key_order.is_empty()
+            && is_empty_list
+            && matches!(key.as_str(), Some("tags" | "categories"))
+        {
+            continue;
+        }
+        ordered.insert(key, value);
+    }
+
+    Ok(ordered)
+}
+
+/// Edit frontmatter as a key/value mapping, for changes that aren't tied to
+/// a particular field. Keys keep their order; new keys are added at the end
+/// of `layout.key_order`.
+pub fn edit_frontmatter<F>(frontmatter: &Frontmatter, layout: &mut DocumentLayout, edit: F) -> Result<Frontmatter, String>
+where
+    F: FnOnce(&mut serde_yaml::Mapping) -> Result<(), String>,
+{
+    let mut mapping = ordered_frontmatter(frontmatter, &layout.key_order)?;
+    edit(&mut mapping)?;
+
+    let key_order = mapping
+        .keys()
+        .filter_map(|k| k.as_str().map(str::to_string))
+        .collect();
+    let edited: FrontmatterYaml = serde_yaml::from_value(serde_yaml::Value::Mapping(mapping))
+        .map_err(|e| format!("Invalid frontmatter: {}", e))?;
+
+    layout.key_order = key_order;
+    Ok(Frontmatter::from(edited))
+}
+
+/// YAML frontmatter with keys in `key_order`; see `ordered_frontmatter`
+pub fn frontmatter_to_yaml(frontmatter: &Frontmatter, key_order: &[String]) -> Result<String, String> {
+    serde_yaml::to_string(&ordered_frontmatter(frontmatter, key_order)?)
         .map_err(|e| format!("Failed to serialize frontmatter: {}", e))
 }
 
+pub fn frontmatter_to_toml(frontmatter: &Frontmatter, layout: &DocumentLayout) -> Result<String, String> {
+    let mut mapping = serde_yaml::Value::Mapping(ordered_frontmatter(frontmatter, &layout.key_order)?);
+    // TOML has no null, so drop empty values instead of failing
+    strip_nulls(&mut mapping);
+    let mut toml_value = toml::Value::try_from(mapping)
+        .map_err(|e| format!("Failed to serialize frontmatter: {}", e))?;
+    // Values that were datetimes go back unquoted, unless edited into
+    // something that no longer is one
+    for path in &layout.toml_datetimes {
+        let value = path
+            .iter()
+            .try_fold(&mut toml_value, |value, key| value.as_table_mut()?.get_mut(key));
+        if let Some(value) = value {
+            if let Some(datetime) = value.as_str().and_then(|text| text.parse().ok()) {
+                *value = toml::Value::Datetime(datetime);
+            }
+        }
+    }
+    toml::to_string(&toml_value)
+        .map_err(|e| format!("Failed to serialize frontmatter: {}", e))
+}
+
+pub fn frontmatter_to_json(frontmatter: &Frontmatter, key_order: &[String]) -> Result<String, String> {
+    serde_json::to_string_pretty(&ordered_frontmatter(frontmatter, key_order)?)
+        .map_err(|e| format!("Failed to serialize frontmatter: {}", e))
+}
+
+fn strip_nulls(value: &mut serde_yaml::Value) {
+    match value {
+        serde_yaml::Value::Mapping(map) => {
+            map.retain(|_, v| !v.is_null());
+            map.values_mut().for_each(strip_nulls);
+        }
+        serde_yaml::Value::Sequence(items) => {
+            items.retain(|v| !v.is_null());
+            items.iter_mut().for_each(strip_nulls);
+        }
+        _ => {}
+    }
+}
+
+/// Top-level keys of a YAML (or JSON) frontmatter block, in file order
+fn yaml_key_order(frontmatter_str: &str) -> Vec<String> {
+    serde_yaml::from_str::<serde_yaml::Mapping>(frontmatter_str)
+        .map(|mapping| {
+            mapping
+                .keys()
+                .filter_map(|k| k.as_str().map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Serialize frontmatter and body into a markdown file using the given
+/// frontmatter delimiters and the file's original `layout`. With
+/// `final_newline` (the app config's `ensure_final_newline`) the file ends
+/// with exactly one line break.
+pub fn render_markdown(
+    frontmatter: &Frontmatter,
+    content: &str,
+    format: FrontmatterFormat,
+    layout: &DocumentLayout,
+    final_newline: bool,
+) -> Result<String, String> {
+    let mut rendered = render_frontmatter_and_body(frontmatter, content, format, layout)?;
+    if final_newline {
+        rendered.truncate(rendered.trim_end_matches(['\r', '\n']).len());
+        rendered.push('\n');
+    }
+    let rendered = match &layout.preamble {
+        Some(preamble) => format!("{}\n{}", preamble, rendered),
+        None => rendered,
+    };
+    Ok(layout.line_ending.apply(&rendered))
+}
+
+fn render_frontmatter_and_body(
+    frontmatter: &Frontmatter,
+    content: &str,
+    format: FrontmatterFormat,
+    layout: &DocumentLayout,
+) -> Result<String, String> {
+    match format {
+        FrontmatterFormat::Yaml => {
+            let frontmatter_yaml = frontmatter_to_yaml(frontmatter, &layout.key_order)?;
+            Ok(format!("---\n{}---\n\n{}", frontmatter_yaml, content))
+        }
+        FrontmatterFormat::Toml => {
+            let frontmatter_toml = frontmatter_to_toml(frontmatter, layout)?;
+            Ok(format!("+++\n{}+++\n\n{}", frontmatter_toml, content))
+        }
+        FrontmatterFormat::Json => {
+            let frontmatter_json = frontmatter_to_json(frontmatter, &layout.key_order)?;
+            Ok(format!("{}\n\n{}", frontmatter_json, content))
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct MarkdownDocument {
     pub frontmatter: Frontmatter,
     pub content: String,
+    pub format: FrontmatterFormat,
+    pub layout: DocumentLayout,
 }
 
 impl MarkdownDocument {
+    /// Parse a markdown file. CRLF files are parsed as LF, so `content`
+    /// always uses `\n`; the original ending is kept in `layout`.
     pub fn parse(raw: &str) -> Result<(Self, bool), String> {
+        Self::parse_with(raw, false)
+    }
+
+    /// Like `parse`, but a `---`/`+++` block without a title or date still
+    /// counts as frontmatter, for files imported from other generators
+    pub fn parse_imported(raw: &str) -> Result<(Self, bool), String> {
+        Self::parse_with(raw, true)
+    }
+
+    fn parse_with(raw: &str, fill_missing: bool) -> Result<(Self, bool), String> {
+        let line_ending = LineEnding::detect(raw);
+        let (mut doc, had_no_frontmatter) = match line_ending {
+            LineEnding::Lf => Self::parse_lf(raw, fill_missing)?,
+            LineEnding::Crlf => Self::parse_lf(&raw.replace("\r\n", "\n"), fill_missing)?,
+        };
+        doc.layout.line_ending = line_ending;
+        Ok((doc, had_no_frontmatter))
+    }
+
+    fn parse_lf(raw: &str, fill_missing: bool) -> Result<(Self, bool), String> {
+        let delimited = |value: serde_yaml::Value| delimited_frontmatter(value, fill_missing);
+        let (preamble, body) = split_preamble(raw);
+        let with_preamble = |mut doc: Self| {
+            doc.layout.preamble = preamble.map(str::to_string);
+            Ok((doc, false))
+        };
+
         // Standard format: ---\nfrontmatter\n---\ncontent
-        if raw.starts_with("---") {
-            let parts: Vec<&str> = raw.splitn(3, "---").collect();
-            if parts.len() >= 3 {
-                let frontmatter_str = parts[1].trim();
-                if let Ok(frontmatter) = serde_yaml::from_str::<FrontmatterYaml>(frontmatter_str) {
-                    let content = parts[2].trim().to_string();
-                    return Ok((Self { frontmatter: frontmatter.into(), content }, false));
-                }
+        if let Some((frontmatter_str, content)) = split_delimited(body, "---") {
+            if let Some(frontmatter) = serde_yaml::from_str(frontmatter_str).ok().and_then(delimited) {
+                let key_order = yaml_key_order(frontmatter_str);
+                let content = content.trim().to_string();
+                return with_preamble(Self::new(frontmatter, key_order, content, FrontmatterFormat::Yaml));
             }
         }
 
         // TOML frontmatter: +++\nfrontmatter\n+++\ncontent
-        if raw.starts_with("+++") {
-            let parts: Vec<&str> = raw.splitn(3, "+++").collect();
-            if parts.len() >= 3 {
-                let frontmatter_str = parts[1].trim();
-                if let Ok(toml_value) = toml::from_str::<toml::Value>(frontmatter_str) {
-                    if let Ok(json_value) = serde_json::to_value(toml_value) {
-                        if let Ok(frontmatter) = serde_json::from_value::<FrontmatterYaml>(json_value) {
-                            let content = parts[2].trim().to_string();
-                            return Ok((Self { frontmatter: frontmatter.into(), content }, false));
-                        }
-                    }
+        if let Some((frontmatter_str, content)) = split_delimited(body, "+++") {
+            if let Ok(toml_value) = toml::from_str::<toml::Value>(frontmatter_str) {
+                let key_order = toml_value
+                    .as_table()
+                    .map(|table| table.keys().cloned().collect())
+                    .unwrap_or_default();
+                let mut toml_datetimes = Vec::new();
+                collect_toml_datetimes(&toml_value, &mut Vec::new(), &mut toml_datetimes);
+                let value = serde_yaml::to_value(toml_to_json(toml_value)).ok();
+                if let Some(frontmatter) = value.and_then(delimited) {
+                    let content = content.trim().to_string();
+                    let mut doc = Self::new(frontmatter, key_order, content, FrontmatterFormat::Toml);
+                    doc.layout.toml_datetimes = toml_datetimes;
+                    return with_preamble(doc);
                 }
             }
         }
 
         // JSON frontmatter: { ... }\ncontent
-        if raw.trim_start().starts_with('{') {
-            if let Some((frontmatter_str, content)) = split_json_frontmatter(raw) {
+        if body.starts_with('{') {
+            if let Some((frontmatter_str, content)) = split_json_frontmatter(body) {
                 if let Ok(frontmatter) = serde_yaml::from_str::<FrontmatterYaml>(&frontmatter_str) {
-                    return Ok((Self { frontmatter: frontmatter.into(), content }, false));
+                    let key_order = yaml_key_order(&frontmatter_str);
+                    return with_preamble(Self::new(frontmatter, key_order, content, FrontmatterFormat::Json));
                 }
             }
         }
 
         // Alternative format: frontmatter\n---\ncontent (without opening ---)
         // This is used by some Hugo content workflows
-        if let Some(separator_pos) = raw.find("\n---") {
-            let frontmatter_str = &raw[..separator_pos].trim();
-            // Check if this looks like YAML frontmatter (contains "title:" or "date:")
-            if frontmatter_str.contains("title:") || frontmatter_str.contains("date:") {
-                if let Ok(frontmatter) = serde_yaml::from_str::<FrontmatterYaml>(frontmatter_str) {
-                    // Content is everything after the ---
-                    let content_start = separator_pos + 4; // "\n---".len()
-                    let content = if content_start < raw.len() {
-                        raw[content_start..].trim().to_string()
-                    } else {
-                        String::new()
-                    };
-                    return Ok((Self { frontmatter: frontmatter.into(), content }, false));
-                }
+        if let Some((frontmatter_str, content)) = split_alternative_frontmatter(raw) {
+            if let Ok(frontmatter) = serde_yaml::from_str::<FrontmatterYaml>(frontmatter_str) {
+                let key_order = yaml_key_order(frontmatter_str);
+                return Ok((Self::new(frontmatter, key_order, content.trim().to_string(), FrontmatterFormat::Yaml), false));
             }
         }
 
@@ -167,33 +459,199 @@ impl MarkdownDocument {
             comments: None,
             layout: None,
             permalink: None,
+            slug: None,
+            aliases: Vec::new(),
             description: None,
             draft: None,
-            custom_fields: HashMap::new(),
+            weight: None,
+            publish_date: None,
+            expiry_date: None,
+            custom_fields: IndexMap::new(),
         };
 
-        Ok((Self {
+        let doc = Self {
             frontmatter,
             content: raw.to_string(),
-        }, true))
+            format: FrontmatterFormat::Yaml,
+            layout: DocumentLayout::default(),
+        };
+        Ok((doc, true))
     }
 
+    fn new(
+        frontmatter: FrontmatterYaml,
+        key_order: Vec<String>,
+        content: String,
+        format: FrontmatterFormat,
+    ) -> Self {
+        let layout = DocumentLayout { key_order, ..Default::default() };
+        Self { frontmatter: Frontmatter::from(frontmatter), content, format, layout }
+    }
 }
 
+/// Frontmatter from a `---`/`+++` block. With `fill_missing` a missing title
+/// or date is filled with the "unset" values that `Post::from_file`
+/// replaces, instead of the block ending up in the body.
+fn delimited_frontmatter(value: serde_yaml::Value, fill_missing: bool) -> Option<FrontmatterYaml> {
+    let serde_yaml::Value::Mapping(mut mapping) = value else {
+        return None;
+    };
+    if !fill_missing {
+        return serde_yaml::from_value(serde_yaml::Value::Mapping(mapping)).ok();
+    }
+    if !mapping.contains_key("title") {
+        mapping.insert("title".into(), "Untitled Post".into());
+    }
+    if !mapping.contains_key("date") {
+        mapping.insert("date".into(), "".into());
+    }
+    serde_yaml::from_value(serde_yaml::Value::Mapping(mapping)).ok()
+}
+
+/// Split off what Hugo accepts before the frontmatter: a byte order mark,
+/// whitespace and HTML comments. The comments are returned so saves can keep
+/// them; the rest is dropped.
+fn split_preamble(raw: &str) -> (Option<&str>, &str) {
+    let text = raw.strip_prefix('\u{feff}').unwrap_or(raw);
+    let mut rest = text.trim_start();
+    while rest.starts_with("<!--") {
+        let Some(end) = rest.find("-->") else {
+            break;
+        };
+        rest = rest[end + 3..].trim_start();
+    }
+
+    let preamble = text[..text.len() - rest.len()].trim();
+    ((!preamble.is_empty()).then_some(preamble), rest)
+}
+
+/// Key paths of the datetimes in a TOML table, nested tables included
+fn collect_toml_datetimes(value: &toml::Value, path: &mut Vec<String>, found: &mut Vec<Vec<String>>) {
+    match value {
+        toml::Value::Datetime(_) => found.push(path.clone()),
+        toml::Value::Table(table) => {
+            for (key, item) in table {
+                path.push(key.clone());
+                collect_toml_datetimes(item, path, found);
+                path.pop();
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Convert TOML to JSON. Native datetimes (`date = 2024-01-01T10:00:00Z`)
+/// become their RFC 3339 text rather than serde's internal datetime object.
+pub fn toml_to_json(value: toml::Value) -> serde_json::Value {
+    match value {
+        toml::Value::String(text) => serde_json::Value::String(text),
+        toml::Value::Integer(number) => number.into(),
+        toml::Value::Float(number) => number.into(),
+        toml::Value::Boolean(flag) => flag.into(),
+        toml::Value::Datetime(datetime) => serde_json::Value::String(datetime.to_string()),
+        toml::Value::Array(items) => items.into_iter().map(toml_to_json).collect(),
+        toml::Value::Table(table) => serde_json::Value::Object(
+            table
+                .into_iter()
+                .map(|(key, item)| (key, toml_to_json(item)))
+                .collect(),
+        ),
+    }
+}
+
+/// Whether a line is `delimiter` on its own, as frontmatter delimiters must be
+pub fn is_delimiter_line(line: &str, delimiter: &str) -> bool {
+    line.trim_end() == delimiter
+}
+
+/// The delimiter of the YAML or TOML block a line opens, if it opens one
+pub fn opening_delimiter(line: &str) -> Option<&'static str> {
+    ["---", "+++"]
+        .into_iter()
+        .find(|delimiter| is_delimiter_line(line, delimiter))
+}
+
+/// Split `delimiter\nfrontmatter\ndelimiter\ncontent`. Delimiters only count
+/// on lines of their own, so a `---` inside a value doesn't end the block.
+fn split_delimited<'a>(raw: &'a str, delimiter: &str) -> Option<(&'a str, &'a str)> {
+    let mut lines = raw.split_inclusive('\n');
+    let first = lines.next()?;
+    if !is_delimiter_line(first, delimiter) {
+        return None;
+    }
+
+    let start = first.len();
+    let mut offset = start;
+    for line in lines {
+        if is_delimiter_line(line, delimiter) {
+            return Some((&raw[start..offset], &raw[offset + line.len()..]));
+        }
+        offset += line.len();
+    }
+    None
+}
+
+/// Split `frontmatter\n---\ncontent` when the block before the first `---`
+/// line is clearly YAML: every line is a `key:` line, an indented or list
+/// continuation, or blank, and both `title` and `date` are set. Anything
+/// else (prose, headings) is a post body that happens to use a `---` rule.
+fn split_alternative_frontmatter(raw: &str) -> Option<(&str, &str)> {
+    let mut offset = 0;
+    let mut separator = None;
+    for line in raw.split_inclusive('\n') {
+        if line.trim_end() == "---" {
+            separator = Some((offset, offset + line.len()));
+            break;
+        }
+        offset += line.len();
+    }
+    let (block_end, content_start) = separator?;
+    let block = raw[..block_end].trim();
+
+    let looks_like_yaml = block.lines().all(|line| {
+        line.trim().is_empty()
+            || line.starts_with([' ', '\t', '-'])
+            || YAML_KEY_LINE_RE.is_match(line)
+    });
+    if !looks_like_yaml {
+        return None;
+    }
+
+    let mapping: serde_yaml::Mapping = serde_yaml::from_str(block).ok()?;
+    if !(mapping.contains_key("title") && mapping.contains_key("date")) {
+        return None;
+    }
+
+    Some((block, &raw[content_start..]))
+}
+
+/// Split a leading JSON object from the body. Braces inside strings don't
+/// count, so a title like "Using {x}" doesn't end the block early.
 fn split_json_frontmatter(raw: &str) -> Option<(String, String)> {
     let mut depth = 0usize;
+    let mut in_string = false;
+    let mut escaped = false;
     let mut end_idx = None;
 
     for (idx, ch) in raw.char_indices() {
+        if in_string {
+            match ch {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+
         match ch {
+            '"' => in_string = true,
             '{' => depth += 1,
-            '}' => {
-                if depth > 0 {
-                    depth -= 1;
-                    if depth == 0 {
-                        end_idx = Some(idx);
-                        break;
-                    }
+            '}' if depth > 0 => {
+                depth -= 1;
+                if depth == 0 {
+                    end_idx = Some(idx);
+                    break;
                 }
             }
             _ => {}
@@ -207,6 +665,256 @@ fn split_json_frontmatter(raw: &str) -> Option<(String, String)> {
     })
 }
 
+/// Reading speed when the app config doesn't set `words_per_minute`
+pub const DEFAULT_WORDS_PER_MINUTE: u32 = 200;
+
+lazy_static::lazy_static! {
+    /// `key: value` at the start of a line, as in a YAML mapping
+    static ref YAML_KEY_LINE_RE: Regex = Regex::new(r"^[A-Za-z_][\w-]*:(\s|$)").unwrap();
+    static ref IMAGE_RE: Regex = Regex::new(r"!\[[^\]]*\]\([^)]*\)").unwrap();
+    static ref LINK_RE: Regex = Regex::new(r"\[([^\]]*)\]\([^)]*\)").unwrap();
+    static ref INLINE_CODE_RE: Regex = Regex::new(r"`[^`]*`").unwrap();
+    static ref HTML_TAG_RE: Regex = Regex::new(r"<[^>]+>").unwrap();
+    static ref BARE_URL_RE: Regex = Regex::new(r"(https?://|mailto:)\S+").unwrap();
+    static ref INLINE_LINK_RE: Regex =
+        Regex::new(r#"(!?)\[[^\]]*\]\(\s*<?([^)\s>]+)>?(?:\s+"[^"]*")?\s*\)"#).unwrap();
+    static ref REFERENCE_LINK_RE: Regex = Regex::new(r"^\s{0,3}\[[^\]]+\]:\s*<?([^\s>]+)>?").unwrap();
+    static ref SHORTCODE_RE: Regex = Regex::new(r"(?s)\{\{([<%])(.*?)[>%]\}\}").unwrap();
+}
+
+/// A link or image reference found in a markdown body
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MarkdownLink {
+    pub target: String,
+    /// 1-based line within the body
+    pub line: usize,
+    pub is_image: bool,
+}
+
+/// Lines before a parsed body in its raw file (preamble, frontmatter and
+/// blank lines), to turn body line numbers into file line numbers
+pub fn body_line_offset(raw: &str, body: &str) -> usize {
+    if body.is_empty() {
+        return 0;
+    }
+    let raw = raw.replace("\r\n", "\n");
+    raw.rfind(body)
+        .map(|start| raw[..start].matches('\n').count())
+        .unwrap_or(0)
+}
+
+/// Extract inline and reference-style link targets, skipping fenced code
+pub fn extract_links(content: &str) -> Vec<MarkdownLink> {
+    let mut links = Vec::new();
+    let mut in_fence = false;
+
+    for (index, line) in content.lines().enumerate() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+            continue;
+        }
+        if in_fence {
+            continue;
+        }
+
+        let line_without_code = INLINE_CODE_RE.replace_all(line, "");
+        for captures in INLINE_LINK_RE.captures_iter(&line_without_code) {
+            links.push(MarkdownLink {
+                target: captures[2].to_string(),
+                line: index + 1,
+                is_image: &captures[1] == "!",
+            });
+        }
+        if let Some(captures) = REFERENCE_LINK_RE.captures(&line_without_code) {
+            links.push(MarkdownLink {
+                target: captures[1].to_string(),
+                line: index + 1,
+                is_image: false,
+            });
+        }
+    }
+
+    links
+}
+
+/// A Hugo shortcode call such as `{{< figure src="a.png" >}}`
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct Shortcode {
+    pub name: String,
+    /// Arguments as written, split on whitespace outside quotes
+    pub args: Vec<String>,
+    /// `%` shortcodes have their inner content rendered as markdown
+    pub markdown: bool,
+    /// Byte range of the opening tag within the body
+    pub start: usize,
+    pub end: usize,
+}
+
+/// Opening shortcode tags in a body. Closing tags and escaped examples
+/// (`{{</* name */>}}`) are skipped.
+pub fn extract_shortcodes(content: &str) -> Vec<Shortcode> {
+    let mut shortcodes = Vec::new();
+
+    for captures in SHORTCODE_RE.captures_iter(content) {
+        let whole = captures.get(0).expect("match always has group 0");
+        let inner = captures[2].trim();
+        if inner.starts_with("/*") || inner.starts_with('/') {
+            continue;
+        }
+
+        let inner = inner.strip_suffix('/').unwrap_or(inner).trim_end();
+        let mut tokens = split_shortcode_args(inner).into_iter();
+        let Some(name) = tokens.next() else {
+            continue;
+        };
+
+        shortcodes.push(Shortcode {
+            name,
+            args: tokens.collect(),
+            markdown: &captures[1] == "%",
+            start: whole.start(),
+            end: whole.end(),
+        });
+    }
+
+    shortcodes
+}
+
+fn split_shortcode_args(inner: &str) -> Vec<String> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut quote: Option<char> = None;
+
+    for ch in inner.chars() {
+        match quote {
+            Some(q) if ch == q => {
+                quote = None;
+                current.push(ch);
+            }
+            Some(_) => current.push(ch),
+            None if ch == '"' || ch == '`' => {
+                quote = Some(ch);
+                current.push(ch);
+            }
+            None if ch.is_whitespace() => {
+                if !current.is_empty() {
+                    args.push(std::mem::take(&mut current));
+                }
+            }
+            None => current.push(ch),
+        }
+    }
+    if !current.is_empty() {
+        args.push(current);
+    }
+
+    args
+}
+
+/// Count prose words in a markdown body, ignoring code, markup, shortcode
+/// tags and URLs
+pub fn count_words(content: &str) -> usize {
+    let mut in_fence = false;
+    let mut words = 0;
+
+    for line in content.lines() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+            continue;
+        }
+        if in_fence {
+            continue;
+        }
+
+        let line = SHORTCODE_RE.replace_all(line, " ");
+        let line = IMAGE_RE.replace_all(&line, " ");
+        let line = LINK_RE.replace_all(&line, " $1 ");
+        let line = INLINE_CODE_RE.replace_all(&line, " ");
+        let line = HTML_TAG_RE.replace_all(&line, " ");
+        let line = BARE_URL_RE.replace_all(&line, " ");
+
+        words += line
+            .split_whitespace()
+            .filter(|word| word.chars().any(|c| c.is_alphanumeric()))
+            .count();
+    }
+
+    words
+}
+
+/// Estimated minutes to read `word_count` words, rounded up
+pub fn reading_time_minutes(word_count: usize, words_per_minute: u32) -> u32 {
+    let words_per_minute = words_per_minute.max(1) as usize;
+    word_count.div_ceil(words_per_minute) as u32
+}
+
+/// Parse a frontmatter date in one of the formats Hugo commonly sees:
+/// RFC3339, `%Y-%m-%dT%H:%M:%S`, `%Y-%m-%d %H:%M:%S`, `%Y-%m-%d %H:%M` or
+/// `%Y-%m-%d`. Values without an offset are taken as UTC.
+pub fn parse_frontmatter_date(value: &str) -> Option<chrono::DateTime<chrono::FixedOffset>> {
+    let value = value.trim();
+    if let Ok(date) = chrono::DateTime::parse_from_rfc3339(value) {
+        return Some(date);
+    }
+
+    let naive = ["%Y-%m-%dT%H:%M:%S", "%Y-%m-%d %H:%M:%S", "%Y-%m-%d %H:%M"]
+        .iter()
+        .find_map(|format| chrono::NaiveDateTime::parse_from_str(value, format).ok())
+        .or_else(|| {
+            chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d")
+                .ok()
+                .and_then(|date| date.and_hms_opt(0, 0, 0))
+        })?;
+
+    Some(naive.and_utc().fixed_offset())
+}
+
+/// A non-markdown file stored next to a leaf bundle's `index.md`
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BundleResource {
+    pub name: String,
+    pub path: String,
+    pub size: u64,
+}
+
+/// Whether a markdown file is the content file of a leaf bundle
+pub fn is_bundle_index(file_path: &Path) -> bool {
+    file_path.file_name().and_then(|s| s.to_str()) == Some("index.md")
+}
+
+/// List resources of the bundle a file belongs to, or nothing if it isn't a bundle
+pub fn bundle_resources(file_path: &Path) -> Vec<BundleResource> {
+    if !is_bundle_index(file_path) {
+        return Vec::new();
+    }
+    let Some(bundle_dir) = file_path.parent() else {
+        return Vec::new();
+    };
+
+    let mut resources: Vec<BundleResource> = walkdir::WalkDir::new(bundle_dir)
+        .min_depth(1)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .filter(|e| e.path().extension().and_then(|s| s.to_str()) != Some("md"))
+        .filter_map(|e| {
+            let relative = e.path().strip_prefix(bundle_dir).ok()?;
+            Some(BundleResource {
+                name: e.file_name().to_string_lossy().to_string(),
+                path: relative.to_string_lossy().replace('\\', "/"),
+                size: e.metadata().map(|m| m.len()).unwrap_or(0),
+            })
+        })
+        .collect();
+    resources.sort_by(|a, b| a.path.cmp(&b.path));
+
+    resources
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Post {
@@ -216,8 +924,73 @@ pub struct Post {
     pub content: String,
     pub frontmatter: Frontmatter,
     pub file_path: String,
+    /// Milliseconds since the epoch, like `modified_at`
     pub created_at: i64,
+    /// Milliseconds since the epoch; saves compare it to detect changes
+    /// made on disk since the file was loaded
     pub modified_at: i64,
+    #[serde(default)]
+    pub frontmatter_format: FrontmatterFormat,
+    #[serde(default)]
+    pub layout: DocumentLayout,
+    #[serde(default)]
+    pub word_count: usize,
+    #[serde(default)]
+    pub reading_time_minutes: u32,
+    #[serde(default)]
+    pub resources: Vec<BundleResource>,
+    /// `date` normalized to RFC3339, when it could be parsed
+    #[serde(default)]
+    pub date_iso: Option<String>,
+    /// Content language; set by commands that know the site's language setup
+    #[serde(default)]
+    pub language: Option<String>,
+    /// Encoding the file was decoded from when it wasn't clean UTF-8. Saves
+    /// are refused while it is set; clearing it confirms converting to UTF-8
+    #[serde(default)]
+    pub encoding: Option<String>,
+}
+
+/// A post without its body, for list views. See `Post::from_file_metadata`.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PostMeta {
+    pub id: String,
+    pub title: String,
+    pub date: String,
+    pub frontmatter: Frontmatter,
+    pub file_path: String,
+    /// Milliseconds since the epoch, like `modified_at`
+    pub created_at: i64,
+    /// Milliseconds since the epoch; saves compare it to detect changes
+    /// made on disk since the file was loaded
+    pub modified_at: i64,
+    #[serde(default)]
+    pub frontmatter_format: FrontmatterFormat,
+    #[serde(default)]
+    pub resources: Vec<BundleResource>,
+    #[serde(default)]
+    pub date_iso: Option<String>,
+    #[serde(default)]
+    pub language: Option<String>,
+}
+
+impl From<Post> for PostMeta {
+    fn from(post: Post) -> Self {
+        Self {
+            id: post.id,
+            title: post.title,
+            date: post.date,
+            frontmatter: post.frontmatter,
+            file_path: post.file_path,
+            created_at: post.created_at,
+            modified_at: post.modified_at,
+            frontmatter_format: post.frontmatter_format,
+            resources: post.resources,
+            date_iso: post.date_iso,
+            language: post.language,
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -228,15 +1001,31 @@ pub struct Page {
     pub content: String,
     pub frontmatter: Frontmatter,
     pub file_path: String,
+    /// Milliseconds since the epoch, like `modified_at`
     pub created_at: i64,
+    /// Milliseconds since the epoch; saves compare it to detect changes
+    /// made on disk since the file was loaded
     pub modified_at: i64,
+    #[serde(default)]
+    pub frontmatter_format: FrontmatterFormat,
+    #[serde(default)]
+    pub layout: DocumentLayout,
+    #[serde(default)]
+    pub word_count: usize,
+    #[serde(default)]
+    pub reading_time_minutes: u32,
+    /// Content language; set by commands that know the site's language setup
+    #[serde(default)]
+    pub language: Option<String>,
+    /// Encoding the file was decoded from when it wasn't clean UTF-8. Saves
+    /// are refused while it is set; clearing it confirms converting to UTF-8
+    #[serde(default)]
+    pub encoding: Option<String>,
 }
 
 impl Page {
-    pub fn to_markdown(&self) -> Result<String, String> {
-        let frontmatter_yaml = frontmatter_to_yaml(&self.frontmatter)?;
-
-        Ok(format!("---\n{}---\n\n{}", frontmatter_yaml, self.content))
+    pub fn to_markdown(&self, final_newline: bool) -> Result<String, String> {
+        render_markdown(&self.frontmatter, &self.content, self.frontmatter_format, &self.layout, final_newline)
     }
 }
 
@@ -248,15 +1037,28 @@ pub struct Draft {
     pub content: String,
     pub frontmatter: Frontmatter,
     pub file_path: String,
+    /// Milliseconds since the epoch, like `modified_at`
     pub created_at: i64,
+    /// Milliseconds since the epoch; saves compare it to detect changes
+    /// made on disk since the file was loaded
     pub modified_at: i64,
+    #[serde(default)]
+    pub frontmatter_format: FrontmatterFormat,
+    #[serde(default)]
+    pub layout: DocumentLayout,
+    #[serde(default)]
+    pub word_count: usize,
+    #[serde(default)]
+    pub reading_time_minutes: u32,
+    /// Encoding the file was decoded from when it wasn't clean UTF-8. Saves
+    /// are refused while it is set; clearing it confirms converting to UTF-8
+    #[serde(default)]
+    pub encoding: Option<String>,
 }
 
 impl Draft {
-    pub fn to_markdown(&self) -> Result<String, String> {
-        let frontmatter_yaml = frontmatter_to_yaml(&self.frontmatter)?;
-
-        Ok(format!("---\n{}---\n\n{}", frontmatter_yaml, self.content))
+    pub fn to_markdown(&self, final_newline: bool) -> Result<String, String> {
+        render_markdown(&self.frontmatter, &self.content, self.frontmatter_format, &self.layout, final_newline)
     }
 }
 
@@ -285,14 +1087,38 @@ pub struct ImageInfo {
     pub size: u64,
     pub width: Option<u32>,
     pub height: Option<u32>,
+    /// Milliseconds since the epoch
     pub created_at: i64,
 }
 
 impl Post {
     pub fn from_file(file_path: &Path, project_path: &Path) -> Result<Self, String> {
+        let (content, encoding) = files::read_file_with_encoding(file_path)?;
+        let mut post = Self::from_source(file_path, project_path, &content, false)?;
+        post.encoding = encoding.map(str::to_string);
+        Ok(post)
+    }
+
+    /// Like `from_file`, for a file from another site: frontmatter missing
+    /// its title or date is completed rather than treated as body text
+    pub fn from_imported_file(file_path: &Path, project_path: &Path) -> Result<Self, String> {
         let content = files::read_file(file_path)?;
+        Self::from_source(file_path, project_path, &content, true)
+    }
 
-        let (mut doc, had_no_frontmatter) = MarkdownDocument::parse(&content)?;
+    /// Like `from_file`, but reads only the frontmatter. `content` is empty,
+    /// `word_count` is zero and an untitled post falls back to its file name.
+    pub fn from_file_metadata(file_path: &Path, project_path: &Path) -> Result<Self, String> {
+        let content = files::read_frontmatter_block(file_path)?;
+        Self::from_source(file_path, project_path, &content, false)
+    }
+
+    fn from_source(file_path: &Path, project_path: &Path, content: &str, imported: bool) -> Result<Self, String> {
+        let (mut doc, had_no_frontmatter) = if imported {
+            MarkdownDocument::parse_imported(content)?
+        } else {
+            MarkdownDocument::parse(content)?
+        };
 
         // Get file metadata
         let metadata = fs::metadata(file_path)
@@ -303,14 +1129,14 @@ impl Post {
             .ok()
             .or(metadata.modified().ok())
             .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
-            .map(|d: std::time::Duration| d.as_secs() as i64)
+            .map(|d: std::time::Duration| d.as_millis() as i64)
             .unwrap_or(0);
 
         let modified_at = metadata
             .modified()
             .ok()
             .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
-            .map(|d: std::time::Duration| d.as_secs() as i64)
+            .map(|d: std::time::Duration| d.as_millis() as i64)
             .unwrap_or(0);
 
         // If title is "Untitled Post", try to extract from content or filename
@@ -344,6 +1170,10 @@ impl Post {
             .unwrap_or_else(|| file_path.to_str().unwrap_or(""))
             .to_string();
 
+        let word_count = count_words(&doc.content);
+        let date_iso = parse_frontmatter_date(&doc.frontmatter.date)
+            .map(|date| date.to_rfc3339_opts(chrono::SecondsFormat::Secs, true));
+
         Ok(Self {
             id,
             title: doc.frontmatter.title.clone(),
@@ -353,19 +1183,38 @@ impl Post {
             file_path: file_path.to_string_lossy().to_string(),
             created_at,
             modified_at,
+            frontmatter_format: doc.format,
+            layout: doc.layout,
+            word_count,
+            reading_time_minutes: reading_time_minutes(word_count, crate::config::words_per_minute()),
+            resources: bundle_resources(file_path),
+            date_iso,
+            language: None,
+            encoding: None,
         })
     }
 
-    pub fn to_markdown(&self) -> Result<String, String> {
-        let frontmatter_yaml = frontmatter_to_yaml(&self.frontmatter)?;
-
-        Ok(format!("---\n{}---\n\n{}", frontmatter_yaml, self.content))
+    pub fn to_markdown(&self, final_newline: bool) -> Result<String, String> {
+        render_markdown(&self.frontmatter, &self.content, self.frontmatter_format, &self.layout, final_newline)
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::MarkdownDocument;
+    use super::{
+        body_line_offset, count_words, extract_links, extract_shortcodes, parse_frontmatter_date, reading_time_minutes,
+        render_markdown, FrontmatterFormat, LineEnding, MarkdownDocument,
+    };
+
+    fn round_trip(raw: &str) -> (MarkdownDocument, MarkdownDocument) {
+        let (doc, _) = MarkdownDocument::parse(raw).expect("parse failed");
+        let rendered = render_markdown(&doc.frontmatter, &doc.content, doc.format, &doc.layout, false)
+            .expect("render failed");
+        let (reparsed, had_no_frontmatter) =
+            MarkdownDocument::parse(&rendered).expect("reparse failed");
+        assert!(!had_no_frontmatter);
+        (doc, reparsed)
+    }
 
     #[test]
     fn parse_standard_frontmatter() {
@@ -389,6 +1238,59 @@ mod tests {
         assert_eq!(doc.content, "Alt body");
     }
 
+    #[test]
+    fn parse_alternative_frontmatter_ignores_prose_before_rule() {
+        let raw = "# Release notes\n\ntitle: v2\ndate: 2024-05-01\n---\nDetails";
+        let (doc, had_no_frontmatter) = MarkdownDocument::parse(raw).expect("parse failed");
+
+        assert!(had_no_frontmatter);
+        assert_eq!(doc.content, raw);
+
+        let raw = "Notes where the title: comes up mid-sentence.\n\n---\n\nMore";
+        let (_, had_no_frontmatter) = MarkdownDocument::parse(raw).expect("parse failed");
+        assert!(had_no_frontmatter);
+    }
+
+    #[test]
+    fn parse_frontmatter_with_delimiter_inside_values() {
+        let raw = "---\ntitle: \"Before --- after\"\ndate: 2024-01-01\nsummary: |\n  one\n  ---\n  two\n---\n\nBody\n\n---\n\nMore";
+        let (doc, reparsed) = round_trip(raw);
+
+        assert_eq!(doc.frontmatter.title, "Before --- after");
+        assert_eq!(
+            doc.frontmatter.custom_fields.get("summary").and_then(|v| v.as_str()),
+            Some("one\n---\ntwo\n")
+        );
+        assert_eq!(doc.content, "Body\n\n---\n\nMore");
+        assert_eq!(reparsed.content, doc.content);
+    }
+
+    #[test]
+    fn parse_frontmatter_after_leading_comment_keeps_it() {
+        let raw = "\n<!-- imported from WordPress -->\n---\ntitle: Hello\ndate: 2024-01-01\n---\n\nBody";
+        let (doc, had_no_frontmatter) = MarkdownDocument::parse(raw).expect("parse failed");
+
+        assert!(!had_no_frontmatter);
+        assert_eq!(doc.frontmatter.title, "Hello");
+        assert_eq!(doc.content, "Body");
+
+        let rendered = render_markdown(&doc.frontmatter, &doc.content, doc.format, &doc.layout, false).expect("render failed");
+        assert_eq!(rendered, raw.trim_start());
+    }
+
+    #[test]
+    fn parse_delimited_frontmatter_without_title_or_date() {
+        let raw = "---\nauthor: Rail\n---\n\nBody";
+        let (_, had_no_frontmatter) = MarkdownDocument::parse(raw).expect("parse failed");
+        assert!(had_no_frontmatter);
+
+        let (doc, had_no_frontmatter) = MarkdownDocument::parse_imported(raw).expect("parse failed");
+        assert!(!had_no_frontmatter);
+        assert_eq!(doc.frontmatter.title, "Untitled Post");
+        assert_eq!(doc.frontmatter.date, "");
+        assert_eq!(doc.content, "Body");
+    }
+
     #[test]
     fn parse_without_frontmatter_defaults() {
         let raw = "Just text";
@@ -398,4 +1300,246 @@ mod tests {
         assert_eq!(doc.frontmatter.title, "Untitled Post");
         assert_eq!(doc.content, "Just text");
     }
+
+    #[test]
+    fn render_ends_with_one_newline_unless_disabled() {
+        let (doc, _) = MarkdownDocument::parse("---\ntitle: Hello\ndate: 2024-01-01\n---\n").expect("parse failed");
+
+        let render = |content: &str, final_newline| {
+            render_markdown(&doc.frontmatter, content, doc.format, &doc.layout, final_newline).expect("render failed")
+        };
+        assert!(render("Body", true).ends_with("\n\nBody\n"));
+        assert!(render("Body\n\n\n", true).ends_with("\n\nBody\n"));
+        assert!(render("", true).ends_with("---\n"));
+        assert!(render("Body", false).ends_with("\n\nBody"));
+    }
+
+    #[test]
+    fn round_trip_keeps_crlf_line_endings() {
+        let raw = "---\r\ntitle: Hello\r\ndate: 2024-01-01\r\n---\r\n\r\nLine one\r\nLine two";
+        let (doc, _) = MarkdownDocument::parse(raw).expect("parse failed");
+
+        assert_eq!(doc.layout.line_ending, LineEnding::Crlf);
+        assert_eq!(doc.frontmatter.title, "Hello");
+        assert_eq!(doc.content, "Line one\nLine two");
+
+        let rendered = render_markdown(&doc.frontmatter, &doc.content, doc.format, &doc.layout, false).expect("render failed");
+        assert_eq!(rendered, raw);
+    }
+
+    #[test]
+    fn round_trip_yaml_frontmatter() {
+        let raw = "---\ntitle: \"Hello\"\ndate: \"2024-01-01\"\ntags:\n  - rust\ncover: /images/a.png\n---\nBody";
+        let (doc, reparsed) = round_trip(raw);
+
+        assert_eq!(doc.format, FrontmatterFormat::Yaml);
+        assert_eq!(reparsed.format, FrontmatterFormat::Yaml);
+        assert_eq!(reparsed.frontmatter.title, "Hello");
+        assert_eq!(reparsed.frontmatter.tags, vec!["rust".to_string()]);
+        assert_eq!(
+            reparsed.frontmatter.custom_fields.get("cover").and_then(|v| v.as_str()),
+            Some("/images/a.png")
+        );
+        assert_eq!(reparsed.content, "Body");
+    }
+
+    #[test]
+    fn round_trip_toml_frontmatter() {
+        let raw = "+++\ntitle = \"Hello\"\ndate = \"2024-01-01\"\ntags = [\"rust\"]\ncover = \"/images/a.png\"\n+++\nBody";
+        let (doc, reparsed) = round_trip(raw);
+
+        assert_eq!(doc.format, FrontmatterFormat::Toml);
+        assert_eq!(reparsed.format, FrontmatterFormat::Toml);
+        assert_eq!(reparsed.frontmatter.title, "Hello");
+        assert_eq!(reparsed.frontmatter.tags, vec!["rust".to_string()]);
+        assert_eq!(
+            reparsed.frontmatter.custom_fields.get("cover").and_then(|v| v.as_str()),
+            Some("/images/a.png")
+        );
+        assert_eq!(reparsed.content, "Body");
+    }
+
+    #[test]
+    fn parse_toml_native_datetimes() {
+        let raw = "+++\ntitle = \"Hello\"\ndate = 2024-01-01T10:00:00Z\npublishDate = 2024-01-02\n[params]\nseen = 2024-01-03T08:30:00+03:00\n+++\nBody";
+        let (doc, _) = MarkdownDocument::parse(raw).unwrap();
+
+        assert_eq!(doc.frontmatter.title, "Hello");
+        assert_eq!(doc.frontmatter.date, "2024-01-01T10:00:00Z");
+        assert!(parse_frontmatter_date(&doc.frontmatter.date).is_some());
+        assert_eq!(doc.frontmatter.publish_date.as_deref(), Some("2024-01-02"));
+        let params = doc.frontmatter.custom_fields.get("params").unwrap();
+        assert_eq!(params.get("seen").and_then(|v| v.as_str()), Some("2024-01-03T08:30:00+03:00"));
+    }
+
+    #[test]
+    fn round_trip_keeps_toml_datetimes_unquoted() {
+        let raw = "+++\ntitle = \"Hello\"\ndate = 2024-01-01T10:00:00Z\nlastmod = \"2024-01-05\"\npublishDate = 2024-01-02\n\n[params]\nseen = 2024-01-03T08:30:00+03:00\n+++\n\nBody";
+        let (doc, _) = MarkdownDocument::parse(raw).expect("parse failed");
+
+        let rendered = render_markdown(&doc.frontmatter, &doc.content, doc.format, &doc.layout, false).expect("render failed");
+        assert_eq!(rendered, raw);
+    }
+
+    #[test]
+    fn round_trip_json_frontmatter() {
+        let raw = "{\n  \"title\": \"Hello\",\n  \"date\": \"2024-01-01\",\n  \"tags\": [\"rust\"],\n  \"cover\": \"/images/a.png\"\n}\nBody";
+        let (doc, reparsed) = round_trip(raw);
+
+        assert_eq!(doc.format, FrontmatterFormat::Json);
+        assert_eq!(reparsed.format, FrontmatterFormat::Json);
+        assert_eq!(reparsed.frontmatter.title, "Hello");
+        assert_eq!(reparsed.frontmatter.tags, vec!["rust".to_string()]);
+        assert_eq!(
+            reparsed.frontmatter.custom_fields.get("cover").and_then(|v| v.as_str()),
+            Some("/images/a.png")
+        );
+        assert_eq!(reparsed.content, "Body");
+    }
+
+    #[test]
+    fn round_trip_json_with_braces_in_strings_and_nested_fields() {
+        let raw = "{\n  \"title\": \"Using {x} in \\\"quotes\\\"}\",\n  \"date\": \"2024-01-01\",\n  \"params\": {\n    \"toc\": true\n  }\n}\n\nBody with } brace";
+        let (doc, _) = MarkdownDocument::parse(raw).expect("parse failed");
+        assert_eq!(doc.format, FrontmatterFormat::Json);
+        assert_eq!(doc.frontmatter.title, "Using {x} in \"quotes\"}");
+        assert_eq!(doc.content, "Body with } brace");
+
+        let rendered = render_markdown(&doc.frontmatter, &doc.content, doc.format, &doc.layout, false).expect("render failed");
+        assert_eq!(rendered, raw);
+    }
+
+    #[test]
+    fn round_trip_preserves_key_order() {
+        let raw = "---\ncover: /images/a.png\ntitle: Hello\nweight: 3\ndate: 2024-01-01\ntags:\n- rust\nauthor: Rail\n---\n\nBody";
+        let (doc, _) = MarkdownDocument::parse(raw).expect("parse failed");
+
+        let rendered = render_markdown(&doc.frontmatter, &doc.content, doc.format, &doc.layout, false).expect("render failed");
+        assert_eq!(rendered, raw);
+
+        // A second save must be identical to the first
+        let (reparsed, _) = MarkdownDocument::parse(&rendered).expect("reparse failed");
+        let rerendered =
+            render_markdown(&reparsed.frontmatter, &reparsed.content, reparsed.format, &reparsed.layout, false).expect("render failed");
+        assert_eq!(rerendered, rendered);
+    }
+
+    #[test]
+    fn round_trip_preserves_toml_key_order() {
+        let raw = "+++\nweight = 3\ntitle = \"Hello\"\ndate = \"2024-01-01\"\ncover = \"/images/a.png\"\n+++\n\nBody";
+        let (doc, _) = MarkdownDocument::parse(raw).expect("parse failed");
+
+        let rendered = render_markdown(&doc.frontmatter, &doc.content, doc.format, &doc.layout, false).expect("render failed");
+        assert_eq!(rendered, raw);
+    }
+
+    #[test]
+    fn new_fields_follow_documented_order() {
+        let raw = "---\ntitle: Hello\ndate: 2024-01-01\n---\n\nBody";
+        let (mut doc, _) = MarkdownDocument::parse(raw).expect("parse failed");
+        doc.frontmatter.custom_fields.insert("zeta".to_string(), "z".into());
+        doc.frontmatter.custom_fields.insert("alpha".to_string(), "a".into());
+        doc.frontmatter.description = Some("About".to_string());
+
+        let rendered = render_markdown(&doc.frontmatter, &doc.content, doc.format, &doc.layout, false).expect("render failed");
+        assert_eq!(
+            rendered,
+            "---\ntitle: Hello\ndate: 2024-01-01\ndescription: About\nzeta: z\nalpha: a\n---\n\nBody"
+        );
+    }
+
+    #[test]
+    fn round_trip_slug_and_aliases() {
+        let raw = "---\ntitle: Hello\ndate: 2024-01-01\nslug: hello-world\naliases:\n- /old/hello/\n---\n\nBody";
+        let (doc, reparsed) = round_trip(raw);
+
+        assert_eq!(doc.frontmatter.slug.as_deref(), Some("hello-world"));
+        assert_eq!(doc.frontmatter.aliases, vec!["/old/hello/".to_string()]);
+        assert!(doc.frontmatter.custom_fields.is_empty());
+        assert_eq!(reparsed.frontmatter.aliases, doc.frontmatter.aliases);
+
+        // Empty aliases aren't written
+        let (doc, _) = MarkdownDocument::parse("---\ntitle: Hello\ndate: 2024-01-01\n---\n").expect("parse failed");
+        let rendered = render_markdown(&doc.frontmatter, &doc.content, doc.format, &doc.layout, false).expect("render failed");
+        assert!(!rendered.contains("aliases"));
+    }
+
+    #[test]
+    fn round_trip_weight_and_schedule_dates() {
+        let raw = "---\ntitle: Hello\ndate: 2024-01-01\nweight: 5\npublishDate: 2024-02-01\nexpirydate: 2025-01-01\n---\n\nBody";
+        let (doc, reparsed) = round_trip(raw);
+
+        assert_eq!(doc.frontmatter.weight, Some(5));
+        assert_eq!(doc.frontmatter.publish_date.as_deref(), Some("2024-02-01"));
+        assert_eq!(doc.frontmatter.expiry_date.as_deref(), Some("2025-01-01"));
+        assert!(doc.frontmatter.custom_fields.is_empty());
+        assert_eq!(reparsed.frontmatter.expiry_date, doc.frontmatter.expiry_date);
+    }
+
+    #[test]
+    fn extract_shortcodes_with_args_and_ranges() {
+        let content = "Intro {{< figure src=\"/images/a b.png\" title=\"A\" >}}\n{{% notice tip %}}Body{{% /notice %}}\n{{</* youtube abc */>}} {{< gist user 123 />}}";
+        let shortcodes = extract_shortcodes(content);
+
+        let names: Vec<&str> = shortcodes.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["figure", "notice", "gist"]);
+        assert_eq!(shortcodes[0].args, vec!["src=\"/images/a b.png\"", "title=\"A\""]);
+        assert_eq!(&content[shortcodes[0].start..shortcodes[0].end], "{{< figure src=\"/images/a b.png\" title=\"A\" >}}");
+        assert!(!shortcodes[0].markdown);
+        assert!(shortcodes[1].markdown);
+        assert_eq!(shortcodes[1].args, vec!["tip"]);
+        assert_eq!(shortcodes[2].args, vec!["user", "123"]);
+        assert_eq!(count_words("{{< figure src=\"a.png\" >}} two words"), 2);
+    }
+
+    #[test]
+    fn parse_frontmatter_date_formats() {
+        let iso = |value: &str| parse_frontmatter_date(value).map(|d| d.to_rfc3339());
+
+        assert_eq!(iso("2024-03-05T10:20:30+02:00").as_deref(), Some("2024-03-05T10:20:30+02:00"));
+        assert_eq!(iso("2024-03-05 10:20:30").as_deref(), Some("2024-03-05T10:20:30+00:00"));
+        assert_eq!(iso("2024-03-05 10:20").as_deref(), Some("2024-03-05T10:20:00+00:00"));
+        assert_eq!(iso("2024-03-05").as_deref(), Some("2024-03-05T00:00:00+00:00"));
+        assert_eq!(iso("March 5th"), None);
+    }
+
+    #[test]
+    fn count_words_skips_code_and_urls() {
+        let content = "# Hello world\n\nSee [the docs](https://example.com/docs) and ![shot](/images/a.png).\n\n```rust\nfn main() {}\n```\n\nUse `cargo run` - done.";
+        // Hello world See the docs and Use done
+        assert_eq!(count_words(content), 8);
+    }
+
+    #[test]
+    fn reading_time_rounds_up() {
+        assert_eq!(reading_time_minutes(0, 200), 0);
+        assert_eq!(reading_time_minutes(1, 200), 1);
+        assert_eq!(reading_time_minutes(401, 200), 3);
+    }
+
+    #[test]
+    fn body_line_offset_counts_frontmatter_and_preamble() {
+        let raw = "<!-- note -->\r\n---\r\ntitle: Hello\r\ndate: 2024-01-01\r\n---\r\n\r\nFirst\r\nSecond";
+        let (doc, _) = MarkdownDocument::parse(raw).expect("parse failed");
+        assert_eq!(body_line_offset(raw, &doc.content), 6);
+    }
+
+    #[test]
+    fn extract_links_finds_inline_reference_and_image_targets() {
+        let content = "Intro [post](/posts/other/) and ![img](cover.png \"Cover\").\n\n```\n[skip](/nope)\n```\n[ref]: ../about.md";
+        let links = extract_links(content);
+        let targets: Vec<(&str, usize, bool)> = links
+            .iter()
+            .map(|l| (l.target.as_str(), l.line, l.is_image))
+            .collect();
+
+        assert_eq!(
+            targets,
+            vec![
+                ("/posts/other/", 1, false),
+                ("cover.png", 1, true),
+                ("../about.md", 6, false),
+            ]
+        );
+    }
 }
@@ -0,0 +1,175 @@
+// Standalone HTML preview of a post body
+// CommonMark rendering via pulldown-cmark; Hugo shortcodes are not executed
+
+use crate::frontmatter_config::{has_parent_dir, resolve_static_path};
+use crate::markdown::extract_shortcodes;
+use pulldown_cmark::{html, CowStr, Event, Options, Parser, Tag};
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RenderedHtml {
+    pub html: String,
+    /// Absolute paths that image `src` attributes were rewritten to, so the
+    /// frontend can map them to asset URLs
+    pub local_images: Vec<String>,
+    pub warnings: Vec<String>,
+}
+
+/// Render markdown to HTML. Root-relative image URLs resolve against
+/// `static_dir`, relative ones against `bundle_dir` (the post's folder).
+/// Raw HTML is shown as text and script URLs are dropped, since the preview
+/// runs inside the app's webview.
+pub fn render_html(content: &str, static_dir: &Path, bundle_dir: Option<&Path>) -> RenderedHtml {
+    let options = Options::ENABLE_TABLES
+        | Options::ENABLE_STRIKETHROUGH
+        | Options::ENABLE_FOOTNOTES
+        | Options::ENABLE_TASKLISTS;
+
+    let mut local_images = Vec::new();
+    let parser = Parser::new_ext(content, options).map(|event| match event {
+        Event::Start(Tag::Image { link_type, dest_url, title, id }) => {
+            let dest_url = match resolve_image(&dest_url, static_dir, bundle_dir) {
+                Some(path) => {
+                    let path = path.to_string_lossy().to_string();
+                    local_images.push(path.clone());
+                    CowStr::from(path)
+                }
+                None => dest_url,
+            };
+            Event::Start(Tag::Image { link_type, dest_url, title, id })
+        }
+        Event::Start(Tag::Link { link_type, dest_url, title, id }) if is_script_url(&dest_url) => {
+            Event::Start(Tag::Link { link_type, dest_url: CowStr::from("#"), title, id })
+        }
+        Event::Html(html) | Event::InlineHtml(html) => Event::Text(html),
+        other => other,
+    });
+
+    let mut output = String::new();
+    html::push_html(&mut output, parser);
+
+    RenderedHtml {
+        html: output,
+        local_images,
+        warnings: shortcode_warnings(content),
+    }
+}
+
+/// `javascript:` and similar URLs, which run code when followed
+fn is_script_url(url: &str) -> bool {
+    let scheme: String = url
+        .chars()
+        .filter(|c| !c.is_ascii_whitespace() && !c.is_ascii_control())
+        .take_while(|&c| c != ':')
+        .collect::<String>()
+        .to_ascii_lowercase();
+    url.contains(':') && matches!(scheme.as_str(), "javascript" | "vbscript" | "data")
+}
+
+fn resolve_image(url: &str, static_dir: &Path, bundle_dir: Option<&Path>) -> Option<PathBuf> {
+    let decoded = percent_decode(url);
+    let path = if decoded.starts_with('/') {
+        resolve_static_path(static_dir, &decoded)?
+    } else {
+        if decoded.contains("://") || decoded.starts_with("data:") {
+            return None;
+        }
+        let relative = decoded.split(['?', '#']).next().unwrap_or("");
+        if has_parent_dir(relative) {
+            return None;
+        }
+        bundle_dir?.join(relative)
+    };
+
+    path.is_file().then_some(path)
+}
+
+/// Decode `%XX` escapes, leaving malformed sequences as they are
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' && i + 2 < bytes.len() {
+            let hex = |b: u8| (b as char).to_digit(16);
+            if let (Some(high), Some(low)) = (hex(bytes[i + 1]), hex(bytes[i + 2])) {
+                decoded.push((high * 16 + low) as u8);
+                i += 3;
+                continue;
+            }
+        }
+        decoded.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).to_string()
+}
+
+/// One warning per distinct shortcode, since the preview shows them as text
+fn shortcode_warnings(content: &str) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    for shortcode in extract_shortcodes(content) {
+        if !names.contains(&shortcode.name) {
+            names.push(shortcode.name);
+        }
+    }
+
+    names
+        .into_iter()
+        .map(|name| format!("Shortcode '{}' is not rendered in the preview", name))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn render_html_resolves_images_and_warns_on_shortcodes() {
+        let dir = tempfile::tempdir().unwrap();
+        let static_dir = dir.path().join("static");
+        fs::create_dir_all(static_dir.join("images")).unwrap();
+        fs::write(static_dir.join("images/my pic.png"), b"png").unwrap();
+
+        let content = "# Title\n\n![pic](/images/my%20pic.png) ![gone](/images/missing.png)\n\n{{< figure src=\"a.png\" >}}\n{{% notice %}}hi{{% /notice %}}\n{{< figure src=\"b.png\" >}}";
+        let rendered = render_html(content, &static_dir, None);
+
+        let expected = static_dir.join("images/my pic.png").to_string_lossy().to_string();
+        assert!(rendered.html.contains("<h1>Title</h1>"));
+        assert_eq!(rendered.local_images, vec![expected]);
+        assert!(rendered.html.contains("src=\"/images/missing.png\""));
+        assert_eq!(rendered.warnings.len(), 2);
+        assert!(rendered.warnings[0].contains("'figure'"));
+        assert!(rendered.warnings[1].contains("'notice'"));
+    }
+
+    #[test]
+    fn render_html_neutralizes_raw_html_and_script_links() {
+        let dir = tempfile::tempdir().unwrap();
+        let content = "<script>alert(1)</script>\n\nText <img src=x onerror=alert(1)> and [link](JavaScript:alert(1))";
+        let rendered = render_html(content, dir.path(), None);
+
+        assert!(!rendered.html.contains("<script"));
+        assert!(!rendered.html.contains("<img"));
+        assert!(!rendered.html.to_lowercase().contains("javascript:"));
+        assert!(rendered.html.contains("&lt;script&gt;"));
+        assert!(rendered.html.contains("<a href=\"#\">link</a>"));
+    }
+
+    #[test]
+    fn render_html_ignores_images_outside_static_and_bundle() {
+        let dir = tempfile::tempdir().unwrap();
+        let static_dir = dir.path().join("static");
+        let bundle_dir = dir.path().join("content/posts/hello");
+        fs::create_dir_all(&static_dir).unwrap();
+        fs::create_dir_all(&bundle_dir).unwrap();
+        fs::write(dir.path().join("secret.png"), b"png").unwrap();
+
+        let content = "![a](../../../secret.png) ![b](/../secret.png) ![c](/%2E%2E/secret.png)";
+        let rendered = render_html(content, &static_dir, Some(&bundle_dir));
+
+        assert!(rendered.local_images.is_empty());
+        assert!(!rendered.html.contains(&dir.path().to_string_lossy().to_string()));
+    }
+}
@@ -0,0 +1,132 @@
+// Per-project preferences
+// Stored in .hugo-bros/settings.json next to the other app files of a project.
+// The env block may hold secrets such as deploy tokens, so saving also writes a
+// .hugo-bros/.gitignore that keeps settings.json out of the project repository.
+
+use crate::config::SlugMode;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectSettings {
+    /// Folder under content/ that holds posts, e.g. `blog`. When unset,
+    /// `content/posts`, then `content/post`, then content/ itself is used.
+    #[serde(default)]
+    pub posts_section: Option<String>,
+    /// Environment variables set for every Hugo command run in the project;
+    /// per-call variables override these
+    #[serde(default)]
+    pub env: HashMap<String, String>,
+    /// Overrides the app config's `slug_mode` for this project
+    #[serde(default)]
+    pub slug_mode: Option<SlugMode>,
+    /// Overrides the app config's `use_trash` for this project
+    #[serde(default)]
+    pub use_trash: Option<bool>,
+}
+
+impl ProjectSettings {
+    fn settings_path(project_path: &Path) -> PathBuf {
+        project_path.join(".hugo-bros").join("settings.json")
+    }
+
+    /// Settings for a project; defaults when the file doesn't exist
+    pub fn load(project_path: &Path) -> Result<Self, String> {
+        let settings_path = Self::settings_path(project_path);
+
+        if !settings_path.exists() {
+            return Ok(Self::default());
+        }
+
+        let settings_str = fs::read_to_string(&settings_path)
+            .map_err(|e| format!("Failed to read project settings: {}", e))?;
+
+        serde_json::from_str(&settings_str)
+            .map_err(|e| format!("Failed to parse project settings: {}", e))
+    }
+
+    pub fn save(&self, project_path: &Path) -> Result<(), String> {
+        let settings_path = Self::settings_path(project_path);
+        if let Some(parent) = settings_path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create .hugo-bros directory: {}", e))?;
+        }
+
+        let settings_str = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Failed to serialize project settings: {}", e))?;
+
+        crate::files::write_file_atomic(&settings_path, settings_str.as_bytes())
+            .map_err(|e| format!("Failed to write project settings: {}", e))?;
+
+        Self::ignore_settings_file(project_path)
+    }
+
+    /// Add `settings.json` to `.hugo-bros/.gitignore`, keeping existing entries
+    fn ignore_settings_file(project_path: &Path) -> Result<(), String> {
+        let gitignore_path = project_path.join(".hugo-bros").join(".gitignore");
+        let existing = match fs::read_to_string(&gitignore_path) {
+            Ok(existing) => existing,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(format!("Failed to read .hugo-bros/.gitignore: {}", e)),
+        };
+
+        if existing.lines().any(|line| line.trim() == "settings.json") {
+            return Ok(());
+        }
+
+        let mut updated = existing;
+        if !updated.is_empty() && !updated.ends_with('\n') {
+            updated.push('\n');
+        }
+        updated.push_str("settings.json\n");
+
+        crate::files::write_file_atomic(&gitignore_path, updated.as_bytes())
+            .map_err(|e| format!("Failed to write .hugo-bros/.gitignore: {}", e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn load_defaults_and_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let settings = ProjectSettings::load(dir.path()).unwrap();
+        assert!(settings.posts_section.is_none());
+        assert!(settings.env.is_empty());
+
+        let settings = ProjectSettings {
+            posts_section: Some("blog".to_string()),
+            env: HashMap::from([("HUGO_ENV".to_string(), "staging".to_string())]),
+            slug_mode: Some(SlugMode::Unicode),
+            use_trash: None,
+        };
+        settings.save(dir.path()).unwrap();
+
+        let loaded = ProjectSettings::load(dir.path()).unwrap();
+        assert_eq!(loaded.posts_section.as_deref(), Some("blog"));
+        assert_eq!(loaded.env["HUGO_ENV"], "staging");
+        assert_eq!(loaded.slug_mode, Some(SlugMode::Unicode));
+        assert!(loaded.use_trash.is_none());
+    }
+
+    #[test]
+    fn save_gitignores_settings_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let gitignore_path = dir.path().join(".hugo-bros").join(".gitignore");
+        fs::create_dir_all(gitignore_path.parent().unwrap()).unwrap();
+        fs::write(&gitignore_path, "backups/").unwrap();
+
+        ProjectSettings::default().save(dir.path()).unwrap();
+        ProjectSettings::default().save(dir.path()).unwrap();
+
+        assert_eq!(
+            fs::read_to_string(&gitignore_path).unwrap(),
+            "backups/\nsettings.json\n"
+        );
+    }
+}
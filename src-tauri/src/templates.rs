@@ -0,0 +1,151 @@
+// Post templates: named frontmatter + body presets for new posts
+// Stored in .hugo-bros/templates.json, as a lighter alternative to Hugo archetypes
+
+use crate::markdown::{Frontmatter, MarkdownDocument};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PostTemplate {
+    pub name: String,
+    /// Frontmatter of new posts. `title` and `date` may be left out; string
+    /// values can use the `{title}` and `{date}` placeholders.
+    #[serde(default)]
+    pub frontmatter: serde_yaml::Mapping,
+    /// Body of new posts, with the same placeholders
+    #[serde(default)]
+    pub content: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+struct TemplatesFile {
+    #[serde(default)]
+    templates: Vec<PostTemplate>,
+}
+
+fn templates_path(project_path: &Path) -> PathBuf {
+    project_path.join(".hugo-bros").join("templates.json")
+}
+
+/// Templates of a project; empty when the file doesn't exist
+pub fn load_templates(project_path: &Path) -> Result<Vec<PostTemplate>, String> {
+    let path = templates_path(project_path);
+
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let templates_str = fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read templates: {}", e))?;
+
+    let file: TemplatesFile = serde_json::from_str(&templates_str)
+        .map_err(|e| format!("Failed to parse templates: {}", e))?;
+
+    Ok(file.templates)
+}
+
+/// Add a template, replacing any existing one with the same name
+pub fn save_template(project_path: &Path, template: PostTemplate) -> Result<(), String> {
+    let mut templates = load_templates(project_path)?;
+    match templates.iter_mut().find(|t| t.name == template.name) {
+        Some(existing) => *existing = template,
+        None => templates.push(template),
+    }
+
+    let path = templates_path(project_path);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create .hugo-bros directory: {}", e))?;
+    }
+
+    let templates_str = serde_json::to_string_pretty(&TemplatesFile { templates })
+        .map_err(|e| format!("Failed to serialize templates: {}", e))?;
+
+    crate::files::write_file_atomic(&path, templates_str.as_bytes())
+        .map_err(|e| format!("Failed to write templates: {}", e))
+}
+
+/// Frontmatter and body for a new post from a template. The given title
+/// always wins over the template's; its date is used only if it has none.
+pub fn apply_template(
+    template: &PostTemplate,
+    title: &str,
+    date: &str,
+) -> Result<(Frontmatter, String), String> {
+    let substitute = |text: &str| text.replace("{title}", title).replace("{date}", date);
+
+    let mut mapping = serde_yaml::Mapping::new();
+    mapping.insert("title".into(), title.into());
+    mapping.insert("date".into(), date.into());
+    for (key, value) in &template.frontmatter {
+        if key.as_str() == Some("title") {
+            continue;
+        }
+        mapping.insert(key.clone(), substitute_value(value, &substitute));
+    }
+
+    let frontmatter_yaml = serde_yaml::to_string(&mapping)
+        .map_err(|e| format!("Failed to serialize template frontmatter: {}", e))?;
+    let (doc, _) = MarkdownDocument::parse(&format!("---\n{}---\n", frontmatter_yaml))?;
+
+    Ok((doc.frontmatter, substitute(&template.content)))
+}
+
+fn substitute_value(value: &serde_yaml::Value, substitute: &impl Fn(&str) -> String) -> serde_yaml::Value {
+    match value {
+        serde_yaml::Value::String(text) => serde_yaml::Value::String(substitute(text)),
+        serde_yaml::Value::Sequence(items) => {
+            serde_yaml::Value::Sequence(items.iter().map(|item| substitute_value(item, substitute)).collect())
+        }
+        serde_yaml::Value::Mapping(mapping) => serde_yaml::Value::Mapping(
+            mapping
+                .iter()
+                .map(|(key, item)| (key.clone(), substitute_value(item, substitute)))
+                .collect(),
+        ),
+        other => other.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn apply_template_substitutes_placeholders() {
+        let template = PostTemplate {
+            name: "Review".to_string(),
+            frontmatter: serde_yaml::from_str(
+                "title: ignored\ndate: 2020-01-01\ncategories: [Reviews]\ndescription: Review of {title}\n",
+            )
+            .unwrap(),
+            content: "# {title}\n\nWritten {date}.".to_string(),
+        };
+
+        let (frontmatter, content) = apply_template(&template, "Dune", "2024-05-01").unwrap();
+        assert_eq!(frontmatter.title, "Dune");
+        assert_eq!(frontmatter.date, "2020-01-01");
+        assert_eq!(frontmatter.categories, vec!["Reviews".to_string()]);
+        assert_eq!(frontmatter.description.as_deref(), Some("Review of Dune"));
+        assert_eq!(content, "# Dune\n\nWritten 2024-05-01.");
+    }
+
+    #[test]
+    fn save_template_replaces_by_name() {
+        let dir = tempfile::tempdir().unwrap();
+        let template = |content: &str| PostTemplate {
+            name: "Note".to_string(),
+            frontmatter: serde_yaml::Mapping::new(),
+            content: content.to_string(),
+        };
+
+        save_template(dir.path(), template("first")).unwrap();
+        save_template(dir.path(), template("second")).unwrap();
+
+        let templates = load_templates(dir.path()).unwrap();
+        assert_eq!(templates.len(), 1);
+        assert_eq!(templates[0].content, "second");
+    }
+}
@@ -0,0 +1,170 @@
+// Project trash for recoverable deletes
+// Deleted files are moved to .hugo-bros/trash/<timestamp>/<original-relative-path>
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TrashEntry {
+    pub id: String,
+    pub original_path: String,
+    /// Milliseconds since the epoch
+    pub deleted_at: i64,
+    pub size: u64,
+}
+
+fn trash_dir(project_path: &Path) -> PathBuf {
+    project_path.join(".hugo-bros").join("trash")
+}
+
+/// Move a file inside the project into the trash, keeping its relative path
+pub fn move_to_trash(project_path: &Path, file_path: &Path) -> Result<(), String> {
+    let relative = file_path
+        .strip_prefix(project_path)
+        .map_err(|_| "File is outside the project".to_string())?;
+
+    let timestamp = chrono::Utc::now().timestamp_millis();
+    let dest = trash_dir(project_path)
+        .join(timestamp.to_string())
+        .join(relative);
+
+    if let Some(parent) = dest.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create trash directory: {}", e))?;
+    }
+
+    fs::rename(file_path, &dest)
+        .map_err(|e| format!("Failed to move file to trash: {}", e))
+}
+
+pub fn list_trash(project_path: &Path) -> Result<Vec<TrashEntry>, String> {
+    let trash_dir = trash_dir(project_path);
+
+    if !trash_dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut entries = Vec::new();
+
+    for entry in WalkDir::new(&trash_dir)
+        .min_depth(2)
+        .into_iter()
+        .filter_map(|e| e.ok())
+    {
+        let path = entry.path();
+        if !path.is_file() {
+            continue;
+        }
+
+        let Ok(relative) = path.strip_prefix(&trash_dir) else {
+            continue;
+        };
+        let mut components = relative.components();
+        let deleted_at = components
+            .next()
+            .and_then(|c| c.as_os_str().to_str())
+            .and_then(|s| s.parse::<i64>().ok())
+            .unwrap_or(0);
+        let original_path = components.as_path().to_string_lossy().replace('\\', "/");
+
+        entries.push(TrashEntry {
+            id: relative.to_string_lossy().replace('\\', "/"),
+            original_path,
+            deleted_at,
+            size: entry.metadata().map(|m| m.len()).unwrap_or(0),
+        });
+    }
+
+    entries.sort_by_key(|e| std::cmp::Reverse(e.deleted_at));
+
+    Ok(entries)
+}
+
+/// Move a trashed file back to where it was deleted from.
+/// `entry_id` must already be validated as a safe relative path.
+pub fn restore_entry(project_path: &Path, entry_id: &Path) -> Result<String, String> {
+    let trash_dir = trash_dir(project_path);
+    let source = trash_dir.join(entry_id);
+
+    if !source.is_file() {
+        return Err("Trash entry not found".to_string());
+    }
+
+    let mut components = entry_id.components();
+    let batch = components.next().ok_or("Invalid trash entry")?;
+    let original_relative = components.as_path().to_path_buf();
+    if original_relative.as_os_str().is_empty() {
+        return Err("Invalid trash entry".to_string());
+    }
+
+    let original = project_path.join(&original_relative);
+    if original.exists() {
+        return Err("A file already exists at the original location".to_string());
+    }
+
+    if let Some(parent) = original.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create directory: {}", e))?;
+    }
+
+    fs::rename(&source, &original)
+        .map_err(|e| format!("Failed to restore file: {}", e))?;
+
+    // Drop directories left empty by the restore, up to the trash root
+    let batch_dir = trash_dir.join(batch);
+    let mut dir = source.parent();
+    while let Some(current) = dir {
+        if !current.starts_with(&batch_dir) || fs::remove_dir(current).is_err() {
+            break;
+        }
+        dir = current.parent();
+    }
+
+    Ok(original_relative.to_string_lossy().replace('\\', "/"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn move_list_and_restore_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("content/posts/hello.md");
+        fs::create_dir_all(file.parent().unwrap()).unwrap();
+        fs::write(&file, "Hello").unwrap();
+
+        move_to_trash(dir.path(), &file).unwrap();
+        assert!(!file.exists());
+
+        let entries = list_trash(dir.path()).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].original_path, "content/posts/hello.md");
+        assert_eq!(entries[0].size, 5);
+        assert!(entries[0].deleted_at > 0);
+
+        let restored = restore_entry(dir.path(), Path::new(&entries[0].id)).unwrap();
+        assert_eq!(restored, "content/posts/hello.md");
+        assert_eq!(fs::read_to_string(&file).unwrap(), "Hello");
+        assert!(list_trash(dir.path()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn restore_refuses_to_overwrite_a_recreated_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("content/about.md");
+        fs::create_dir_all(file.parent().unwrap()).unwrap();
+        fs::write(&file, "Old").unwrap();
+
+        move_to_trash(dir.path(), &file).unwrap();
+        fs::write(&file, "New").unwrap();
+
+        let entries = list_trash(dir.path()).unwrap();
+        assert!(restore_entry(dir.path(), Path::new(&entries[0].id)).is_err());
+        assert_eq!(fs::read_to_string(&file).unwrap(), "New");
+        assert_eq!(list_trash(dir.path()).unwrap().len(), 1);
+    }
+}
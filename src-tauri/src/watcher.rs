@@ -0,0 +1,140 @@
+// Project file watcher
+// Watches content/ and static/ and emits `project://changed` when files change
+// outside the app
+
+use notify::event::ModifyKind;
+use notify::{EventKind, RecursiveMode};
+use notify_debouncer_full::{new_debouncer, DebounceEventResult, Debouncer, RecommendedCache};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+
+/// Quiet period before a burst of file events is reported
+const DEBOUNCE_TIMEOUT: Duration = Duration::from_millis(300);
+
+/// Directories inside the project that are watched
+const WATCHED_DIRS: [&str; 2] = ["content", "static"];
+
+type ProjectDebouncer = Debouncer<notify::RecommendedWatcher, RecommendedCache>;
+
+// Only one project is open at a time, so there is at most one watcher.
+// Dropping the debouncer stops its watcher and thread.
+lazy_static::lazy_static! {
+    static ref PROJECT_WATCHER: Mutex<Option<(String, ProjectDebouncer)>> = Mutex::new(None);
+}
+
+#[derive(Debug, serde::Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeKind {
+    Created,
+    Modified,
+    Removed,
+}
+
+#[derive(Debug, serde::Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct FileChange {
+    pub path: String,
+    pub kind: ChangeKind,
+}
+
+#[derive(serde::Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectChangedEvent {
+    pub project_path: String,
+    pub changes: Vec<FileChange>,
+}
+
+/// Start watching a project, replacing any watcher for a previous project
+pub fn start(app: AppHandle, project_path: &Path) -> Result<(), String> {
+    let project_key = project_path.to_string_lossy().to_string();
+
+    let mut watcher = PROJECT_WATCHER.lock().unwrap();
+    if watcher.as_ref().is_some_and(|(path, _)| path == &project_key) {
+        return Ok(());
+    }
+    // Stop the old watcher before starting a new one
+    *watcher = None;
+
+    let mut roots = vec![project_path.to_path_buf()];
+    if let Ok(canonical) = project_path.canonicalize() {
+        roots.push(canonical);
+    }
+    let event_project = project_key.clone();
+    let mut debouncer = new_debouncer(DEBOUNCE_TIMEOUT, None, move |result: DebounceEventResult| {
+        let Ok(events) = result else {
+            return;
+        };
+
+        // Collapse the burst to one change per path, keeping the latest kind
+        let mut changes: BTreeMap<String, ChangeKind> = BTreeMap::new();
+        for event in events {
+            for path in &event.paths {
+                let Some(kind) = change_kind(&event.kind, path) else {
+                    continue;
+                };
+                if let Some(relative) = relative_path(&roots, path) {
+                    changes.insert(relative, kind);
+                }
+            }
+        }
+
+        if changes.is_empty() {
+            return;
+        }
+        crate::content_cache::invalidate(&roots[0]);
+
+        let _ = app.emit(
+            "project://changed",
+            ProjectChangedEvent {
+                project_path: event_project.clone(),
+                changes: changes
+                    .into_iter()
+                    .map(|(path, kind)| FileChange { path, kind })
+                    .collect(),
+            },
+        );
+    })
+    .map_err(|e| format!("Failed to create file watcher: {}", e))?;
+
+    for dir in WATCHED_DIRS {
+        let dir_path = project_path.join(dir);
+        if dir_path.is_dir() {
+            debouncer
+                .watch(&dir_path, RecursiveMode::Recursive)
+                .map_err(|e| format!("Failed to watch {}: {}", dir, e))?;
+        }
+    }
+
+    *watcher = Some((project_key, debouncer));
+
+    Ok(())
+}
+
+pub fn stop() {
+    *PROJECT_WATCHER.lock().unwrap() = None;
+}
+
+fn change_kind(kind: &EventKind, path: &Path) -> Option<ChangeKind> {
+    match kind {
+        EventKind::Create(_) => Some(ChangeKind::Created),
+        EventKind::Remove(_) => Some(ChangeKind::Removed),
+        // A rename reports both ends; tell them apart by what's on disk now
+        EventKind::Modify(ModifyKind::Name(_)) => Some(if path.exists() {
+            ChangeKind::Created
+        } else {
+            ChangeKind::Removed
+        }),
+        EventKind::Modify(_) => Some(ChangeKind::Modified),
+        _ => None,
+    }
+}
+
+/// Path relative to the project root. Some backends report canonical paths
+/// (e.g. /private/var on macOS), so the canonical root is tried too.
+fn relative_path(roots: &[PathBuf], path: &Path) -> Option<String> {
+    let relative = roots.iter().find_map(|root| path.strip_prefix(root).ok())?;
+    Some(relative.to_string_lossy().replace('\\', "/"))
+}
@@ -1,15 +1,54 @@
 // Backend service for Tauri command invocations
-import { invoke } from '@tauri-apps/api/core';
+import { invoke, convertFileSrc } from '@tauri-apps/api/core';
 import type {
   Post,
   Page,
   Draft,
   ImageInfo,
   StaticEntry,
+  StaticFilter,
   HugoConfig,
   FrontmatterConfig,
   AppConfig,
-  CommandOutput
+  CommandOutput,
+  ServerStart,
+  TrashEntry,
+  BundleResource,
+  HugoBinary,
+  HugoVersion,
+  Frontmatter,
+  FrontmatterIssue,
+  PreviewImageReport,
+  BrokenLink,
+  TermCount,
+  CopiedImage,
+  ImageResizeOptions,
+  BackupEntry,
+  RenderedHtml,
+  ShortcodeUsage,
+  ConfigIssue,
+  MenuEntry,
+  PostList,
+  ListPostsOptions,
+  SortSpec,
+  ContentStats,
+  ResolvedImage,
+  SectionInfo,
+  ServerOptions,
+  ServerInfo,
+  FrontmatterChange,
+  BatchUpdateResult,
+  ImportResult,
+  PostTemplate,
+  GitFileStatus,
+  PostDiff,
+  PostMeta,
+  BackendErrorCode,
+  ImageUsage,
+  ImageCopyResult,
+  ImageVerification,
+  ProjectSettings,
+  RelatedPost
 } from '$lib/types';
 
 export class BackendService {
@@ -49,9 +88,53 @@ export class BackendService {
     return path;
   }
 
-  async getProjectConfig(): Promise<HugoConfig> {
+  /** Show a file or folder (project-relative or absolute) in the OS file manager */
+  async revealInFileManager(path: string): Promise<void> {
     const projectPath = this.ensureProject();
-    return invoke<HugoConfig>('get_project_config', { projectPath });
+    await invoke('reveal_in_file_manager', { projectPath, path });
+  }
+
+  /** `environment` overlays config/<environment>/ as `hugo --environment` does */
+  async getProjectConfig(environment?: string): Promise<HugoConfig> {
+    const projectPath = this.ensureProject();
+    return invoke<HugoConfig>('get_project_config', { projectPath, environment });
+  }
+
+  async saveProjectConfig(config: HugoConfig): Promise<void> {
+    const projectPath = this.ensureProject();
+    await invoke('save_project_config', { projectPath, config });
+  }
+
+  async validateProjectConfig(): Promise<ConfigIssue[]> {
+    const projectPath = this.ensureProject();
+    return invoke<ConfigIssue[]>('validate_project_config', { projectPath });
+  }
+
+  /** Per-project preferences; defaults when the project has none saved */
+  async getProjectSettings(): Promise<ProjectSettings> {
+    const projectPath = this.ensureProject();
+    return invoke<ProjectSettings>('get_project_settings', { projectPath });
+  }
+
+  async saveProjectSettings(settings: ProjectSettings): Promise<void> {
+    const projectPath = this.ensureProject();
+    await invoke('save_project_settings', { projectPath, settings });
+  }
+
+  /** Folder under content/ holding posts; omit to detect posts/ or post/ */
+  async setPostsSection(section?: string): Promise<void> {
+    const projectPath = this.ensureProject();
+    await invoke('set_posts_section', { projectPath, section });
+  }
+
+  async getParams(): Promise<Record<string, unknown>> {
+    const projectPath = this.ensureProject();
+    return invoke<Record<string, unknown>>('get_params', { projectPath });
+  }
+
+  async getMenus(): Promise<MenuEntry[]> {
+    const projectPath = this.ensureProject();
+    return invoke<MenuEntry[]>('get_menus', { projectPath });
   }
 
   async getFrontmatterConfig(): Promise<FrontmatterConfig> {
@@ -59,18 +142,53 @@ export class BackendService {
     return invoke<FrontmatterConfig>('get_frontmatter_config', { projectPath });
   }
 
-  async generateFrontmatterConfig(): Promise<FrontmatterConfig> {
+  /** Infer the config from posts, pages and (unless disabled) drafts */
+  async generateFrontmatterConfig(includeDrafts?: boolean): Promise<FrontmatterConfig> {
+    const projectPath = this.ensureProject();
+    return invoke<FrontmatterConfig>('generate_frontmatter_config_command', { projectPath, includeDrafts });
+  }
+
+  /** Regenerate the config from current content; the old one is kept as a .bak */
+  async regenerateFrontmatterConfig(merge: boolean, includeDrafts?: boolean): Promise<FrontmatterConfig> {
+    const projectPath = this.ensureProject();
+    return invoke<FrontmatterConfig>('regenerate_frontmatter_config', { projectPath, merge, includeDrafts });
+  }
+
+  /** What regenerateFrontmatterConfig would write, without writing it */
+  async previewFrontmatterConfig(merge: boolean, includeDrafts?: boolean): Promise<FrontmatterConfig> {
     const projectPath = this.ensureProject();
-    return invoke<FrontmatterConfig>('generate_frontmatter_config_command', { projectPath });
+    return invoke<FrontmatterConfig>('preview_frontmatter_config', { projectPath, merge, includeDrafts });
+  }
+
+  /** Saves only when no errors are returned */
+  async saveFrontmatterConfig(config: FrontmatterConfig): Promise<FrontmatterIssue[]> {
+    const projectPath = this.ensureProject();
+    return invoke<FrontmatterIssue[]>('save_frontmatter_config', { projectPath, config });
+  }
+
+  async validateFrontmatter(frontmatter: Frontmatter): Promise<FrontmatterIssue[]> {
+    const projectPath = this.ensureProject();
+    return invoke<FrontmatterIssue[]>('validate_frontmatter', { projectPath, frontmatter });
   }
 
   // ====================
   // Posts Commands
   // ====================
 
-  async listPosts(): Promise<Post[]> {
+  async listPosts(options: ListPostsOptions = {}): Promise<PostList> {
     const projectPath = this.ensureProject();
-    return invoke<Post[]>('list_posts', { projectPath });
+    return invoke<PostList>('list_posts', { projectPath, ...options });
+  }
+
+  async listPostsMetadata(): Promise<PostMeta[]> {
+    const projectPath = this.ensureProject();
+    return invoke<PostMeta[]>('list_posts_metadata', { projectPath });
+  }
+
+  /** Published posts sharing the most tags and categories; `limit` defaults to 5 */
+  async getRelatedPosts(postId: string, limit?: number): Promise<RelatedPost[]> {
+    const projectPath = this.ensureProject();
+    return invoke<RelatedPost[]>('get_related_posts', { projectPath, postId, limit });
   }
 
   async getPost(postId: string): Promise<Post> {
@@ -78,9 +196,30 @@ export class BackendService {
     return invoke<Post>('get_post', { projectPath, postId });
   }
 
-  async savePost(post: Post): Promise<void> {
+  async listTranslations(postId: string): Promise<Post[]> {
+    const projectPath = this.ensureProject();
+    return invoke<Post[]>('list_translations', { projectPath, postId });
+  }
+
+  /**
+   * Save a post and return its new modification time. With
+   * `expectedModifiedAt`, the save fails with a conflict error (see
+   * isConflictError) if the file changed on disk since then.
+   */
+  async savePost(post: Post, expectedModifiedAt?: number): Promise<number> {
     const projectPath = this.ensureProject();
-    await invoke('save_post', { projectPath, post });
+    return invoke<number>('save_post', { projectPath, post, expectedModifiedAt });
+  }
+
+  /** SHA-256 of a project file's bytes, for telling whether it changed */
+  async getContentHash(fileId: string): Promise<string> {
+    const projectPath = this.ensureProject();
+    return invoke<string>('get_content_hash', { projectPath, fileId });
+  }
+
+  async diffPost(postId: string, editorContent: string): Promise<PostDiff> {
+    const projectPath = this.ensureProject();
+    return invoke<PostDiff>('diff_post', { projectPath, postId, editorContent });
   }
 
   async getPage(pageId: string): Promise<Page> {
@@ -88,9 +227,9 @@ export class BackendService {
     return invoke<Page>('get_page', { projectPath, pageId });
   }
 
-  async savePage(page: Page): Promise<void> {
+  async savePage(page: Page, expectedModifiedAt?: number): Promise<number> {
     const projectPath = this.ensureProject();
-    await invoke('save_page', { projectPath, page });
+    return invoke<number>('save_page', { projectPath, page, expectedModifiedAt });
   }
 
   async deletePage(pageId: string): Promise<void> {
@@ -103,6 +242,36 @@ export class BackendService {
     return invoke<Post>('create_post', { projectPath, title });
   }
 
+  async createPostWithFrontmatter(frontmatter: Frontmatter, content: string): Promise<Post> {
+    const projectPath = this.ensureProject();
+    return invoke<Post>('create_post_with_frontmatter', { projectPath, frontmatter, content });
+  }
+
+  async renamePost(postId: string, newTitle: string): Promise<Post> {
+    const projectPath = this.ensureProject();
+    return invoke<Post>('rename_post', { projectPath, postId, newTitle });
+  }
+
+  async movePost(postId: string, targetRelativeDir: string): Promise<Post> {
+    const projectPath = this.ensureProject();
+    return invoke<Post>('move_post', { projectPath, postId, targetRelativeDir });
+  }
+
+  async toggleDraft(postId: string): Promise<Post> {
+    const projectPath = this.ensureProject();
+    return invoke<Post>('toggle_draft', { projectPath, postId });
+  }
+
+  async batchUpdateFrontmatter(postIds: string[], changes: FrontmatterChange[]): Promise<BatchUpdateResult[]> {
+    const projectPath = this.ensureProject();
+    return invoke<BatchUpdateResult[]>('batch_update_frontmatter', { projectPath, postIds, changes });
+  }
+
+  async copyResourceToBundle(postId: string, sourcePath: string): Promise<BundleResource> {
+    const projectPath = this.ensureProject();
+    return invoke<BundleResource>('copy_resource_to_bundle', { projectPath, postId, sourcePath });
+  }
+
   async deletePost(postId: string): Promise<void> {
     const projectPath = this.ensureProject();
     await invoke('delete_post', { projectPath, postId });
@@ -112,14 +281,36 @@ export class BackendService {
   // Pages Commands
   // ====================
 
-  async createPage(title: string): Promise<Page> {
+  /** `bundle: false` creates content/<slug>.md instead of a <slug>/index.md bundle */
+  async createPage(title: string, bundle = true): Promise<Page> {
+    const projectPath = this.ensureProject();
+    return invoke<Page>('create_page', { projectPath, title, bundle });
+  }
+
+  async listPages(sort?: SortSpec): Promise<Page[]> {
+    const projectPath = this.ensureProject();
+    return invoke<Page[]>('list_pages', { projectPath, sort });
+  }
+
+  // ====================
+  // Section Commands
+  // ====================
+
+  async listSections(): Promise<SectionInfo[]> {
     const projectPath = this.ensureProject();
-    return invoke<Page>('create_page', { projectPath, title });
+    return invoke<SectionInfo[]>('list_sections', { projectPath });
   }
 
-  async listPages(): Promise<Page[]> {
+  /** Sections without an _index.md yet come back as an unsaved page */
+  async getSection(sectionPath: string): Promise<Page> {
     const projectPath = this.ensureProject();
-    return invoke<Page[]>('list_pages', { projectPath });
+    return invoke<Page>('get_section', { projectPath, sectionPath });
+  }
+
+  /** Omit `expectedModifiedAt` for a section that has no _index.md yet */
+  async saveSection(section: Page, expectedModifiedAt?: number): Promise<number> {
+    const projectPath = this.ensureProject();
+    return invoke<number>('save_section', { projectPath, section, expectedModifiedAt });
   }
 
   // ====================
@@ -136,9 +327,9 @@ export class BackendService {
     return invoke<Draft>('get_draft', { projectPath, draftId });
   }
 
-  async saveDraft(draft: Draft): Promise<void> {
+  async saveDraft(draft: Draft, expectedModifiedAt?: number): Promise<number> {
     const projectPath = this.ensureProject();
-    await invoke('save_draft', { projectPath, draft });
+    return invoke<number>('save_draft', { projectPath, draft, expectedModifiedAt });
   }
 
   async deleteDraft(draftId: string): Promise<void> {
@@ -146,6 +337,46 @@ export class BackendService {
     await invoke('delete_draft', { projectPath, draftId });
   }
 
+  async publishDraft(draftId: string, refreshDate?: boolean): Promise<Post> {
+    const projectPath = this.ensureProject();
+    return invoke<Post>('publish_draft', { projectPath, draftId, refreshDate });
+  }
+
+  async unpublishPost(postId: string): Promise<Draft> {
+    const projectPath = this.ensureProject();
+    return invoke<Draft>('unpublish_post', { projectPath, postId });
+  }
+
+  async createDraftFromPost(postId: string): Promise<Draft> {
+    const projectPath = this.ensureProject();
+    return invoke<Draft>('create_draft_from_post', { projectPath, postId });
+  }
+
+  async listTemplates(): Promise<PostTemplate[]> {
+    const projectPath = this.ensureProject();
+    return invoke<PostTemplate[]>('list_templates', { projectPath });
+  }
+
+  async saveTemplate(template: PostTemplate): Promise<void> {
+    const projectPath = this.ensureProject();
+    await invoke('save_template', { projectPath, template });
+  }
+
+  async createPostFromTemplate(templateName: string, title: string): Promise<Post> {
+    const projectPath = this.ensureProject();
+    return invoke<Post>('create_post_from_template', { projectPath, templateName, title });
+  }
+
+  async gitStatus(): Promise<GitFileStatus[]> {
+    const projectPath = this.ensureProject();
+    return invoke<GitFileStatus[]>('git_status', { projectPath });
+  }
+
+  async gitCommit(message: string, paths: string[]): Promise<string> {
+    const projectPath = this.ensureProject();
+    return invoke<string>('git_commit', { projectPath, message, paths });
+  }
+
   async listDrafts(): Promise<Draft[]> {
     const projectPath = this.ensureProject();
     return invoke<Draft[]>('list_drafts', { projectPath });
@@ -160,9 +391,23 @@ export class BackendService {
     return invoke<ImageInfo[]>('list_images', { projectPath });
   }
 
-  async listStaticEntries(dir?: string): Promise<StaticEntry[]> {
+  async listStaticEntries(
+    dir?: string,
+    filter?: StaticFilter,
+    includeDirSizes?: boolean
+  ): Promise<StaticEntry[]> {
+    const projectPath = this.ensureProject();
+    return invoke<StaticEntry[]>('list_static_entries', {
+      projectPath,
+      dir,
+      filter,
+      includeDirSizes
+    });
+  }
+
+  async listStaticTree(maxDepth?: number, filter?: StaticFilter): Promise<StaticEntry[]> {
     const projectPath = this.ensureProject();
-    return invoke<StaticEntry[]>('list_static_entries', { projectPath, dir });
+    return invoke<StaticEntry[]>('list_static_tree', { projectPath, maxDepth, filter });
   }
 
   async createStaticFolder(parentDir: string, name: string): Promise<string> {
@@ -175,9 +420,77 @@ export class BackendService {
     await invoke('delete_static_entry', { projectPath, relativePath });
   }
 
-  async copyImageToProject(sourcePath: string, targetDir?: string): Promise<string> {
+  async moveStaticEntry(fromRelative: string, toRelative: string): Promise<string> {
+    const projectPath = this.ensureProject();
+    return invoke<string>('move_static_entry', { projectPath, fromRelative, toRelative });
+  }
+
+  async copyImageToProject(
+    sourcePath: string,
+    targetDir?: string,
+    resize?: ImageResizeOptions
+  ): Promise<CopiedImage> {
+    const projectPath = this.ensureProject();
+    return invoke<CopiedImage>('copy_image_to_project', {
+      projectPath,
+      sourcePath,
+      targetDir,
+      ...resize
+    });
+  }
+
+  /** Check that an image's content matches its extension */
+  async verifyImage(imagePath: string): Promise<ImageVerification> {
+    const projectPath = this.ensureProject();
+    return invoke<ImageVerification>('verify_image', { projectPath, imagePath });
+  }
+
+  /** Rename an image to match its real format; returns the new path */
+  async fixImageExtension(imagePath: string): Promise<string> {
+    const projectPath = this.ensureProject();
+    return invoke<string>('fix_image_extension', { projectPath, imagePath });
+  }
+
+  /** Copy several images at once; failures are reported per file */
+  async copyImagesToProject(
+    sourcePaths: string[],
+    targetDir?: string,
+    resize?: ImageResizeOptions
+  ): Promise<ImageCopyResult[]> {
+    const projectPath = this.ensureProject();
+    return invoke<ImageCopyResult[]>('copy_images_to_project', {
+      projectPath,
+      sourcePaths,
+      targetDir,
+      ...resize
+    });
+  }
+
+  async checkPreviewImages(): Promise<PreviewImageReport[]> {
+    const projectPath = this.ensureProject();
+    return invoke<PreviewImageReport[]>('check_preview_images', { projectPath });
+  }
+
+  /** `fileId` lets bundle-relative values resolve against the post's folder */
+  async resolveImageField(value: string, fileId?: string): Promise<ResolvedImage> {
     const projectPath = this.ensureProject();
-    return invoke<string>('copy_image_to_project', { projectPath, sourcePath, targetDir });
+    return invoke<ResolvedImage>('resolve_image_field', { projectPath, value, fileId });
+  }
+
+  /**
+   * Asset URL of a cached thumbnail fitting in `maxEdge` pixels, or null when
+   * the original should be shown (SVGs and small images)
+   */
+  async getImageThumbnail(imagePath: string, maxEdge: number): Promise<string | null> {
+    const projectPath = this.ensureProject();
+    const thumbnail = await invoke<string | null>('get_image_thumbnail', { projectPath, imagePath, maxEdge });
+    return thumbnail ? convertFileSrc(thumbnail) : null;
+  }
+
+  /** Content files referencing an image URL, to warn before deleting it */
+  async getImageUsage(imageUrl: string): Promise<ImageUsage[]> {
+    const projectPath = this.ensureProject();
+    return invoke<ImageUsage[]>('get_image_usage', { projectPath, imageUrl });
   }
 
   async deleteImage(imagePath: string): Promise<void> {
@@ -185,6 +498,133 @@ export class BackendService {
     await invoke('delete_image', { projectPath, imagePath });
   }
 
+  // ====================
+  // Taxonomy Commands
+  // ====================
+
+  async listTags(): Promise<TermCount[]> {
+    const projectPath = this.ensureProject();
+    return invoke<TermCount[]>('list_tags', { projectPath });
+  }
+
+  async listCategories(): Promise<TermCount[]> {
+    const projectPath = this.ensureProject();
+    return invoke<TermCount[]>('list_categories', { projectPath });
+  }
+
+  async getContentStats(): Promise<ContentStats> {
+    const projectPath = this.ensureProject();
+    return invoke<ContentStats>('get_content_stats', { projectPath });
+  }
+
+  /** Returns the number of entries written */
+  async exportContentIndex(outputPath: string): Promise<number> {
+    const projectPath = this.ensureProject();
+    return invoke<number>('export_content_index', { projectPath, outputPath });
+  }
+
+  async renameTag(oldTag: string, newTag: string): Promise<number> {
+    const projectPath = this.ensureProject();
+    return invoke<number>('rename_tag', { projectPath, oldTag, newTag });
+  }
+
+  async renameCategory(oldCategory: string, newCategory: string): Promise<number> {
+    const projectPath = this.ensureProject();
+    return invoke<number>('rename_category', { projectPath, oldCategory, newCategory });
+  }
+
+  // ====================
+  // Link Checker
+  // ====================
+
+  async checkLinks(includeExternal?: boolean): Promise<BrokenLink[]> {
+    const projectPath = this.ensureProject();
+    return invoke<BrokenLink[]>('check_links', { projectPath, includeExternal });
+  }
+
+  // ====================
+  // Trash Commands
+  // ====================
+
+  async listTrash(): Promise<TrashEntry[]> {
+    const projectPath = this.ensureProject();
+    return invoke<TrashEntry[]>('list_trash', { projectPath });
+  }
+
+  async restoreTrashEntry(entryId: string): Promise<string> {
+    const projectPath = this.ensureProject();
+    return invoke<string>('restore_trash_entry', { projectPath, entryId });
+  }
+
+  // ====================
+  // Preview Commands
+  // ====================
+
+  /**
+   * Renders a post body to HTML. Local images come back as file paths and are
+   * mapped to asset URLs here so the result can be shown directly.
+   */
+  async renderMarkdownToHtml(content: string, fileId?: string): Promise<RenderedHtml> {
+    const projectPath = this.ensureProject();
+    const rendered = await invoke<RenderedHtml>('render_markdown_to_html', {
+      projectPath,
+      content,
+      fileId
+    });
+    const localImages = new Set(rendered.localImages);
+    const html = rendered.html.replace(/src="([^"]+)"/g, (match, src: string) => {
+      const path = decodeURIComponent(src);
+      return localImages.has(path) ? `src="${convertFileSrc(path)}"` : match;
+    });
+    return { ...rendered, html };
+  }
+
+  async listShortcodesUsed(): Promise<ShortcodeUsage[]> {
+    const projectPath = this.ensureProject();
+    return invoke<ShortcodeUsage[]>('list_shortcodes_used', { projectPath });
+  }
+
+  // ====================
+  // Backup Commands
+  // ====================
+
+  async listBackups(fileId: string): Promise<BackupEntry[]> {
+    const projectPath = this.ensureProject();
+    return invoke<BackupEntry[]>('list_backups', { projectPath, fileId });
+  }
+
+  async restoreBackup(backupId: string): Promise<string> {
+    const projectPath = this.ensureProject();
+    return invoke<string>('restore_backup', { projectPath, backupId });
+  }
+
+  async importMarkdownFiles(sourceDir: string, targetSection: string): Promise<ImportResult[]> {
+    const projectPath = this.ensureProject();
+    return invoke<ImportResult[]>('import_markdown_files', { projectPath, sourceDir, targetSection });
+  }
+
+  // ====================
+  // Watcher Commands
+  // ====================
+
+  /**
+   * Watches content/ and static/ of the current project. Changes arrive as
+   * `project://changed` events; watching another project replaces this watch.
+   */
+  async startWatchingProject(): Promise<void> {
+    const projectPath = this.ensureProject();
+    await invoke('start_watching_project', { projectPath });
+  }
+
+  async stopWatchingProject(): Promise<void> {
+    await invoke('stop_watching_project');
+  }
+
+  async invalidateContentCache(): Promise<void> {
+    const projectPath = this.ensureProject();
+    await invoke('invalidate_content_cache', { projectPath });
+  }
+
   // ====================
   // App Config Commands
   // ====================
@@ -197,28 +637,118 @@ export class BackendService {
     await invoke('save_app_config', { config });
   }
 
+  async getAppConfigPath(): Promise<string> {
+    return invoke<string>('get_app_config_path');
+  }
+
+  async resetAppConfig(): Promise<AppConfig> {
+    return invoke<AppConfig>('reset_app_config');
+  }
+
+  async removeRecentProject(projectPath: string): Promise<string[]> {
+    return invoke<string[]>('remove_recent_project', { projectPath });
+  }
+
+  async clearRecentProjects(): Promise<string[]> {
+    return invoke<string[]>('clear_recent_projects');
+  }
+
+  async pruneRecentProjects(): Promise<string[]> {
+    return invoke<string[]>('prune_recent_projects');
+  }
+
+  async pinProject(projectPath: string): Promise<string[]> {
+    return invoke<string[]>('pin_project', { projectPath });
+  }
+
+  async unpinProject(projectPath: string): Promise<string[]> {
+    return invoke<string[]>('unpin_project', { projectPath });
+  }
+
   // ====================
   // Hugo Server Commands
   // ====================
 
-  async runHugoCommand(args: string[]): Promise<CommandOutput> {
+  async runHugoCommand(args: string[], env?: Record<string, string>): Promise<CommandOutput> {
+    const projectPath = this.ensureProject();
+    return invoke<CommandOutput>('run_hugo_command', { projectPath, args, env });
+  }
+
+  /**
+   * Starts a hugo command in the background. Output arrives as `hugo://output`
+   * events and the exit code as a `hugo://complete` event, both tagged with the
+   * returned run id.
+   */
+  async runHugoCommandStreaming(args: string[], env?: Record<string, string>): Promise<string> {
     const projectPath = this.ensureProject();
-    return invoke<CommandOutput>('run_hugo_command', { projectPath, args });
+    return invoke<string>('run_hugo_command_streaming', { projectPath, args, env });
   }
 
-  async startHugoServer(): Promise<string> {
+  async startHugoServer(options: ServerOptions = {}): Promise<ServerStart> {
     const projectPath = this.ensureProject();
-    return invoke<string>('start_hugo_server', { projectPath });
+    return invoke<ServerStart>('start_hugo_server', { projectPath, options });
+  }
+
+  /** Variables every Hugo command in the project gets; per-call `env` overrides them */
+  async setHugoEnvDefaults(env: Record<string, string>): Promise<void> {
+    const projectPath = this.ensureProject();
+    await invoke('set_hugo_env_defaults', { projectPath, env });
   }
 
   async stopHugoServer(serverId: string): Promise<void> {
     await invoke('stop_hugo_server', { serverId });
   }
 
+  /** Open the server's site in the default browser */
+  async openPreview(serverId: string): Promise<void> {
+    await invoke('open_preview', { serverId });
+  }
+
+  async getHugoServerLogs(serverId: string): Promise<string[]> {
+    return invoke<string[]>('get_hugo_server_logs', { serverId });
+  }
+
+  async getHugoServerInfo(): Promise<ServerInfo | null> {
+    const projectPath = this.ensureProject();
+    return invoke<ServerInfo | null>('get_hugo_server_info', { projectPath });
+  }
+
   async isHugoServerRunning(): Promise<boolean> {
     const projectPath = this.ensureProject();
     return invoke<boolean>('is_hugo_server_running', { projectPath });
   }
+
+  async detectHugoBinary(): Promise<HugoBinary> {
+    return invoke<HugoBinary>('detect_hugo_binary');
+  }
+
+  async getHugoVersion(): Promise<HugoVersion> {
+    const projectPath = this.ensureProject();
+    return invoke<HugoVersion>('get_hugo_version', { projectPath });
+  }
+}
+
+/**
+ * Split a backend error into its code and message. Common errors are sent as
+ * `[post_not_found] Post not found`, already worded in the UI language; the
+ * code lets the frontend pick its own translation.
+ */
+export function parseBackendError(error: unknown): { code?: BackendErrorCode; message: string } {
+  const text = error instanceof Error ? error.message : typeof error === 'string' ? error : String(error);
+  const match = /^\[([a-z_]+)\] ([\s\S]*)$/.exec(text);
+  return match ? { code: match[1] as BackendErrorCode, message: match[2] } : { message: text };
+}
+
+/** Message to show for a failed call; `fallback` when the error has none */
+export function errorMessage(error: unknown, fallback: string): string {
+  if (typeof error !== 'string' && !(error instanceof Error)) return fallback;
+  return parseBackendError(error).message || fallback;
+}
+
+/** Whether a save failed because the file changed or was deleted on disk */
+export function isConflictError(error: unknown): boolean {
+  const { code } = parseBackendError(error);
+  return code === 'file_changed' || code === 'file_deleted';
 }
 
 // Singleton instance
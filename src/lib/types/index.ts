@@ -7,10 +7,33 @@ export interface Post {
   content: string;
   frontmatter: Frontmatter;
   filePath: string;
+  /** Milliseconds since the epoch */
   createdAt: number;
+  /** Milliseconds since the epoch */
   modifiedAt: number;
+  frontmatterFormat?: FrontmatterFormat;
+  layout?: DocumentLayout;
+  wordCount?: number;
+  readingTimeMinutes?: number;
+  resources?: BundleResource[];
+  /** `date` normalized to RFC3339 when it could be parsed */
+  dateIso?: string;
+  language?: string;
+  /** Set when the file isn't clean UTF-8, e.g. 'windows-1251'. Saves are refused until it is cleared, which converts the file to UTF-8 */
+  encoding?: string;
 }
 
+/** A post without its body, for list views */
+export type PostMeta = Omit<Post, 'content' | 'layout' | 'wordCount' | 'readingTimeMinutes' | 'encoding'>;
+
+export interface BundleResource {
+  name: string;
+  path: string;
+  size: number;
+}
+
+export type FrontmatterFormat = 'yaml' | 'toml' | 'json';
+
 export interface Frontmatter {
   title: string;
   date: string;
@@ -20,20 +43,44 @@ export interface Frontmatter {
   comments?: boolean;
   layout?: string;
   permalink?: string;
+  /** Overrides the last URL segment */
+  slug?: string;
+  /** Old URLs that redirect here */
+  aliases?: string[];
   description?: string;
   draft?: boolean;
+  /** Lower weights sort first; unset or 0 sorts last */
+  weight?: number;
+  publishDate?: string;
+  expiryDate?: string;
   customFields?: Record<string, unknown>;
 }
 
+/** How the file was written; pass back unchanged so saves keep it */
+export interface DocumentLayout {
+  /** Frontmatter keys in file order */
+  keyOrder?: string[];
+  /** HTML comments before the frontmatter */
+  preamble?: string;
+  lineEnding?: 'lf' | 'crlf';
+  /** Key paths of unquoted TOML datetimes */
+  tomlDatetimes?: string[][];
+}
+
 export interface FrontmatterFieldConfig {
   name: string;
   label?: string;
+  /**
+   * string, text, number, boolean, date, datetime, image, object or array;
+   * lists of one kind are 'string[]', 'number[]' or 'object[]'
+   */
   type: string;
   description?: string;
   ui?: {
     placeholder?: string;
     rows?: number;
   };
+  required?: boolean;
 }
 
 export interface FrontmatterFieldGroup {
@@ -49,6 +96,13 @@ export interface FrontmatterConfig {
   isDefault?: boolean;
   customFields: FrontmatterFieldConfig[];
   fieldGroups: FrontmatterFieldGroup[];
+  strictValidation?: boolean;
+}
+
+export interface FrontmatterIssue {
+  field: string;
+  severity: 'error' | 'warning';
+  message: string;
 }
 
 export interface Page {
@@ -57,8 +111,16 @@ export interface Page {
   content: string;
   frontmatter: Frontmatter;
   filePath: string;
+  /** Milliseconds since the epoch */
   createdAt: number;
+  /** Milliseconds since the epoch */
   modifiedAt: number;
+  frontmatterFormat?: FrontmatterFormat;
+  layout?: DocumentLayout;
+  wordCount?: number;
+  readingTimeMinutes?: number;
+  language?: string;
+  encoding?: string;
 }
 
 export interface Draft {
@@ -67,8 +129,71 @@ export interface Draft {
   content: string;
   frontmatter: Frontmatter;
   filePath: string;
+  /** Milliseconds since the epoch */
   createdAt: number;
+  /** Milliseconds since the epoch */
   modifiedAt: number;
+  frontmatterFormat?: FrontmatterFormat;
+  layout?: DocumentLayout;
+  wordCount?: number;
+  readingTimeMinutes?: number;
+  encoding?: string;
+}
+
+/** Named preset for new posts, stored in .hugo-bros/templates.json */
+export interface PostTemplate {
+  name: string;
+  /** String values may use the {title} and {date} placeholders */
+  frontmatter: Record<string, unknown>;
+  content: string;
+}
+
+export type GitChangeKind = 'added' | 'modified' | 'deleted' | 'renamed' | 'untracked' | 'conflicted';
+
+export interface GitFileStatus {
+  /** Relative to the project, like content ids */
+  path: string;
+  kind: GitChangeKind;
+  /** Already in the git index */
+  staged: boolean;
+}
+
+/** A run of differing lines; line numbers are 1-based */
+export interface DiffChange {
+  kind: 'added' | 'removed' | 'changed';
+  /** Position in the on-disk text */
+  oldStart: number;
+  oldLines: string[];
+  /** Position in the editor's text */
+  newStart: number;
+  newLines: string[];
+}
+
+export interface PostDiff {
+  /** Modification time of the file on disk, in milliseconds since the epoch */
+  modifiedAt: number;
+  changes: DiffChange[];
+}
+
+/** Codes of backend errors the UI can translate; see parseBackendError */
+export type BackendErrorCode =
+  | 'post_not_found'
+  | 'page_not_found'
+  | 'draft_not_found'
+  | 'title_required'
+  | 'hugo_config_not_found'
+  | 'content_dir_not_found'
+  | 'server_already_running'
+  | 'not_git_repository'
+  | 'file_changed'
+  | 'file_deleted'
+  | 'not_utf8';
+
+export interface ImageUsage {
+  /** Id of the post, page or draft */
+  id: string;
+  /** 1-based file lines, frontmatter included */
+  lines: number[];
 }
 
 export interface ImageInfo {
@@ -79,18 +204,28 @@ export interface ImageInfo {
   size: number;
   width?: number;
   height?: number;
+  /** Milliseconds since the epoch */
   createdAt: number;
 }
 
+/** Files listed by the static browser; folders are always included */
+export type StaticFilter = 'images' | 'all' | { extensions: string[] };
+
 export interface StaticEntry {
   name: string;
   path: string;
   kind: 'file' | 'dir';
   size: number;
+  /** Milliseconds since the epoch */
   createdAt: number;
+  /** Milliseconds since the epoch */
   modifiedAt: number;
   url?: string;
   fullPath: string;
+  /** Only set by listStaticTree */
+  children?: StaticEntry[];
+  /** Only set for folders when directory sizes were requested */
+  fileCount?: number;
 }
 
 export interface HugoConfig {
@@ -99,6 +234,7 @@ export interface HugoConfig {
   languageCode?: string;
   defaultContentLanguage?: string;
   theme?: string;
+  languages?: string[];
   raw?: unknown;
 }
 
@@ -106,12 +242,34 @@ export interface AppConfig {
   version: string;
   lastProjectPath?: string;
   recentProjects: string[];
+  pinnedProjects: string[];
   uiLanguage: string;
   theme: 'light' | 'dark' | 'auto';
   autoSaveEnabled: boolean;
   autoSaveInterval: number;
   editorFontSize: number;
   editorLineHeight: number;
+  useTrash: boolean;
+  hugoBinaryPath?: string;
+  maxBackupsPerFile: number;
+  /** 'unicode' keeps letters from any script in generated file names */
+  slugMode: 'ascii' | 'unicode';
+  /** End saved markdown files with exactly one line break */
+  ensureFinalNewline: boolean;
+  /** Reading speed behind `readingTimeMinutes` */
+  wordsPerMinute: number;
+}
+
+/** Preferences stored in the project's .hugo-bros/settings.json */
+export interface ProjectSettings {
+  /** Folder under content/ holding posts; unset detects posts/ or post/ */
+  postsSection?: string | null;
+  /** Environment variables for every Hugo command in the project */
+  env: Record<string, string>;
+  /** Overrides the app's slug mode for this project */
+  slugMode?: 'ascii' | 'unicode' | null;
+  /** Overrides the app's trash setting for this project */
+  useTrash?: boolean | null;
 }
 
 export interface CommandOutput {
@@ -120,3 +278,253 @@ export interface CommandOutput {
   stderr: string;
   exitCode: number;
 }
+
+export interface ServerOptions {
+  port?: number;
+  /** Needed for ports below 1024 */
+  allowPrivilegedPort?: boolean;
+  /** Overrides the project's default Hugo environment variables */
+  env?: Record<string, string>;
+  /** --baseURL, an http(s) URL */
+  baseUrl?: string;
+  /** --bind, the IP address to listen on */
+  bind?: string;
+  navigateToChanged?: boolean;
+  disableFastRender?: boolean;
+  /** -D, -F and -E; all off by default, as in Hugo */
+  includeDrafts?: boolean;
+  includeFuture?: boolean;
+  includeExpired?: boolean;
+}
+
+export interface ServerStart {
+  serverId: string;
+  port: number;
+}
+
+export interface ServerInfo {
+  id: string;
+  /** Known once Hugo has logged it */
+  url: string | null;
+  port: number;
+  pid: number;
+  /** Milliseconds since the epoch */
+  startedAt: number;
+}
+
+export interface TrashEntry {
+  id: string;
+  originalPath: string;
+  /** Milliseconds since the epoch */
+  deletedAt: number;
+  size: number;
+}
+
+export interface HugoBinary {
+  path: string;
+  version: string;
+}
+
+export interface HugoVersion {
+  version: string;
+  extended: boolean;
+  os: string;
+  arch: string;
+  raw: string;
+}
+
+export interface SectionInfo {
+  /** Folder relative to content/; empty for the home page */
+  path: string;
+  /** Page id of the section's _index.md, whether or not it exists yet */
+  indexId: string;
+  hasIndex: boolean;
+  title?: string;
+}
+
+export interface ResolvedImage {
+  fullPath: string;
+  /** Unset for SVG and other formats without a raster size */
+  width?: number;
+  height?: number;
+}
+
+export interface PreviewImageReport {
+  postId: string;
+  title: string;
+  value: string;
+  expectedPath: string;
+  missing: boolean;
+  width?: number;
+  height?: number;
+}
+
+export interface BrokenLink {
+  fileId: string;
+  target: string;
+  line: number;
+  kind: 'link' | 'image' | 'external';
+}
+
+export interface RelatedPost {
+  id: string;
+  title: string;
+  /** Number of shared tags and categories */
+  score: number;
+}
+
+export interface TermCount {
+  name: string;
+  count: number;
+}
+
+export interface ContentStats {
+  postCount: number;
+  pageCount: number;
+  draftCount: number;
+  /** Words across published posts */
+  totalWords: number;
+  tagCount: number;
+  categoryCount: number;
+  staticImages: number;
+  /** Bytes of every file under static/ */
+  staticSize: number;
+}
+
+export interface HugoOutputEvent {
+  runId: string;
+  stream: 'stdout' | 'stderr';
+  line: string;
+}
+
+export interface HugoCompleteEvent {
+  runId: string;
+  success: boolean;
+  exitCode: number;
+}
+
+export interface ConfigIssue {
+  key?: string;
+  severity: 'error' | 'warning';
+  message: string;
+}
+
+export interface PostList {
+  posts: Post[];
+  total: number;
+}
+
+export interface SortSpec {
+  key: 'date' | 'title' | 'modified' | 'weight';
+  ascending?: boolean;
+}
+
+export interface PostFilter {
+  /** Every tag must be present (case-insensitive) */
+  tags?: string[];
+  category?: string;
+  /** Defaults to 'published' */
+  draftState?: 'published' | 'drafts' | 'all';
+}
+
+export interface ListPostsOptions {
+  offset?: number;
+  limit?: number;
+  /** Skip reading post bodies; `content` comes back empty and `wordCount` zero */
+  metadataOnly?: boolean;
+  sort?: SortSpec;
+  filter?: PostFilter;
+}
+
+/** One edit applied by batchUpdateFrontmatter */
+export type FrontmatterChange =
+  | { op: 'set'; key: string; value: unknown }
+  | { op: 'remove'; key: string }
+  /** Adds to a list unless the value is already there */
+  | { op: 'append'; key: string; value: unknown };
+
+export interface BatchUpdateResult {
+  id: string;
+  success: boolean;
+  error: string | null;
+}
+
+export interface ImportResult {
+  /** Absolute path of the imported file */
+  source: string;
+  success: boolean;
+  /** Id of the new content file */
+  id: string | null;
+  error: string | null;
+}
+
+export interface MenuEntry {
+  menu: string;
+  name: string;
+  url?: string;
+  weight: number;
+  parent?: string;
+  identifier?: string;
+}
+
+export interface ShortcodeUsage {
+  name: string;
+  count: number;
+  files: string[];
+}
+
+export interface RenderedHtml {
+  html: string;
+  localImages: string[];
+  warnings: string[];
+}
+
+export interface BackupEntry {
+  id: string;
+  fileId: string;
+  /** Milliseconds since the epoch */
+  createdAt: number;
+  size: number;
+}
+
+export interface FileChange {
+  path: string;
+  kind: 'created' | 'modified' | 'removed';
+}
+
+export interface ProjectChangedEvent {
+  projectPath: string;
+  changes: FileChange[];
+}
+
+export interface CopiedImage {
+  url: string;
+  width?: number;
+  height?: number;
+  size: number;
+}
+
+export interface ImageVerification {
+  path: string;
+  /** Format the extension implies, e.g. 'png'; absent for .svg and the like */
+  extensionFormat?: string;
+  /** Format detected from the file's content */
+  actualFormat?: string;
+  matches: boolean;
+  /** Extension to rename to when they don't match */
+  suggestedExtension?: string;
+}
+
+export interface ImageCopyResult {
+  /** Source path as given */
+  source: string;
+  success: boolean;
+  image?: CopiedImage;
+  error?: string;
+}
+
+export interface ImageResizeOptions {
+  maxWidth?: number;
+  maxHeight?: number;
+  quality?: number;
+}